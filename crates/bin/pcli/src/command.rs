@@ -1,5 +1,6 @@
 pub use debug::DebugCmd;
 pub use init::InitCmd;
+pub use keys::KeysCmd;
 pub use query::QueryCmd;
 pub use threshold::ThresholdCmd;
 pub use tx::TxCmd;
@@ -11,6 +12,7 @@ use self::ceremony::CeremonyCmd;
 mod ceremony;
 mod debug;
 mod init;
+mod keys;
 mod query;
 mod threshold;
 mod tx;
@@ -50,6 +52,9 @@ pub enum Command {
     /// View your private chain state, like account balances.
     #[clap(subcommand, display_order = 300, visible_alias = "v")]
     View(ViewCmd),
+    /// Export and manage keys granting scoped capabilities, like detection keys.
+    #[clap(subcommand, display_order = 350)]
+    Keys(KeysCmd),
     /// Create and broadcast a transaction.
     #[clap(subcommand, display_order = 400, visible_alias = "tx")]
     Transaction(TxCmd),
@@ -74,6 +79,7 @@ impl Command {
             Command::Init(_) => true,
             Command::Transaction(cmd) => cmd.offline(),
             Command::View(cmd) => cmd.offline(),
+            Command::Keys(cmd) => cmd.offline(),
             Command::Validator(cmd) => cmd.offline(),
             Command::Query(cmd) => cmd.offline(),
             Command::Debug(cmd) => cmd.offline(),
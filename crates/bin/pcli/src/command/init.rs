@@ -5,7 +5,7 @@ use std::{
 
 use anyhow::Result;
 use camino::Utf8PathBuf;
-use penumbra_custody::threshold;
+use penumbra_custody::{cloud_kms, threshold};
 use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
 use rand_core::OsRng;
 use url::Url;
@@ -60,6 +60,9 @@ pub enum InitSubCmd {
     /// Initialize using a manual threshold signing backend.
     #[clap(subcommand, display_order = 150)]
     Threshold(ThresholdInitCmd),
+    /// Initialize using a spend key held in an external KMS or HSM.
+    #[clap(display_order = 175)]
+    CloudKms(CloudKmsInitCmd),
     // This is not accessible directly by the user, because it's impermissible to initialize the
     // governance subkey as view-only.
     #[clap(skip)]
@@ -136,6 +139,33 @@ impl SoftKmsInitCmd {
     }
 }
 
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CloudKmsInitCmd {
+    /// The full viewing key derived from the spend key held by the KMS.
+    full_viewing_key: String,
+    /// The base URL of the KMS signing API.
+    #[clap(long)]
+    endpoint: String,
+    /// The identifier of the spend authorization key within the KMS.
+    #[clap(long)]
+    key_id: String,
+    /// The name of an environment variable holding a bearer token for the KMS.
+    #[clap(long)]
+    credentials_env: Option<String>,
+}
+
+impl CloudKmsInitCmd {
+    fn config(&self) -> Result<cloud_kms::Config> {
+        Ok(cloud_kms::Config {
+            full_viewing_key: self.full_viewing_key.parse()?,
+            endpoint: self.endpoint.clone(),
+            key_id: self.key_id.clone(),
+            credentials_env: self.credentials_env.clone(),
+            auth_policy: Default::default(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, clap::Subcommand)]
 pub enum ThresholdInitCmd {
     /// Use a centralized dealer to create config files for each signer.
@@ -210,8 +240,12 @@ fn exec_deal(
                 full_viewing_key,
                 grpc_url: grpc_url.clone(),
                 view_url: None,
+                view_auth_token: None,
                 disable_warning: false,
                 governance_custody: None,
+                screening: None,
+                scan_filter: Default::default(),
+                revoked_detection_keys: Vec::new(),
             }
         } else {
             let mut pcli_config = PcliConfig::load(config_path.join(crate::CONFIG_FILE_NAME))?;
@@ -309,6 +343,13 @@ impl InitCmd {
                 let config = threshold::dkg(*threshold, *num_participants, &ActualTerminal).await?;
                 (config.fvk().clone(), CustodyConfig::Threshold(config))
             }
+            (_, InitSubCmd::CloudKms(cmd)) => {
+                let config = cmd.config()?;
+                (
+                    config.full_viewing_key.clone(),
+                    CustodyConfig::CloudKms(config),
+                )
+            }
             (_, InitSubCmd::Threshold(ThresholdInitCmd::Deal { .. })) => {
                 unreachable!("this should already have been handled above")
             }
@@ -327,8 +368,12 @@ impl InitCmd {
                 full_viewing_key,
                 grpc_url: self.grpc_url.clone(),
                 view_url: None,
+                view_auth_token: None,
                 disable_warning: false,
                 governance_custody: None,
+                screening: None,
+                scan_filter: Default::default(),
+                revoked_detection_keys: Vec::new(),
             }
         } else {
             let config_path = home_dir.join(crate::CONFIG_FILE_NAME);
@@ -336,6 +381,7 @@ impl InitCmd {
             let governance_custody = match custody {
                 CustodyConfig::SoftKms(config) => GovernanceCustodyConfig::SoftKms(config),
                 CustodyConfig::Threshold(config) => GovernanceCustodyConfig::Threshold(config),
+                CustodyConfig::CloudKms(config) => GovernanceCustodyConfig::CloudKms(config),
                 _ => unreachable!("governance keys can't be initialized in view-only mode"),
             };
             config.governance_custody = Some(governance_custody);
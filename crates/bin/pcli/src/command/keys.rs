@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use comfy_table::{presets, Table};
+
+use penumbra_keys::keys::AddressIndex;
+use penumbra_view::ViewClient;
+
+use crate::App;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum KeysCmd {
+    /// Export keys granting scoped capabilities to third parties.
+    #[clap(subcommand)]
+    Export(ExportCmd),
+    /// Revoke a previously exported detection key.
+    ///
+    /// Revocation is recorded in the pcli config, and prevents the key from
+    /// being exported again and its address from being shown, but it cannot
+    /// take the key away from anyone who already holds it: they will keep
+    /// detecting transactions sent to that address.  Stop handing out the
+    /// address, and use a fresh randomizer instead.
+    RevokeDetectionKey(AddressIndexArgs),
+    /// List the detection keys exported so far.
+    ListDetectionKeys {
+        /// Include revoked keys in the listing.
+        #[clap(long)]
+        include_revoked: bool,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ExportCmd {
+    /// Export the FMD detection key for an address.
+    ///
+    /// The detection key lets its holder, such as a third-party scanning
+    /// service, probabilistically detect which transactions may be addressed
+    /// to the corresponding address, with false positives at the rate set by
+    /// the chain's FMD parameters.  It does not reveal the incoming viewing
+    /// key: the holder cannot decrypt notes, or detect transactions sent to
+    /// any other address.
+    DetectionKey(AddressIndexArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct AddressIndexArgs {
+    /// The account index of the address.
+    #[clap(default_value = "0")]
+    account: u32,
+    /// The hex-encoded 12-byte randomizer of the address, for addresses
+    /// other than the account's default one.
+    #[clap(long)]
+    randomizer: Option<String>,
+}
+
+impl AddressIndexArgs {
+    fn address_index(&self) -> Result<AddressIndex> {
+        let randomizer = match &self.randomizer {
+            Some(hex_randomizer) => hex::decode(hex_randomizer)
+                .context("randomizer must be hex-encoded")?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("randomizer must be 12 bytes long"))?,
+            None => [0u8; 12],
+        };
+        Ok(AddressIndex {
+            account: self.account,
+            randomizer,
+        })
+    }
+}
+
+impl KeysCmd {
+    pub fn offline(&self) -> bool {
+        // These commands record or read exports in the view service, which is
+        // only available to commands that sync first.  Syncing also means that
+        // the recorded export and revocation heights are current.
+        false
+    }
+
+    pub async fn exec(&self, app: &mut App) -> Result<()> {
+        match self {
+            KeysCmd::Export(ExportCmd::DetectionKey(args)) => {
+                let address_index = args.address_index()?;
+                if app.config.revoked_detection_keys.contains(&address_index) {
+                    anyhow::bail!(
+                        "the detection key for this address was revoked; use a fresh address instead"
+                    );
+                }
+
+                let (address, detection_key) =
+                    app.view().export_detection_key(address_index).await?;
+
+                println!("Address: {address}");
+                println!("Detection key: {}", hex::encode(detection_key.to_bytes()));
+            }
+            KeysCmd::RevokeDetectionKey(args) => {
+                let address_index = args.address_index()?;
+
+                // Record the revocation in the config first, since the view
+                // service's records are lost if it is reset.
+                if !app.config.revoked_detection_keys.contains(&address_index) {
+                    app.config.revoked_detection_keys.push(address_index);
+                    app.config.save(&app.config_path)?;
+                }
+                match app.view().revoke_detection_key(address_index).await {
+                    Ok(()) => {
+                        println!("Detection key revoked; use a fresh address for future payments.")
+                    }
+                    Err(e)
+                        if e.downcast_ref::<tonic::Status>().map(tonic::Status::code)
+                            == Some(tonic::Code::NotFound) =>
+                    {
+                        println!(
+                            "The view service has no record of exporting this detection key, so there was nothing to revoke; it won't be exported from now on."
+                        );
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            KeysCmd::ListDetectionKeys { include_revoked } => {
+                let revoked = app.config.revoked_detection_keys.clone();
+                let records = app
+                    .view()
+                    .detection_keys(*include_revoked)
+                    .await?
+                    .into_iter()
+                    .filter(|record| *include_revoked || !revoked.contains(&record.address_index));
+
+                let mut table = Table::new();
+                table.load_preset(presets::NOTHING);
+                table.set_header(vec![
+                    "Account",
+                    "Randomizer",
+                    "Address",
+                    "Exported",
+                    "Revoked",
+                ]);
+                for record in records {
+                    table.add_row(vec![
+                        format!("# {}", record.address_index.account),
+                        hex::encode(record.address_index.randomizer),
+                        record.address.display_short_form(),
+                        record.exported_height.to_string(),
+                        match record.revoked_height {
+                            Some(height) => height.to_string(),
+                            // Revoked in the config, but not in the view service.
+                            None if revoked.contains(&record.address_index) => "yes".to_string(),
+                            None => String::new(),
+                        },
+                    ]);
+                }
+
+                println!("{table}");
+            }
+        }
+
+        Ok(())
+    }
+}
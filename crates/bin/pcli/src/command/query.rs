@@ -5,6 +5,8 @@ use colored_json::ToColoredJson;
 use shielded_pool::ShieldedPool;
 mod tx;
 use tx::Tx;
+mod disclosure;
+use disclosure::VerifyDisclosureCmd;
 mod chain;
 use chain::ChainCmd;
 mod dex;
@@ -44,6 +46,8 @@ pub enum QueryCmd {
     ShieldedPool(ShieldedPool),
     /// Queries a transaction by hash.
     Tx(Tx),
+    /// Verifies a transaction disclosure exported by `pcli view disclose`.
+    VerifyDisclosure(VerifyDisclosureCmd),
     /// Queries information about the chain.
     #[clap(subcommand)]
     Chain(ChainCmd),
@@ -136,6 +140,9 @@ impl QueryCmd {
         }
 
         let key = match self {
+            QueryCmd::VerifyDisclosure(_) => {
+                unreachable!("disclosure verification already executed")
+            }
             QueryCmd::Tx(_)
             | QueryCmd::Chain(_)
             | QueryCmd::Validator(_)
@@ -175,6 +182,7 @@ impl QueryCmd {
         match self {
             QueryCmd::Dex { .. } | QueryCmd::CommunityPool { .. } => false,
             QueryCmd::Tx { .. }
+            | QueryCmd::VerifyDisclosure { .. }
             | QueryCmd::Chain { .. }
             | QueryCmd::Validator { .. }
             | QueryCmd::ShieldedPool { .. }
@@ -192,6 +200,7 @@ impl QueryCmd {
             }
             QueryCmd::ShieldedPool(sp) => sp.display_value(bytes)?,
             QueryCmd::Tx { .. }
+            | QueryCmd::VerifyDisclosure { .. }
             | QueryCmd::Chain { .. }
             | QueryCmd::Validator { .. }
             | QueryCmd::Dex { .. }
@@ -126,6 +126,10 @@ impl ChainCmd {
             .add_row(vec![
                 "Outbound ICS-20 Enabled",
                 &format!("{}", params.ibc_params.outbound_ics20_transfers_enabled),
+            ])
+            .add_row(vec![
+                "ICS-27 Host Enabled",
+                &format!("{}", params.ibc_params.ics27_host_enabled),
             ]);
 
         println!("{table}");
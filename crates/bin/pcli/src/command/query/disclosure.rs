@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use penumbra_proto::{
+    util::tendermint_proxy::v1::{
+        tendermint_proxy_service_client::TendermintProxyServiceClient, GetTxRequest,
+    },
+    DomainType,
+};
+use penumbra_transaction::{Transaction, TransactionDisclosure};
+use url::Url;
+
+use crate::config::PcliConfig;
+
+/// Verifies a transaction disclosure against the chain, and displays the disclosed transaction.
+///
+/// This doesn't use a wallet at all, so it can be used to check a disclosure made by anyone, even
+/// without having run `pcli init`.
+#[derive(Debug, clap::Args)]
+pub struct VerifyDisclosureCmd {
+    /// The file containing the disclosure, as exported by `pcli view disclose`.
+    file: Utf8PathBuf,
+    /// If set, print the raw transaction view rather than a formatted table.
+    #[clap(long)]
+    raw: bool,
+    /// The GRPC URL of the node to fetch the transaction from.
+    ///
+    /// Defaults to the node in the pcli config, if there is one.
+    #[clap(long, parse(try_from_str = Url::parse))]
+    grpc_url: Option<Url>,
+}
+
+impl VerifyDisclosureCmd {
+    /// Verify the disclosure, taking the home dir directly rather than an `App`, since no wallet
+    /// is needed.
+    pub async fn exec(&self, home_dir: impl AsRef<Utf8Path>) -> Result<()> {
+        let disclosure: TransactionDisclosure = serde_json::from_slice(
+            &std::fs::read(&self.file)
+                .with_context(|| format!("failed to read disclosure from {}", self.file))?,
+        )
+        .context("invalid transaction disclosure")?;
+
+        let grpc_url = match &self.grpc_url {
+            Some(grpc_url) => grpc_url.clone(),
+            None => {
+                let config_path = home_dir.as_ref().join(crate::CONFIG_FILE_NAME);
+                PcliConfig::load(config_path)
+                    .context("pass --grpc-url, or run `pcli init` to configure a node")?
+                    .grpc_url
+            }
+        };
+        let mut client =
+            TendermintProxyServiceClient::new(crate::network::pd_channel(&grpc_url).await?);
+        let rsp = client
+            .get_tx(GetTxRequest {
+                hash: disclosure.transaction_id.0.to_vec(),
+                prove: false,
+            })
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to fetch disclosed transaction {}",
+                    disclosure.transaction_id
+                )
+            })?
+            .into_inner();
+        let tx = Transaction::decode(rsp.tx.as_slice())?;
+
+        let view = disclosure
+            .verify(&tx)
+            .context("disclosure does not match the transaction on chain")?;
+
+        if self.raw {
+            use colored_json::prelude::*;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&view)?.to_colored_json_auto()?
+            );
+        } else {
+            use crate::transaction_view_ext::TransactionViewExt;
+            println!(
+                "Disclosure is valid for transaction {} at height {}",
+                disclosure.transaction_id, rsp.height
+            );
+            view.render_terminal();
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
-use anyhow::Result;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use ed25519_consensus::VerificationKey;
 
 use crate::{
-    config::{CustodyConfig, GovernanceCustodyConfig},
+    config::{CustodyConfig, GovernanceCustodyConfig, PcliConfig},
     terminal::ActualTerminal,
     App,
 };
@@ -10,12 +14,37 @@ use crate::{
 pub enum ThresholdCmd {
     /// Contribute to signing a transaction with threshold custody
     Sign,
+    /// Refresh the threshold signing share together with all the other participants, keeping the
+    /// same keys but invalidating all the old shares
+    Refresh(RefreshCmd),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct RefreshCmd {
+    /// Only refresh if the share was created or last refreshed at least this many days ago.
+    ///
+    /// Only the coordinator should pass this: if the share isn't due for a refresh, it calls off
+    /// the refresh in the first round, and the other participants follow its decision. This lets
+    /// the participants meet for a refresh at regular times, and have the coordinator decide
+    /// whether it's needed. Every participant still needs to run the refresh interactively, at
+    /// the same time, so scheduling those meetings is up to them.
+    #[clap(long)]
+    pub if_older_than_days: Option<u64>,
+    /// The participant key of the coordinator, as printed when it starts the refresh.
+    ///
+    /// The refresh can only be called off by the coordinator, and only if this is given.
+    #[clap(long, parse(try_from_str = parse_participant_key))]
+    pub coordinator: Option<VerificationKey>,
+    /// Refresh the separate governance custody share, instead of the spend custody share.
+    #[clap(long)]
+    pub governance: bool,
 }
 
 impl ThresholdCmd {
     pub fn offline(&self) -> bool {
         match self {
             ThresholdCmd::Sign => true,
+            ThresholdCmd::Refresh(_) => true,
         }
     }
 
@@ -35,6 +64,102 @@ impl ThresholdCmd {
                 penumbra_custody::threshold::follow(config, governance_config, &ActualTerminal)
                     .await
             }
+            ThresholdCmd::Refresh(_) => unreachable!("refresh command already executed"),
         }
     }
 }
+
+impl RefreshCmd {
+    /// Run the refresh protocol, replacing the share in the config file in `home_dir`.
+    ///
+    /// The previous config is kept next to it, as `config.toml.bak`, until every participant has
+    /// acknowledged storing their refreshed share.
+    ///
+    /// This takes the home dir directly, rather than an `App`, since it rewrites the config file.
+    pub async fn exec(&self, home_dir: impl AsRef<Utf8Path>) -> Result<()> {
+        let config_path = home_dir.as_ref().join(crate::CONFIG_FILE_NAME);
+        let backup_path = config_path.with_extension("toml.bak");
+        if backup_path.exists() {
+            anyhow::bail!(
+                "{backup_path} was kept from a previous refresh that wasn't acknowledged by everyone; \
+                 restore or remove it before refreshing again"
+            );
+        }
+        let mut pcli_config = PcliConfig::load(&config_path)?;
+
+        let config = if self.governance {
+            match &pcli_config.governance_custody {
+                Some(GovernanceCustodyConfig::Threshold(config)) => config,
+                Some(_) => anyhow::bail!("governance custody is not a threshold config"),
+                None => anyhow::bail!(
+                    "there is no separate governance custody config; refresh the spend custody share instead"
+                ),
+            }
+        } else {
+            match &pcli_config.custody {
+                CustodyConfig::Threshold(config) => config,
+                _ => anyhow::bail!("custody is not a threshold config"),
+            }
+        };
+
+        println!(
+            "This participant's key is {}",
+            hex::encode_upper(config.signing_key().verification_key().as_bytes())
+        );
+
+        if let Some(days) = self.if_older_than_days {
+            let interval = Duration::from_secs(days * 24 * 60 * 60);
+            if !config.refresh_due(interval, SystemTime::now()) {
+                println!(
+                    "Share was refreshed less than {days} days ago (refresh epoch {}), calling off the refresh.",
+                    config.refresh_epoch()
+                );
+                penumbra_custody::threshold::skip_refresh(config, &ActualTerminal).await?;
+                return Ok(());
+            }
+        }
+
+        let Some(refreshed) = penumbra_custody::threshold::refresh(
+            config,
+            self.coordinator.as_ref(),
+            &ActualTerminal,
+        )
+        .await?
+        else {
+            println!("The refresh was called off, the share was not changed.");
+            return Ok(());
+        };
+        let epoch = refreshed.refresh_epoch();
+        let acknowledged = refreshed.clone();
+        if self.governance {
+            pcli_config.governance_custody = Some(GovernanceCustodyConfig::Threshold(refreshed));
+        } else {
+            pcli_config.custody = CustodyConfig::Threshold(refreshed);
+            if pcli_config.governance_custody.is_none() {
+                println!("The governance key uses the same share, so it was refreshed as well.");
+            }
+        }
+
+        // Keep the old share until everyone has acknowledged storing their new one, since if anyone
+        // failed to, the old shares are the only ones that can still sign.
+        std::fs::copy(&config_path, &backup_path)?;
+        pcli_config.save(&config_path)?;
+        println!("Refreshed share to epoch {epoch}, written to {config_path}");
+
+        penumbra_custody::threshold::acknowledge_refresh(&acknowledged, &ActualTerminal)
+            .await
+            .with_context(|| {
+                format!(
+                    "not every participant acknowledged the refresh; the previous config was kept \
+                     at {backup_path}, restore it if the others didn't store their new share"
+                )
+            })?;
+        std::fs::remove_file(&backup_path)?;
+        println!("All participants acknowledged the refresh, removed {backup_path}");
+        Ok(())
+    }
+}
+
+fn parse_participant_key(s: &str) -> Result<VerificationKey> {
+    Ok(VerificationKey::try_from(hex::decode(s)?.as_slice())?)
+}
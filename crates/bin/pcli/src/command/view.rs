@@ -2,6 +2,7 @@ use anyhow::Result;
 
 use address::AddressCmd;
 use balance::BalanceCmd;
+use disclose::DiscloseCmd;
 use staked::StakedCmd;
 use transaction_hashes::TransactionHashesCmd;
 use tx::TxCmd;
@@ -11,6 +12,7 @@ use crate::App;
 
 mod address;
 mod balance;
+mod disclose;
 mod staked;
 mod wallet_id;
 
@@ -39,6 +41,8 @@ pub enum ViewCmd {
     ListTransactionHashes(TransactionHashesCmd),
     /// Displays a transaction's details by hash.
     Tx(TxCmd),
+    /// Exports the keys needed to decrypt a single transaction, for sharing with a third party.
+    Disclose(DiscloseCmd),
 }
 
 impl ViewCmd {
@@ -52,6 +56,7 @@ impl ViewCmd {
             ViewCmd::Sync => false,
             ViewCmd::ListTransactionHashes(transactions_cmd) => transactions_cmd.offline(),
             ViewCmd::Tx(tx_cmd) => tx_cmd.offline(),
+            ViewCmd::Disclose(disclose_cmd) => disclose_cmd.offline(),
         }
     }
 
@@ -66,6 +71,9 @@ impl ViewCmd {
             ViewCmd::Tx(tx_cmd) => {
                 tx_cmd.exec(app).await?;
             }
+            ViewCmd::Disclose(disclose_cmd) => {
+                disclose_cmd.exec(app).await?;
+            }
             ViewCmd::ListTransactionHashes(transactions_cmd) => {
                 let view_client = app.view();
                 transactions_cmd
@@ -80,7 +88,7 @@ impl ViewCmd {
                 // The wallet has already been reset by a short-circuiting path.
             }
             ViewCmd::Address(address_cmd) => {
-                address_cmd.exec(&full_viewing_key)?;
+                address_cmd.exec(&full_viewing_key, &app.config.revoked_detection_keys)?;
             }
             ViewCmd::Balance(balance_cmd) => {
                 let view_client = app.view();
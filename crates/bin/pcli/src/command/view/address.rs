@@ -2,7 +2,7 @@ use anyhow::Result;
 use base64::Engine;
 use rand_core::OsRng;
 
-use penumbra_keys::{Address, FullViewingKey};
+use penumbra_keys::{keys::AddressIndex, Address, FullViewingKey};
 
 #[derive(Debug, clap::Parser)]
 pub struct AddressCmd {
@@ -26,12 +26,21 @@ impl AddressCmd {
         true
     }
 
-    pub fn exec(&self, fvk: &FullViewingKey) -> Result<()> {
+    /// Runs the command, refusing to show addresses whose detection keys are in `revoked`.
+    pub fn exec(&self, fvk: &FullViewingKey, revoked: &[AddressIndex]) -> Result<()> {
         let index: Result<u32, _> = self.address_or_index.parse();
 
         if let Ok(index) = index {
             //index provided
 
+            // Ephemeral addresses have fresh randomizers, so only the indexed
+            // address can have had its detection key revoked.
+            if !self.ephemeral && revoked.contains(&index.into()) {
+                anyhow::bail!(
+                    "the detection key for this address was revoked, so it should no longer be handed out; use an ephemeral address instead"
+                );
+            }
+
             let (address, _dtk) = match self.ephemeral {
                 false => fvk.incoming().payment_address(index.into()),
                 true => fvk.incoming().ephemeral_address(OsRng, index.into()),
@@ -57,11 +66,15 @@ impl AddressCmd {
 
             match fvk.address_index(&address) {
                 Some(address_index) => println!(
-                    "Address is viewable with this full viewing key. Account index is {0}. {1}",
+                    "Address is viewable with this full viewing key. Account index is {0}. {1}{2}",
                     address_index.account,
                     match address_index.randomizer != [0u8; 12] {
                         true => "Address is ephemeral.",
                         false => "",
+                    },
+                    match revoked.contains(&address_index) {
+                        true => " Its detection key was revoked.",
+                        false => "",
                     }
                 ),
                 None => println!("Address is not viewable with this full viewing key."),
@@ -71,3 +84,23 @@ impl AddressCmd {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use penumbra_keys::test_keys;
+
+    use super::*;
+
+    #[test]
+    fn revoked_addresses_are_not_shown() {
+        let revoked = [AddressIndex::from(1u32)];
+        let fvk = &*test_keys::FULL_VIEWING_KEY;
+        let parse = |args: &[&str]| AddressCmd::parse_from([&["address"][..], args].concat());
+
+        assert!(parse(&["1"]).exec(fvk, &revoked).is_err());
+        assert!(parse(&["0"]).exec(fvk, &revoked).is_ok());
+        // Ephemeral addresses never reuse a revoked randomizer.
+        assert!(parse(&["1", "--ephemeral"]).exec(fvk, &revoked).is_ok());
+    }
+}
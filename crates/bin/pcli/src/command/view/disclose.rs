@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use penumbra_transaction::TransactionDisclosure;
+use penumbra_view::ViewClient;
+
+use crate::App;
+
+/// Exports the keys needed to decrypt one of your transactions, for sharing with a third party.
+///
+/// The disclosure reveals the contents of the outputs, swaps and memo of that transaction
+/// visible to this wallet, and nothing else. It can be checked with `pcli query
+/// verify-disclosure`.
+#[derive(Debug, clap::Args)]
+pub struct DiscloseCmd {
+    /// The hex-formatted hash of the transaction to disclose.
+    hash: String,
+    /// The file to write the disclosure to; if unset, it's printed to stdout.
+    #[clap(long, short)]
+    output: Option<Utf8PathBuf>,
+}
+
+impl DiscloseCmd {
+    pub fn offline(&self) -> bool {
+        false
+    }
+
+    pub async fn exec(&self, app: &mut App) -> Result<()> {
+        let hash = self
+            .hash
+            // We have to convert to uppercase because `tendermint::Hash` only accepts uppercase :(
+            .to_uppercase()
+            .parse()
+            .context("invalid transaction hash")?;
+
+        let tx_info = app
+            .view()
+            .transaction_info_by_hash(hash)
+            .await
+            .context("transaction not found in view service")?;
+
+        let disclosure =
+            TransactionDisclosure::new(&tx_info.transaction, &tx_info.perspective.payload_keys);
+        if disclosure.payload_keys.is_empty() && disclosure.memo_key.is_none() {
+            anyhow::bail!(
+                "no part of transaction {} is visible to this wallet",
+                self.hash
+            );
+        }
+
+        let json = serde_json::to_string_pretty(&disclosure)?;
+        match &self.output {
+            Some(path) => {
+                std::fs::write(path, json)
+                    .with_context(|| format!("failed to write disclosure to {path}"))?;
+                println!("Wrote disclosure of transaction {} to {path}", self.hash);
+            }
+            None => println!("{json}"),
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{io::Write, path::Path};
 
 use anyhow::{Context, Result};
 use penumbra_stake::GovernanceKey;
@@ -6,8 +6,12 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 use url::Url;
 
-use penumbra_custody::{soft_kms::Config as SoftKmsConfig, threshold::Config as ThresholdConfig};
-use penumbra_keys::FullViewingKey;
+use penumbra_custody::{
+    cloud_kms::Config as CloudKmsConfig, screening::ScreeningConfig,
+    soft_kms::Config as SoftKmsConfig, threshold::Config as ThresholdConfig,
+};
+use penumbra_keys::{keys::AddressIndex, FullViewingKey};
+use penumbra_view::{auth::AuthToken, ScanFilter};
 
 /// Configuration data for `pcli`.
 #[serde_as]
@@ -17,6 +21,9 @@ pub struct PcliConfig {
     pub grpc_url: Url,
     /// If set, use a remote view service instead of local synchronization.
     pub view_url: Option<Url>,
+    /// If set, present this bearer token to the remote view service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_auth_token: Option<AuthToken>,
     /// Disable the scary "you will lose all your money" warning.
     #[serde(default, skip_serializing_if = "is_default")]
     pub disable_warning: bool,
@@ -27,6 +34,18 @@ pub struct PcliConfig {
     pub custody: CustodyConfig,
     /// The governance custody backend to use.
     pub governance_custody: Option<GovernanceCustodyConfig>,
+    /// If set, screen the destinations of transactions before building them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screening: Option<ScreeningConfig>,
+    /// Sections of compact blocks to skip while syncing the local view service.
+    #[serde(default, skip_serializing_if = "ScanFilter::is_empty")]
+    pub scan_filter: ScanFilter,
+    /// The address indices whose detection keys have been revoked.
+    ///
+    /// These are kept here rather than only in the view service, whose records are lost when it
+    /// is reset, so that revoked addresses are never handed out or exported again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revoked_detection_keys: Vec<AddressIndex>,
 }
 
 impl PcliConfig {
@@ -38,9 +57,36 @@ impl PcliConfig {
         Ok(toml::from_str(&contents)?)
     }
 
+    /// Save the config to `path`, replacing any existing file atomically.
+    ///
+    /// The config is written to a temporary file next to `path` and then renamed over it, so
+    /// that a crash partway through can't leave a truncated config, and the key material in it,
+    /// behind.  The new file keeps the permissions of the one it replaces, and is only readable
+    /// by its owner if there wasn't one.
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
         let contents = toml::to_string_pretty(&self)?;
-        std::fs::write(path, contents)?;
+        let tmp_path = path.with_extension("toml.tmp");
+
+        // A temporary file left behind by an earlier crash may have looser permissions, which
+        // opening it wouldn't change.
+        match std::fs::remove_file(&tmp_path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            result => result?,
+        }
+        {
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let mut file = options.open(&tmp_path)?;
+            if let Ok(metadata) = std::fs::metadata(path) {
+                file.set_permissions(metadata.permissions())?;
+            }
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, path)?;
         Ok(())
     }
 
@@ -50,6 +96,9 @@ impl PcliConfig {
                 spend_key.full_viewing_key()
             }
             Some(GovernanceCustodyConfig::Threshold(threshold_config)) => threshold_config.fvk(),
+            Some(GovernanceCustodyConfig::CloudKms(CloudKmsConfig {
+                full_viewing_key, ..
+            })) => full_viewing_key,
             None => &self.full_viewing_key,
         };
         GovernanceKey(fvk.spend_verification_key().clone())
@@ -67,6 +116,8 @@ pub enum CustodyConfig {
     SoftKms(SoftKmsConfig),
     /// A manual threshold custody service.
     Threshold(ThresholdConfig),
+    /// A spend key held in an external KMS or HSM.
+    CloudKms(CloudKmsConfig),
 }
 
 /// The governance custody backend to use.
@@ -78,6 +129,8 @@ pub enum GovernanceCustodyConfig {
     SoftKms(SoftKmsConfig),
     /// A manual threshold custody service.
     Threshold(ThresholdConfig),
+    /// A spend key held in an external KMS or HSM.
+    CloudKms(CloudKmsConfig),
 }
 
 impl Default for CustodyConfig {
@@ -107,21 +160,72 @@ mod tests {
             grpc_url: Url::parse("https://grpc.testnet.penumbra.zone").unwrap(),
             disable_warning: false,
             view_url: None,
+            view_auth_token: None,
             full_viewing_key: penumbra_keys::test_keys::FULL_VIEWING_KEY.clone(),
             custody: CustodyConfig::SoftKms(SoftKmsConfig::from(
                 penumbra_keys::test_keys::SPEND_KEY.clone(),
             )),
             governance_custody: None,
+            screening: None,
+            scan_filter: Default::default(),
+            revoked_detection_keys: Vec::new(),
         };
 
         let mut config2 = config.clone();
         config2.custody = CustodyConfig::ViewOnly;
         config2.disable_warning = true;
+        config2.revoked_detection_keys = vec![AddressIndex::from(1u32)];
 
         let toml_config = toml::to_string_pretty(&config).unwrap();
         let toml_config2 = toml::to_string_pretty(&config2).unwrap();
 
         println!("{}", toml_config);
         println!("{}", toml_config2);
+
+        assert_eq!(
+            toml::from_str::<PcliConfig>(&toml_config2).unwrap(),
+            config2
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_keeps_config_private() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let config = PcliConfig {
+            grpc_url: Url::parse("https://grpc.testnet.penumbra.zone").unwrap(),
+            disable_warning: false,
+            view_url: None,
+            view_auth_token: None,
+            full_viewing_key: penumbra_keys::test_keys::FULL_VIEWING_KEY.clone(),
+            custody: CustodyConfig::SoftKms(SoftKmsConfig::from(
+                penumbra_keys::test_keys::SPEND_KEY.clone(),
+            )),
+            governance_custody: None,
+            screening: None,
+            scan_filter: Default::default(),
+            revoked_detection_keys: Vec::new(),
+        };
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("config.toml");
+        let mode = |path: &Path| -> Result<u32> {
+            Ok(std::fs::metadata(path)?.permissions().mode() & 0o777)
+        };
+
+        // A new config is only readable by its owner...
+        config.save(&path)?;
+        assert_eq!(mode(&path)?, 0o600);
+
+        // ...and saving over an existing config keeps its permissions.
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640))?;
+        config.save(&path)?;
+        assert_eq!(mode(&path)?, 0o640);
+        assert_eq!(
+            toml::from_str::<PcliConfig>(&std::fs::read_to_string(&path)?)?,
+            config
+        );
+
+        Ok(())
     }
 }
@@ -38,6 +38,8 @@ pub struct App {
     pub custody: CustodyServiceClient<BoxGrpcService>,
     pub governance_custody: CustodyServiceClient<BoxGrpcService>,
     pub config: PcliConfig,
+    /// The path `config` was loaded from, for commands that update it.
+    pub config_path: camino::Utf8PathBuf,
 }
 
 impl App {
@@ -96,6 +98,13 @@ async fn main() -> Result<()> {
     // that tracing is set up even for wallet commands that don't build the `App`.
     opt.init_tracing();
 
+    // Verifying a disclosure doesn't need a wallet, so handle it before anything touches the home
+    // directory.
+    if let Command::Query(QueryCmd::VerifyDisclosure(verify_cmd)) = &opt.cmd {
+        verify_cmd.exec(opt.home.as_path()).await?;
+        return Ok(());
+    }
+
     //Ensure that the data_path exists, in case this is a cold start
     fs::create_dir_all(&opt.home)
         .with_context(|| format!("Failed to create home directory {}", opt.home))?;
@@ -114,6 +123,11 @@ async fn main() -> Result<()> {
         reset.exec(opt.home.as_path())?;
         return Ok(());
     }
+    // The threshold refresh command takes the home dir directly, since it replaces the config.
+    if let Command::Threshold(ThresholdCmd::Refresh(refresh_cmd)) = &opt.cmd {
+        refresh_cmd.exec(opt.home.as_path()).await?;
+        return Ok(());
+    }
     // The debug command takes the home dir directly
     if let Command::Debug(debug_cmd) = &opt.cmd {
         let dd = opt.home.into_std_path_buf();
@@ -136,6 +150,7 @@ async fn main() -> Result<()> {
         Command::Debug(_) => unreachable!("debug command already executed"),
         Command::Transaction(tx_cmd) => tx_cmd.exec(&mut app).await?,
         Command::View(view_cmd) => view_cmd.exec(&mut app).await?,
+        Command::Keys(cmd) => cmd.exec(&mut app).await?,
         Command::Validator(cmd) => cmd.exec(&mut app).await?,
         Command::Query(cmd) => cmd.exec(&mut app).await?,
         Command::Ceremony(cmd) => cmd.exec(&mut app).await?,
@@ -1,8 +1,10 @@
 use anyhow::Context;
 use decaf377_rdsa::{Signature, SpendAuth};
 use futures::{FutureExt, TryStreamExt};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use penumbra_fee::GasPrices;
 use penumbra_governance::ValidatorVoteBody;
+use penumbra_keys::Address;
 use penumbra_proto::{
     custody::v1::{AuthorizeValidatorDefinitionRequest, AuthorizeValidatorVoteRequest},
     util::tendermint_proxy::v1::tendermint_proxy_service_client::TendermintProxyServiceClient,
@@ -11,14 +13,45 @@ use penumbra_proto::{
     DomainType,
 };
 use penumbra_stake::validator::Validator;
-use penumbra_transaction::{gas::GasCost, txhash::TransactionId, Transaction, TransactionPlan};
+use penumbra_transaction::{
+    gas::GasCost,
+    plan::{CancellationToken, ProvingObserver, ProvingStage},
+    txhash::TransactionId,
+    Transaction, TransactionPlan,
+};
 use penumbra_view::ViewClient;
-use std::future::Future;
+use std::{future::Future, io::Write as _};
 use tonic::transport::{Channel, ClientTlsConfig};
 use tracing::instrument;
 
 use crate::App;
 
+/// The progress bar shown while proving a transaction.
+///
+/// If the build fails, or its future is dropped before it finishes, the bar is abandoned and any
+/// remaining proofs are cancelled, rather than leaving them running behind the next output.
+struct ProvingProgress {
+    progress_bar: ProgressBar,
+    cancellation: CancellationToken,
+    finished: bool,
+}
+
+impl ProvingProgress {
+    fn finish(mut self) {
+        self.finished = true;
+        self.progress_bar.finish_and_clear();
+    }
+}
+
+impl Drop for ProvingProgress {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.cancellation.cancel();
+            self.progress_bar.abandon();
+        }
+    }
+}
+
 impl App {
     pub async fn build_and_submit_transaction(
         &mut self,
@@ -50,20 +83,42 @@ impl App {
         &mut self,
         plan: TransactionPlan,
     ) -> impl Future<Output = anyhow::Result<Transaction>> + '_ {
+        let screened = self.screen_plan(&plan);
         println!(
             "building transaction [{} actions, {} proofs]...",
             plan.actions.len(),
             plan.num_proofs(),
         );
-        let start = std::time::Instant::now();
-        let tx = penumbra_wallet::build_transaction(
-            &self.config.full_viewing_key,
-            self.view.as_mut().expect("view service initialized"),
-            &mut self.custody,
-            plan,
+        let progress_bar = ProgressBar::with_draw_target(
+            (plan.num_proofs() * ProvingStage::COUNT) as u64,
+            ProgressDrawTarget::stdout(),
+        )
+        .with_style(
+            ProgressStyle::default_bar()
+                .template("[{elapsed}] {bar:50.cyan/blue} {pos:>3}/{len:3}"),
         );
+        let cancellation = CancellationToken::new();
+        let observer = {
+            let progress_bar = progress_bar.clone();
+            ProvingObserver::new(move |_| progress_bar.inc(1), cancellation.clone())
+        };
+        let start = std::time::Instant::now();
         async move {
-            let tx = tx.await?;
+            screened?;
+            let progress = ProvingProgress {
+                progress_bar,
+                cancellation,
+                finished: false,
+            };
+            let tx = penumbra_wallet::build_transaction(
+                &self.config.full_viewing_key,
+                self.view.as_mut().expect("view service initialized"),
+                &mut self.custody,
+                plan,
+                &observer,
+            )
+            .await?;
+            progress.finish();
             let elapsed = start.elapsed();
             println!(
                 "finished proving in {}.{:03} seconds [{} actions, {} proofs, {} bytes]",
@@ -77,6 +132,18 @@ impl App {
         }
     }
 
+    /// Screens the destinations of the plan, if screening is configured, prompting for
+    /// confirmation where required.
+    fn screen_plan(&self, plan: &TransactionPlan) -> anyhow::Result<()> {
+        let Some(config) = &self.config.screening else {
+            return Ok(());
+        };
+        config
+            .screener()?
+            .with_confirmation(confirm_destinations)
+            .check_plan(plan, "pcli")
+    }
+
     pub async fn sign_validator_definition(
         &mut self,
         validator_definition: Validator,
@@ -184,17 +251,7 @@ impl App {
 
     // TODO: why do we need this here but not in the view crate?
     pub async fn pd_channel(&self) -> anyhow::Result<Channel> {
-        match self.config.grpc_url.scheme() {
-            "http" => Ok(Channel::from_shared(self.config.grpc_url.to_string())?
-                .connect()
-                .await?),
-            "https" => Ok(Channel::from_shared(self.config.grpc_url.to_string())?
-                .tls_config(ClientTlsConfig::new())?
-                .connect()
-                .await?),
-            other => Err(anyhow::anyhow!("unknown url scheme {other}"))
-                .with_context(|| format!("could not connect to {}", self.config.grpc_url)),
-        }
+        pd_channel(&self.config.grpc_url).await
     }
 
     pub async fn tendermint_proxy_client(
@@ -204,3 +261,31 @@ impl App {
         Ok(TendermintProxyServiceClient::new(channel))
     }
 }
+
+/// Connects to the node at `grpc_url`, for commands that run without an [`App`].
+pub async fn pd_channel(grpc_url: &url::Url) -> anyhow::Result<Channel> {
+    match grpc_url.scheme() {
+        "http" => Ok(Channel::from_shared(grpc_url.to_string())?
+            .connect()
+            .await?),
+        "https" => Ok(Channel::from_shared(grpc_url.to_string())?
+            .tls_config(ClientTlsConfig::new())?
+            .connect()
+            .await?),
+        other => Err(anyhow::anyhow!("unknown url scheme {other}"))
+            .with_context(|| format!("could not connect to {grpc_url}")),
+    }
+}
+
+/// Asks on the terminal whether to send funds to destinations flagged by screening.
+fn confirm_destinations(addresses: &[Address]) -> bool {
+    println!("This transaction sends funds to addresses that require confirmation:");
+    for address in addresses {
+        println!("  {address}");
+    }
+    print!("Type 'yes' to continue: ");
+    let _ = std::io::stdout().flush();
+
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line).is_ok() && line.trim() == "yes"
+}
@@ -7,7 +7,7 @@ use anyhow::Result;
 use camino::Utf8PathBuf;
 use clap::Parser;
 use directories::ProjectDirs;
-use penumbra_custody::soft_kms::SoftKms;
+use penumbra_custody::{cloud_kms::CloudKms, soft_kms::SoftKms};
 use penumbra_proto::box_grpc_svc;
 use penumbra_proto::{
     custody::v1::{
@@ -15,8 +15,12 @@ use penumbra_proto::{
     },
     view::v1::{view_service_client::ViewServiceClient, view_service_server::ViewServiceServer},
 };
-use penumbra_view::ViewServer;
+use penumbra_view::{Storage, ViewServer};
 use std::io::IsTerminal as _;
+use tonic::{
+    service::interceptor::InterceptedService,
+    transport::{ClientTlsConfig, Endpoint},
+};
 use tracing_subscriber::EnvFilter;
 
 #[derive(Debug, Parser)]
@@ -76,6 +80,12 @@ impl Opt {
                 let custody_svc = CustodyServiceServer::new(threshold_kms);
                 CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
             }
+            CustodyConfig::CloudKms(config) => {
+                tracing::info!(endpoint = %config.endpoint, "using cloud KMS custody service");
+                let cloud_kms = CloudKms::new(config.clone())?;
+                let custody_svc = CustodyServiceServer::new(cloud_kms);
+                CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
+            }
         };
 
         // Build the governance custody service...
@@ -98,11 +108,25 @@ impl Opt {
                     let custody_svc = CustodyServiceServer::new(threshold_kms);
                     CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
                 }
+                GovernanceCustodyConfig::CloudKms(config) => {
+                    tracing::info!(
+                        endpoint = %config.endpoint,
+                        "using separate cloud KMS custody service for validator voting"
+                    );
+                    let cloud_kms = CloudKms::new(config.clone())?;
+                    let custody_svc = CustodyServiceServer::new(cloud_kms);
+                    CustodyServiceClient::new(box_grpc_svc::local(custody_svc))
+                }
             },
             None => custody.clone(), // If no separate custody for validator voting, use the same one
         };
 
         // ...and the view service...
+        if config.view_auth_token.is_some() && config.view_url.is_none() {
+            anyhow::bail!(
+                "view_auth_token is set in the pcli config, but view_url is not: the token is only used to authenticate to a remote view service"
+            );
+        }
         let view = match (self.cmd.offline(), &config.view_url) {
             // In offline mode, don't construct a view service at all.
             (true, _) => None,
@@ -110,20 +134,41 @@ impl Opt {
                 // Use a remote view service.
                 tracing::info!(%view_url, "using remote view service");
 
-                let ep = tonic::transport::Endpoint::new(view_url.to_string())?;
-                Some(ViewServiceClient::new(box_grpc_svc::connect(ep).await?))
+                let mut ep = Endpoint::new(view_url.to_string())?;
+                if let Some(tls_config) = view_tls_config(view_url) {
+                    ep = ep.tls_config(tls_config)?;
+                }
+                match &config.view_auth_token {
+                    Some(token) => {
+                        // The token grants read access to the whole wallet, so never send it
+                        // in plaintext to anything but the local machine.
+                        if view_url.scheme() != "https" && !is_loopback(view_url) {
+                            anyhow::bail!(
+                                "refusing to send view_auth_token to {view_url} without TLS: use an https:// view_url"
+                            );
+                        }
+                        let channel = ep.connect().await?;
+                        let svc = InterceptedService::new(channel, token.clone());
+                        Some(ViewServiceClient::new(box_grpc_svc::local(svc)))
+                    }
+                    None => Some(ViewServiceClient::new(box_grpc_svc::connect(ep).await?)),
+                }
             }
             (false, None) => {
                 // Use an in-memory view service.
                 let path = self.home.join(crate::VIEW_FILE_NAME);
                 tracing::info!(%path, "using local view service");
 
-                let svc = ViewServer::load_or_initialize(
+                let storage = Storage::load_or_initialize(
                     Some(path),
                     &config.full_viewing_key,
                     config.grpc_url.clone(),
                 )
                 .await?;
+                if storage.set_scan_filter(config.scan_filter).await? {
+                    println!("Scan filter enables previously skipped sections, resyncing from genesis...");
+                }
+                let svc = ViewServer::new(storage, config.grpc_url.clone()).await?;
 
                 // Now build the view and custody clients, doing gRPC with ourselves
                 let svc = ViewServiceServer::new(svc);
@@ -136,11 +181,27 @@ impl Opt {
             custody,
             governance_custody,
             config,
+            config_path: self.home.join(crate::CONFIG_FILE_NAME),
         };
         Ok((app, self.cmd))
     }
 }
 
+/// The TLS configuration to connect to a remote view service at `url` with, if it uses TLS.
+fn view_tls_config(url: &url::Url) -> Option<ClientTlsConfig> {
+    (url.scheme() == "https").then(ClientTlsConfig::new)
+}
+
+/// Whether `url` points at the local machine.
+fn is_loopback(url: &url::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => domain == "localhost",
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
 fn default_home() -> Utf8PathBuf {
     let path = ProjectDirs::from("zone", "penumbra", "pcli")
         .expect("Failed to get platform data dir")
@@ -148,3 +209,17 @@ fn default_home() -> Utf8PathBuf {
         .to_path_buf();
     Utf8PathBuf::from_path_buf(path).expect("Platform default data dir was not UTF-8")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_view_service_uses_tls_over_https() {
+        let https = url::Url::parse("https://view.example.com").unwrap();
+        assert!(view_tls_config(&https).is_some());
+
+        let http = url::Url::parse("http://127.0.0.1:8081").unwrap();
+        assert!(view_tls_config(&http).is_none());
+    }
+}
@@ -19,7 +19,9 @@ use penumbra_proto::{
     custody::v1::custody_service_server::CustodyServiceServer,
     view::v1::view_service_server::ViewServiceServer,
 };
-use penumbra_view::{Storage, ViewServer};
+use penumbra_view::auth::{AuthConfig, AuthInterceptor, AuthToken};
+use penumbra_view::price::PriceFeedConfig;
+use penumbra_view::{ScanFilter, Storage, ViewServer};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
@@ -51,6 +53,22 @@ pub struct PclientdConfig {
     pub bind_addr: SocketAddr,
     /// Optional KMS config for custody mode
     pub kms_config: Option<soft_kms::Config>,
+    /// Optional bearer-token authentication for the view service.
+    ///
+    /// If unset, the view service is served to any client that can connect
+    /// to `bind_addr`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+    /// Optional price feed, used to value balances and transactions.
+    ///
+    /// Disabled unless set, since fetching prices contacts a third party.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_feed: Option<PriceFeedConfig>,
+    /// Sections of compact blocks to skip while syncing.
+    ///
+    /// Enabling a section that was previously skipped resyncs from genesis.
+    #[serde(default, skip_serializing_if = "ScanFilter::is_empty")]
+    pub scan_filter: ScanFilter,
 }
 
 impl PclientdConfig {
@@ -111,6 +129,11 @@ pub enum Command {
     },
     /// Start running `pclientd`.
     Start {},
+    /// Issue a new bearer token for the view service.
+    ///
+    /// The token is printed to stdout, and its digest is recorded in the
+    /// config file, enabling authentication if it was not already enabled.
+    IssueToken {},
     /// Delete `pclientd` storage to reset local state.
     Reset {},
 }
@@ -271,6 +294,9 @@ impl Opt {
 
                 let client_config = PclientdConfig {
                     kms_config,
+                    auth: None,
+                    price_feed: None,
+                    scan_filter: Default::default(),
                     full_viewing_key,
                     grpc_url: grpc_url.clone(),
                     bind_addr: *bind_addr,
@@ -289,6 +315,23 @@ impl Opt {
 
                 Ok(())
             }
+            Command::IssueToken {} => {
+                let mut config = PclientdConfig::load(opt.config_path()).context(
+                    "Failed to load pclientd config file. Have you run `pclientd init` with a FVK?",
+                )?;
+
+                let token = AuthToken::generate(rand_core::OsRng);
+                config
+                    .auth
+                    .get_or_insert_with(Default::default)
+                    .authorized_tokens
+                    .push(token.digest(&config.full_viewing_key.wallet_id()));
+                config.save(opt.config_path())?;
+
+                println!("{}", token);
+
+                Ok(())
+            }
             Command::Start {} => {
                 let config = PclientdConfig::load(opt.config_path()).context(
                     "Failed to load pclientd config file. Have you run `pclientd init` with a FVK?",
@@ -317,8 +360,38 @@ impl Opt {
                 let compact_block_query_proxy = CompactBlockQueryProxy(proxy_channel.clone());
                 let tendermint_proxy_proxy = TendermintProxyProxy(proxy_channel.clone());
 
+                let wallet_id = config.full_viewing_key.wallet_id();
+                let auth_interceptor = match &config.auth {
+                    Some(auth_config) => {
+                        tracing::info!(
+                            authorized_tokens = auth_config.authorized_tokens.len(),
+                            "requiring bearer token authentication for view service"
+                        );
+                        // pclientd serves plaintext gRPC, so unless clients connect over
+                        // loopback, the bearer token is only protected by a TLS-terminating
+                        // proxy in front of it.
+                        if !config.bind_addr.ip().is_loopback() {
+                            tracing::warn!(
+                                bind_addr = %config.bind_addr,
+                                "view service bearer tokens are sent in plaintext; expose pclientd only through a TLS-terminating proxy"
+                            );
+                        }
+                        AuthInterceptor::new(wallet_id, auth_config)
+                    }
+                    None => AuthInterceptor::disabled(wallet_id),
+                };
+                if storage.set_scan_filter(config.scan_filter).await? {
+                    tracing::warn!(
+                        "scan filter enables previously skipped sections, resyncing from genesis"
+                    );
+                }
+                let view_server = ViewServer::new(storage, config.grpc_url).await?;
+                let price_feed = config.price_feed.map(|price_feed| {
+                    tracing::info!(url = %price_feed.provider.url, "enabling price feed");
+                    view_server.enable_price_feed(price_feed.into())
+                });
                 let view_service =
-                    ViewServiceServer::new(ViewServer::new(storage, config.grpc_url).await?);
+                    ViewServiceServer::with_interceptor(view_server, auth_interceptor);
                 let custody_service = config.kms_config.as_ref().map(|kms_config| {
                     CustodyServiceServer::new(SoftKms::new(kms_config.spend_key.clone().into()))
                 });
@@ -348,8 +421,14 @@ impl Opt {
                     ))
                     .serve(config.bind_addr);
 
-                tokio::spawn(server).await??;
+                let result = tokio::spawn(server).await;
+
+                // Stop refreshing prices once the server has shut down.
+                if let Some(price_feed) = price_feed {
+                    price_feed.abort();
+                }
 
+                result??;
                 Ok(())
             }
         }
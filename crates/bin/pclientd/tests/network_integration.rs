@@ -42,6 +42,9 @@ fn generate_config() -> anyhow::Result<PclientdConfig> {
             spend_key: test_keys::SPEND_KEY.clone(),
             auth_policy: Vec::new(),
         }),
+        auth: None,
+        price_feed: None,
+        scan_filter: Default::default(),
     })
 }
 
@@ -137,6 +137,25 @@ pub enum RootCommand {
         #[clap(long, display_order = 400)]
         migrate_archive: Option<PathBuf>,
     },
+    /// Inspect the state of the full node, for debugging.
+    Debug {
+        #[clap(subcommand)]
+        cmd: DebugCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DebugCommand {
+    /// Print every key and hex-encoded value in the latest state of the full node.
+    DumpState {
+        /// The home directory of the full node.
+        #[clap(long, env = "PENUMBRA_PD_HOME", display_order = 100)]
+        home: PathBuf,
+        /// Annotate each key with the component, key template and value type it was declared
+        /// with in the state key schema, flagging keys that no component declared.
+        #[clap(long, display_order = 200)]
+        schema: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -151,6 +170,8 @@ pub enum TestnetCommand {
         #[clap(long)]
         epoch_duration: Option<u64>,
         /// Number of blocks that must elapse before unbonding stake is released.
+        ///
+        /// Defaults to two epochs plus one block if `--epoch-duration` is set.
         #[clap(long)]
         unbonding_delay: Option<u64>,
         /// Maximum number of validators in the consensus set.
@@ -165,6 +186,11 @@ pub enum TestnetCommand {
         /// Path to JSON file containing initial validator configs [default: latest testnet].
         #[clap(long, parse(from_os_str))]
         validators_input_file: Option<PathBuf>,
+        /// Path to TOML file customizing the genesis: chain parameters, additional
+        /// allocations, and IBC clients. Chain parameters set by other flags take
+        /// precedence over those in this file.
+        #[clap(long, parse(from_os_str))]
+        genesis_config_file: Option<PathBuf>,
         /// Testnet name [default: latest testnet].
         #[clap(long)]
         chain_id: Option<String>,
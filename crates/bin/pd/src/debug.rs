@@ -0,0 +1,91 @@
+//! Debugging utilities for inspecting the state of a full node.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use cnidarium::{EscapedByteSlice, StateRead, Storage};
+use cnidarium_component::{StateKeySchema, Store};
+use futures::StreamExt as _;
+use penumbra_app::SUBSTORE_PREFIXES;
+
+/// Prints every key of the latest state of the node at `home`, along with its hex-encoded value.
+///
+/// If `with_schema` is set, each key is annotated with the component, key template and value type
+/// it was declared with, and keys that no component declared are flagged as such.
+pub async fn dump_state(home: PathBuf, with_schema: bool) -> anyhow::Result<()> {
+    let rocksdb_home = home.join("rocksdb");
+    let storage = Storage::load(rocksdb_home, SUBSTORE_PREFIXES.to_vec())
+        .await
+        .context("Unable to initialize RocksDB storage")?;
+    let schema = with_schema
+        .then(penumbra_app::state_key_schema)
+        .transpose()?;
+
+    // Prefix queries are routed to a single substore, so we walk the main store and each substore
+    // in turn. The substores return keys without their prefix, so we add it back.
+    let mut prefixes = vec![String::new()];
+    prefixes.extend(SUBSTORE_PREFIXES.iter().map(|prefix| format!("{prefix}/")));
+
+    let snapshot = storage.latest_snapshot();
+    let mut undeclared = 0;
+    for prefix in &prefixes {
+        let mut stream = snapshot.prefix_raw(prefix);
+        while let Some((key, value)) = stream.next().await.transpose()? {
+            let key = format!("{prefix}{key}");
+            undeclared += print_entry(schema.as_ref(), Store::Verifiable, key.as_bytes(), &value);
+        }
+    }
+    for prefix in &prefixes {
+        let mut stream = snapshot.nonverifiable_prefix_raw(prefix.as_bytes());
+        while let Some((key, value)) = stream.next().await.transpose()? {
+            let key = [prefix.as_bytes(), &key].concat();
+            undeclared += print_entry(schema.as_ref(), Store::Nonverifiable, &key, &value);
+        }
+    }
+    drop(snapshot);
+    storage.release().await;
+
+    if with_schema {
+        eprintln!("found {undeclared} undeclared state keys");
+    }
+    Ok(())
+}
+
+/// Prints a single state entry, returning 1 if the key is undeclared in `schema`, 0 otherwise.
+fn print_entry(schema: Option<&StateKeySchema>, store: Store, key: &[u8], value: &[u8]) -> usize {
+    let store_name = match store {
+        Store::Verifiable => "verifiable",
+        Store::Nonverifiable => "nonverifiable",
+    };
+    let key_display = EscapedByteSlice(key);
+    let value = hex::encode(value);
+
+    let Some(schema) = schema else {
+        println!("{store_name}\t{key_display:?}\t{value}");
+        return 0;
+    };
+
+    // The main store holds the root hash of each substore under the substore's prefix.
+    let is_substore_root = store == Store::Verifiable
+        && SUBSTORE_PREFIXES
+            .iter()
+            .any(|prefix| prefix.as_bytes() == key);
+
+    match schema.lookup(store, key) {
+        Some(declaration) => {
+            println!(
+                "{store_name}\t{key_display:?}\t{}\t{}\t{}\t{value}",
+                declaration.component, declaration.template, declaration.value_type
+            );
+            0
+        }
+        None if is_substore_root => {
+            println!("{store_name}\t{key_display:?}\tcnidarium\tsubstore root hash\t-\t{value}");
+            0
+        }
+        None => {
+            println!("{store_name}\t{key_display:?}\tUNDECLARED\t-\t-\t{value}");
+            1
+        }
+    }
+}
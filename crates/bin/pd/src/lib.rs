@@ -8,6 +8,7 @@
 mod metrics;
 
 pub mod cli;
+pub mod debug;
 pub mod migrate;
 pub mod testnet;
 pub mod zipserve;
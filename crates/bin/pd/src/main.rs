@@ -11,7 +11,7 @@ use anyhow::Context;
 use cnidarium::{StateDelta, Storage};
 use metrics_exporter_prometheus::PrometheusBuilder;
 use pd::{
-    cli::{Opt, RootCommand, TestnetCommand},
+    cli::{DebugCommand, Opt, RootCommand, TestnetCommand},
     migrate::Migration::Testnet70,
     testnet::{
         config::{get_testnet_dir, parse_tm_address, url_has_necessary_parts},
@@ -100,6 +100,8 @@ async fn main() -> anyhow::Result<()> {
             let storage = Storage::load(rocksdb_home, SUBSTORE_PREFIXES.to_vec())
                 .await
                 .context("Unable to initialize RocksDB storage")?;
+            // In debug builds, reject writes to state keys that no component declared.
+            storage.set_key_guard(penumbra_app::state_key_schema()?.key_guard());
 
             tracing::info!(
                 ?abci_bind,
@@ -304,6 +306,7 @@ async fn main() -> anyhow::Result<()> {
                     active_validator_limit,
                     allocations_input_file,
                     validators_input_file,
+                    genesis_config_file,
                     chain_id,
                     preserve_chain_id,
                     external_addresses,
@@ -359,6 +362,7 @@ async fn main() -> anyhow::Result<()> {
                 Some(external_addresses),
                 allocations_input_file,
                 validators_input_file,
+                genesis_config_file,
                 timeout_commit,
                 active_validator_limit,
                 epoch_duration,
@@ -447,6 +451,11 @@ async fn main() -> anyhow::Result<()> {
                 tracing::info!("migration complete: {}", target_directory.display());
             }
         }
+        RootCommand::Debug {
+            cmd: DebugCommand::DumpState { home, schema },
+        } => {
+            pd::debug::dump_state(home, schema).await?;
+        }
     }
     Ok(())
 }
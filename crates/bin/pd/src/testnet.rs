@@ -3,4 +3,5 @@
 
 pub mod config;
 pub mod generate;
+pub mod genesis;
 pub mod join;
@@ -2,19 +2,14 @@
 //! Used for deploying (approximately weekly) testnets
 //! for Penumbra.
 use crate::testnet::config::{get_testnet_dir, TestnetTendermintConfig, ValidatorKeys};
+use crate::testnet::genesis::GenesisConfig;
 use anyhow::{Context, Result};
-use penumbra_app::params::AppParameters;
-use penumbra_governance::genesis::Content as GovernanceContent;
+use penumbra_app::genesis::GenesisBuilder;
 use penumbra_keys::{keys::SpendKey, Address};
-use penumbra_sct::genesis::Content as SctContent;
-use penumbra_sct::params::SctParameters;
-use penumbra_shielded_pool::{
-    genesis::{self as shielded_pool_genesis, Allocation, Content as ShieldedPoolContent},
-    params::ShieldedPoolParameters,
-};
+use penumbra_shielded_pool::genesis::{self as shielded_pool_genesis, Allocation};
 use penumbra_stake::{
-    genesis::Content as StakeContent, params::StakeParameters, validator::Validator,
-    DelegationToken, FundingStream, FundingStreams, GovernanceKey, IdentityKey,
+    validator::Validator, DelegationToken, FundingStream, FundingStreams, GovernanceKey,
+    IdentityKey,
 };
 use serde::{de, Deserialize};
 use std::{
@@ -53,9 +48,11 @@ pub struct TestnetConfig {
 }
 
 impl TestnetConfig {
-    /// Create a new testnet configuration, optionally customizing the allocations and validator
-    /// set. By default, will use the prepared Discord allocations and Penumbra Labs CI validator
-    /// configs.
+    /// Create a new testnet configuration, optionally customizing the allocations, validator
+    /// set, and genesis. By default, will use the prepared Discord allocations and Penumbra Labs
+    /// CI validator configs.
+    ///
+    /// Chain parameters given as arguments take precedence over those in the genesis config.
     #[allow(clippy::too_many_arguments)]
     pub fn generate(
         chain_id: &str,
@@ -64,6 +61,7 @@ impl TestnetConfig {
         external_addresses: Option<Vec<TendermintAddress>>,
         allocations_input_file: Option<PathBuf>,
         validators_input_file: Option<PathBuf>,
+        genesis_config_file: Option<PathBuf>,
         tendermint_timeout_commit: Option<tendermint::Timeout>,
         active_validator_limit: Option<u64>,
         epoch_duration: Option<u64>,
@@ -90,10 +88,16 @@ impl TestnetConfig {
             testnet_validators.iter().map(|v| v.try_into()).collect();
         let validators = validators?;
 
+        let genesis_config = genesis_config_file
+            .map(|path| GenesisConfig::from_toml_file(&path))
+            .transpose()?
+            .unwrap_or_default();
+
         let app_state = Self::make_genesis_content(
             chain_id,
             allocations,
             validators.to_vec(),
+            genesis_config,
             active_validator_limit,
             epoch_duration,
             unbonding_delay,
@@ -178,54 +182,59 @@ impl TestnetConfig {
 
     /// Create a full genesis configuration for inclusion in the tendermint
     /// genesis config.
+    #[allow(clippy::too_many_arguments)]
     fn make_genesis_content(
         chain_id: &str,
         allocations: Vec<Allocation>,
         validators: Vec<Validator>,
+        genesis_config: GenesisConfig,
         active_validator_limit: Option<u64>,
         epoch_duration: Option<u64>,
         unbonding_delay: Option<u64>,
         proposal_voting_blocks: Option<u64>,
     ) -> anyhow::Result<penumbra_app::genesis::Content> {
-        let default_gov_params = penumbra_governance::params::GovernanceParameters::default();
-
-        let gov_params = penumbra_governance::params::GovernanceParameters {
-            proposal_voting_blocks: proposal_voting_blocks
-                .unwrap_or(default_gov_params.proposal_voting_blocks),
-            ..default_gov_params
-        };
+        let builder = GenesisBuilder::new(chain_id)
+            .with_allocations(allocations)
+            .with_validators(validators);
+
+        // Unbonding must take at least two epochs, so if only the epoch duration was customized,
+        // shorten or lengthen the unbonding delay to match it.
+        let derive_unbonding_delay = unbonding_delay.is_none()
+            && !genesis_config.sets_param("stake_params", "unbonding_delay")
+            && (epoch_duration.is_some()
+                || genesis_config.sets_param("sct_params", "epoch_duration"));
+
+        let mut builder = genesis_config.apply(builder)?.update_params(|params| {
+            if let Some(active_validator_limit) = active_validator_limit {
+                params.stake_params.active_validator_limit = active_validator_limit;
+            }
+            if let Some(epoch_duration) = epoch_duration {
+                params.sct_params.epoch_duration = epoch_duration;
+            }
+            if let Some(unbonding_delay) = unbonding_delay {
+                params.stake_params.unbonding_delay = unbonding_delay;
+            }
+            if let Some(proposal_voting_blocks) = proposal_voting_blocks {
+                params.governance_params.proposal_voting_blocks = proposal_voting_blocks;
+            }
+        });
+
+        if derive_unbonding_delay {
+            let epoch_duration = builder.app_params().sct_params.epoch_duration;
+            let unbonding_delay = epoch_duration
+                .checked_mul(2)
+                .and_then(|delay| delay.checked_add(1))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "epoch duration {epoch_duration} is too long to derive an unbonding delay from"
+                    )
+                })?;
+            builder = builder.update_params(|params| {
+                params.stake_params.unbonding_delay = unbonding_delay;
+            });
+        }
 
-        // Look up default app params, so we can fill in defaults.
-        let default_app_params = AppParameters::default();
-
-        let app_state = penumbra_app::genesis::Content {
-            chain_id: chain_id.to_string(),
-            stake_content: StakeContent {
-                validators: validators.into_iter().map(Into::into).collect(),
-                stake_params: StakeParameters {
-                    active_validator_limit: active_validator_limit
-                        .unwrap_or(default_app_params.stake_params.active_validator_limit),
-                    unbonding_delay: unbonding_delay
-                        .unwrap_or(default_app_params.stake_params.unbonding_delay),
-                    ..Default::default()
-                },
-            },
-            governance_content: GovernanceContent {
-                governance_params: gov_params,
-            },
-            shielded_pool_content: ShieldedPoolContent {
-                shielded_pool_params: ShieldedPoolParameters::default(),
-                allocations: allocations.clone(),
-            },
-            sct_content: SctContent {
-                sct_params: SctParameters {
-                    epoch_duration: epoch_duration
-                        .unwrap_or(default_app_params.sct_params.epoch_duration),
-                },
-            },
-            ..Default::default()
-        };
-        Ok(app_state)
+        builder.build()
     }
 
     /// Build Tendermint genesis data, based on Penumbra initial application state.
@@ -348,6 +357,7 @@ pub fn testnet_generate(
     external_addresses: Vec<TendermintAddress>,
     validators_input_file: Option<PathBuf>,
     allocations_input_file: Option<PathBuf>,
+    genesis_config_file: Option<PathBuf>,
     proposal_voting_blocks: Option<u64>,
 ) -> anyhow::Result<()> {
     tracing::info!(?chain_id, "Generating network config");
@@ -358,6 +368,7 @@ pub fn testnet_generate(
         Some(external_addresses),
         allocations_input_file,
         validators_input_file,
+        genesis_config_file,
         tendermint_timeout_commit,
         active_validator_limit,
         epoch_duration,
@@ -605,6 +616,13 @@ where
         {
             Ok(v)
         }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            u64::try_from(v).map_err(E::custom)
+        }
     }
 
     deserializer.deserialize_any(U64StringVisitor)
@@ -668,6 +686,7 @@ mod tests {
             None,
             None,
             None,
+            None,
         )?;
         assert_eq!(testnet_config.name, "test-chain-1234");
         assert_eq!(testnet_config.genesis.validators.len(), 0);
@@ -680,6 +699,101 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    /// Customizing only the epoch duration derives a valid unbonding delay from it, even when the
+    /// default unbonding delay would be shorter than two epochs.
+    fn epoch_duration_sets_default_unbonding_delay() -> anyhow::Result<()> {
+        let testnet_config = TestnetConfig::generate(
+            "test-chain-1234",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(2_000),
+            None,
+            None,
+        )?;
+        let penumbra_app::genesis::AppState::Content(app_state) = testnet_config.genesis.app_state
+        else {
+            unimplemented!("TODO: support checkpointed app state")
+        };
+        assert_eq!(app_state.sct_content.sct_params.epoch_duration, 2_000);
+        assert_eq!(app_state.stake_content.stake_params.unbonding_delay, 4_001);
+        Ok(())
+    }
+
+    /// Builds the genesis content for a testnet with no validators, customized by `genesis_config`
+    /// and the given epoch duration and unbonding delay flags.
+    fn genesis_content(
+        genesis_config: &str,
+        epoch_duration: Option<u64>,
+        unbonding_delay: Option<u64>,
+    ) -> anyhow::Result<penumbra_app::genesis::Content> {
+        TestnetConfig::make_genesis_content(
+            "test-chain-1234",
+            Vec::new(),
+            Vec::new(),
+            toml::from_str(genesis_config)?,
+            None,
+            epoch_duration,
+            unbonding_delay,
+            None,
+        )
+    }
+
+    #[test]
+    /// An epoch duration set only in the genesis config also derives the unbonding delay.
+    fn genesis_config_epoch_duration_sets_default_unbonding_delay() -> anyhow::Result<()> {
+        let content = genesis_content(
+            r#"
+            [params.sct_params]
+            epoch_duration = 2000
+            "#,
+            None,
+            None,
+        )?;
+        assert_eq!(content.sct_content.sct_params.epoch_duration, 2_000);
+        assert_eq!(content.stake_content.stake_params.unbonding_delay, 4_001);
+        Ok(())
+    }
+
+    #[test]
+    /// An unbonding delay set in the genesis config is kept when the epoch duration flag is given.
+    fn genesis_config_unbonding_delay_overrides_derived_one() -> anyhow::Result<()> {
+        let content = genesis_content(
+            r#"
+            [params.stake_params]
+            unbonding_delay = 10000
+            "#,
+            Some(2_000),
+            None,
+        )?;
+        assert_eq!(content.sct_content.sct_params.epoch_duration, 2_000);
+        assert_eq!(content.stake_content.stake_params.unbonding_delay, 10_000);
+
+        // The flag still takes precedence over the file.
+        let content = genesis_content(
+            r#"
+            [params.stake_params]
+            unbonding_delay = 10000
+            "#,
+            Some(2_000),
+            Some(5_000),
+        )?;
+        assert_eq!(content.stake_content.stake_params.unbonding_delay, 5_000);
+        Ok(())
+    }
+
+    #[test]
+    /// An epoch duration too long to derive an unbonding delay from is an error.
+    fn overlong_epoch_duration_is_rejected() {
+        assert!(genesis_content("", Some(u64::MAX), None).is_err());
+    }
+
     #[test]
     /// Generate a config suitable for a public testnet: custom validators input file,
     /// increasing the default validators from 1 -> 2.
@@ -697,6 +811,7 @@ mod tests {
             None,
             None,
             None,
+            None,
         )?;
         assert_eq!(testnet_config.name, "test-chain-4567");
         assert_eq!(testnet_config.genesis.validators.len(), 0);
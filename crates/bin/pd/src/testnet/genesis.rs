@@ -0,0 +1,222 @@
+//! Customizations to a generated testnet's genesis, read from a TOML file.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use penumbra_app::{genesis::GenesisBuilder, params::AppParameters};
+use penumbra_ibc::genesis::GenesisClient;
+use penumbra_proto::core::component::ibc::v1 as ibc_pb;
+use penumbra_shielded_pool::genesis::Allocation;
+use serde::Deserialize;
+
+use crate::testnet::generate::TestnetAllocation;
+
+/// Customizations to the genesis of a generated testnet.
+///
+/// These are read from a TOML file, passed to `pd testnet generate` with
+/// `--genesis-config-file`, for instance:
+///
+/// ```toml
+/// # Chain parameters to override, by component, using the same field names
+/// # as the genesis JSON.  Parameters that aren't listed keep their defaults.
+/// [params.sct_params]
+/// epoch_duration = 100
+///
+/// [params.stake_params]
+/// unbonding_delay = 201
+/// active_validator_limit = 10
+///
+/// # Allocations in addition to those from `--allocations-input-file`.
+/// [[allocations]]
+/// amount = "1_000_000"
+/// denom = "upenumbra"
+/// address = "penumbra1..."
+///
+/// # JSON files containing light clients of counterparty chains, as
+/// # `penumbra.core.component.ibc.v1.GenesisClient` messages.  Relative paths
+/// # are resolved from the directory containing this file.
+/// ibc_clients = ["osmosis-client.json"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisConfig {
+    /// Overrides for the default chain parameters.
+    #[serde(default)]
+    pub params: toml::Table,
+    /// Additional initial allocations.
+    #[serde(default)]
+    pub allocations: Vec<TestnetAllocation>,
+    /// Paths to JSON files describing light clients to create at genesis.
+    #[serde(default)]
+    pub ibc_clients: Vec<PathBuf>,
+}
+
+impl GenesisConfig {
+    /// Read a genesis configuration from a TOML file.
+    pub fn from_toml_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("cannot read genesis config file {path:?}"))?;
+        let mut config: Self = toml::from_str(&contents)
+            .with_context(|| format!("could not parse genesis config file {path:?}"))?;
+
+        // Resolve client files relative to the config file, rather than the working directory.
+        if let Some(dir) = path.parent() {
+            for client in config.ibc_clients.iter_mut() {
+                *client = dir.join(client.as_path());
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Whether this config overrides the chain parameter `name` of `component`, e.g.
+    /// `unbonding_delay` of `stake_params`.
+    pub fn sets_param(&self, component: &str, name: &str) -> bool {
+        self.params
+            .iter()
+            .filter(|(key, _)| lower_camel_case(key) == lower_camel_case(component))
+            .filter_map(|(_, params)| params.as_table())
+            .any(|params| {
+                params
+                    .keys()
+                    .any(|key| lower_camel_case(key) == lower_camel_case(name))
+            })
+    }
+
+    /// Apply these customizations to a genesis under construction.
+    pub fn apply(self, builder: GenesisBuilder) -> anyhow::Result<GenesisBuilder> {
+        let params = override_params(builder.app_params().clone(), self.params)?;
+
+        let allocations = self
+            .allocations
+            .into_iter()
+            .enumerate()
+            .map(|(i, allocation)| {
+                Allocation::try_from(allocation)
+                    .with_context(|| format!("invalid allocation {i} in genesis config"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut builder = builder
+            .with_app_params(params)
+            .with_allocations(allocations);
+
+        for path in self.ibc_clients {
+            let client: ibc_pb::GenesisClient = serde_json::from_reader(
+                fs::File::open(&path)
+                    .with_context(|| format!("cannot open IBC client file {path:?}"))?,
+            )
+            .with_context(|| format!("could not parse IBC client file {path:?}"))?;
+            let client = GenesisClient::try_from(client)
+                .with_context(|| format!("invalid IBC client in {path:?}"))?;
+            builder = builder.with_ibc_client(client);
+        }
+
+        Ok(builder)
+    }
+}
+
+/// Overrides the fields of `params` named in `overrides`, leaving the rest unchanged.
+///
+/// This works on the JSON representation of the parameters, so that the
+/// overrides can name any parameter without this code having to know about it.
+fn override_params(params: AppParameters, overrides: toml::Table) -> anyhow::Result<AppParameters> {
+    let mut json = serde_json::to_value(params)?;
+    merge_json(&mut json, serde_json::to_value(overrides)?);
+    serde_json::from_value(json).context("invalid chain parameter overrides in genesis config")
+}
+
+/// Recursively merges `overrides` into `base`.
+fn merge_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                // The JSON encoding uses camelCase field names, but also
+                // accepts snake_case ones when decoding; normalize them, so
+                // that overrides replace existing fields instead of
+                // duplicating them.
+                merge_json(
+                    base.entry(lower_camel_case(&key))
+                        .or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+fn lower_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_are_overridden_by_name() -> anyhow::Result<()> {
+        let config: GenesisConfig = toml::from_str(
+            r#"
+            [params.sct_params]
+            epoch_duration = 100
+
+            [params.stakeParams]
+            unbonding_delay = 201
+            "#,
+        )?;
+
+        let content = config.apply(GenesisBuilder::new("test-chain"))?.build()?;
+        let defaults = AppParameters::default();
+
+        assert_eq!(content.chain_id, "test-chain");
+        assert_eq!(content.sct_content.sct_params.epoch_duration, 100);
+        assert_eq!(content.stake_content.stake_params.unbonding_delay, 201);
+        assert_eq!(
+            content.stake_content.stake_params.active_validator_limit,
+            defaults.stake_params.active_validator_limit
+        );
+        assert_eq!(
+            content.governance_content.governance_params,
+            defaults.governance_params
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_params_are_rejected() -> anyhow::Result<()> {
+        // Unbonding must take at least two epochs.
+        let config: GenesisConfig = toml::from_str(
+            r#"
+            [params.sct_params]
+            epoch_duration = 100
+
+            [params.stake_params]
+            unbonding_delay = 100
+            "#,
+        )?;
+
+        assert!(config
+            .apply(GenesisBuilder::new("test-chain"))?
+            .build()
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use anyhow::{bail, Result};
+use cnidarium::StateWrite;
+
+use crate::Component;
+
+/// A [`Component`] that participates in epoch transitions.
+///
+/// Some components' [`Component::end_epoch`] implementations depend on state
+/// written by other components' epoch transitions: for instance, the staking
+/// component's rate updates consume the issuance budget computed by the
+/// distributions component, and the funding component pays out rewards from
+/// the funding queue prepared by the staking component.  Rather than leaving
+/// this ordering implicit in the sequence of calls made by the application,
+/// each component declares the handlers that must run before it, and the
+/// application runs them through an [`EpochSchedule`].
+pub trait EpochHandler: Component {
+    /// A unique name for this handler, used to declare dependencies.
+    const NAME: &'static str;
+
+    /// The names of the handlers that must complete their epoch transition
+    /// before this one begins.
+    const RUN_AFTER: &'static [&'static str] = &[];
+}
+
+/// The type-erased future returned by a scheduled epoch handler.
+pub type EpochFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// A single registered epoch handler.
+struct ScheduledHandler<S> {
+    name: &'static str,
+    run_after: &'static [&'static str],
+    end_epoch: for<'a> fn(&'a mut Arc<S>) -> EpochFuture<'a>,
+}
+
+fn end_epoch<H: EpochHandler, S: StateWrite + 'static>(state: &mut Arc<S>) -> EpochFuture<'_> {
+    H::end_epoch(state)
+}
+
+/// A deterministic execution order for a set of [`EpochHandler`]s.
+///
+/// Handlers are registered with [`EpochSchedule::register`], and ordered by
+/// [`EpochSchedule::build`] so that every handler runs after all of the
+/// handlers named in its [`EpochHandler::RUN_AFTER`].  Among handlers whose
+/// dependencies are all satisfied, registration order is preserved, so the
+/// resulting order depends only on the registrations, never on any runtime
+/// state.
+pub struct EpochSchedule<S> {
+    handlers: Vec<ScheduledHandler<S>>,
+}
+
+impl<S: StateWrite + 'static> Default for EpochSchedule<S> {
+    fn default() -> Self {
+        Self {
+            handlers: Vec::new(),
+        }
+    }
+}
+
+impl<S: StateWrite + 'static> EpochSchedule<S> {
+    /// Creates an empty schedule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the handler `H`.
+    pub fn register<H: EpochHandler>(mut self) -> Self {
+        self.handlers.push(ScheduledHandler {
+            name: H::NAME,
+            run_after: H::RUN_AFTER,
+            end_epoch: end_epoch::<H, S>,
+        });
+        self
+    }
+
+    /// Registers an epoch transition that isn't an [`EpochHandler`], such as
+    /// that of a component which doesn't implement [`Component`].
+    pub fn register_fn(
+        mut self,
+        name: &'static str,
+        run_after: &'static [&'static str],
+        end_epoch: for<'a> fn(&'a mut Arc<S>) -> EpochFuture<'a>,
+    ) -> Self {
+        self.handlers.push(ScheduledHandler {
+            name,
+            run_after,
+            end_epoch,
+        });
+        self
+    }
+
+    /// Orders the registered handlers so that each one runs after its
+    /// declared dependencies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two handlers share a name, if a handler depends on
+    /// a handler that was not registered, or if the dependencies are cyclic.
+    pub fn build(self) -> Result<Self> {
+        let mut pending = self.handlers;
+        let mut ordered: Vec<ScheduledHandler<S>> = Vec::with_capacity(pending.len());
+
+        for (i, handler) in pending.iter().enumerate() {
+            if pending[..i].iter().any(|h| h.name == handler.name) {
+                bail!("epoch handler {} is registered twice", handler.name);
+            }
+            for dependency in handler.run_after {
+                if !pending.iter().any(|h| h.name == *dependency) {
+                    bail!(
+                        "epoch handler {} depends on unregistered handler {}",
+                        handler.name,
+                        dependency
+                    );
+                }
+            }
+        }
+
+        while !pending.is_empty() {
+            // Pick the earliest-registered handler whose dependencies have all run.
+            let Some(next) = pending.iter().position(|handler| {
+                handler
+                    .run_after
+                    .iter()
+                    .all(|dependency| ordered.iter().any(|h| h.name == *dependency))
+            }) else {
+                let stuck: Vec<_> = pending.iter().map(|h| h.name).collect();
+                bail!("epoch handlers have cyclic dependencies: {:?}", stuck);
+            };
+            ordered.push(pending.remove(next));
+        }
+
+        Ok(Self { handlers: ordered })
+    }
+
+    /// Returns the names of the registered handlers, in execution order.
+    ///
+    /// This is only meaningful after calling [`EpochSchedule::build`].
+    pub fn order(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.handlers.iter().map(|h| h.name)
+    }
+
+    /// Runs every handler's [`Component::end_epoch`], in order.
+    pub async fn end_epoch(&self, state: &mut Arc<S>) -> Result<()> {
+        for handler in &self.handlers {
+            (handler.end_epoch)(state)
+                .await
+                .map_err(|e| e.context(format!("epoch handler {} failed", handler.name)))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use cnidarium::{Snapshot, StateDelta};
+    use tendermint::abci;
+
+    use super::*;
+
+    type State = StateDelta<Snapshot>;
+
+    macro_rules! handler {
+        ($ty:ident, $name:literal, [$($dep:literal),*]) => {
+            struct $ty;
+
+            #[async_trait]
+            impl Component for $ty {
+                type AppState = ();
+
+                async fn init_chain<S: StateWrite>(_state: S, _app_state: Option<&()>) {}
+
+                async fn begin_block<S: StateWrite + 'static>(
+                    _state: &mut Arc<S>,
+                    _begin_block: &abci::request::BeginBlock,
+                ) {
+                }
+
+                async fn end_block<S: StateWrite + 'static>(
+                    _state: &mut Arc<S>,
+                    _end_block: &abci::request::EndBlock,
+                ) {
+                }
+            }
+
+            impl EpochHandler for $ty {
+                const NAME: &'static str = $name;
+                const RUN_AFTER: &'static [&'static str] = &[$($dep),*];
+            }
+        };
+    }
+
+    handler!(A, "a", []);
+    handler!(B, "b", ["c"]);
+    handler!(C, "c", []);
+    handler!(D, "d", ["a", "b"]);
+    handler!(Cyclic, "cyclic", ["cyclic"]);
+    handler!(Orphan, "orphan", ["missing"]);
+
+    #[test]
+    fn dependencies_run_first_and_ties_keep_registration_order() {
+        let schedule = EpochSchedule::<State>::new()
+            .register::<D>()
+            .register::<A>()
+            .register::<B>()
+            .register::<C>()
+            .build()
+            .unwrap();
+
+        assert_eq!(schedule.order().collect::<Vec<_>>(), ["a", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn invalid_schedules_are_rejected() {
+        assert!(EpochSchedule::<State>::new()
+            .register::<A>()
+            .register::<A>()
+            .build()
+            .is_err());
+        assert!(EpochSchedule::<State>::new()
+            .register::<Orphan>()
+            .build()
+            .is_err());
+        assert!(EpochSchedule::<State>::new()
+            .register::<Cyclic>()
+            .build()
+            .is_err());
+    }
+}
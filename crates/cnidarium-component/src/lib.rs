@@ -8,6 +8,17 @@
 //! - [`ActionHandler`], which defines the _externally driven_ behavior of a
 //! component, triggered by actions in blockchain transactions.
 //!
+//! Components whose epoch transitions depend on one another additionally
+//! implement [`EpochHandler`], declaring which other handlers must run first;
+//! the application runs them in a deterministic order using an
+//! [`EpochSchedule`].
+//!
+//! Components also declare the families of state keys they own, and the types
+//! of the values stored under them, in a [`StateKeySchema`]. The application
+//! assembles the schema for all of its components, which catches collisions
+//! between the keys of different components, and in debug builds installs it
+//! as a guard on the storage layer.
+//!
 //! Component crates should be structured as follows:
 //!
 //! - Definitions of any transaction actions related to the component, and their
@@ -17,7 +28,8 @@
 //!   any locally-defined actions, and any other code touching the chain state
 //!   inside;
 //! - a `crate::state_key` module defining the component's state keys (which are
-//!   a public API, like the rest of the chain state);
+//!   a public API, like the rest of the chain state), along with a
+//!   `declare_schema` function declaring them in a [`StateKeySchema`];
 //! - a `crate::event` module defining any events emitted by the component;
 //!
 //! The structure of the feature-gated `component` submodule allows reusing data
@@ -42,6 +54,10 @@
 
 mod action_handler;
 mod component;
+mod epoch_handler;
+mod state_key_schema;
 
 pub use action_handler::ActionHandler;
 pub use component::Component;
+pub use epoch_handler::{EpochFuture, EpochHandler, EpochSchedule};
+pub use state_key_schema::{ComponentKeys, KeyDeclaration, StateKeySchema, Store};
@@ -0,0 +1,240 @@
+use anyhow::{bail, Result};
+use cnidarium::KeyGuard;
+
+/// Which of the two key-value stores a state key lives in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Store {
+    /// The consensus-critical state, stored in the JMT.
+    Verifiable,
+    /// The non-consensus-critical state, stored alongside the JMT.
+    Nonverifiable,
+}
+
+/// A family of state keys declared by a component.
+///
+/// The keys are described by a template, made of literal text and `{name}` placeholders, e.g.
+/// `governance/proposal/{proposal_id}/state`. A placeholder matches one or more bytes other than
+/// `/`, except for a placeholder at the end of the template, which matches the rest of the key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyDeclaration {
+    /// The name of the component owning the keys.
+    pub component: &'static str,
+    /// The store the keys live in.
+    pub store: Store,
+    /// The template describing the keys.
+    pub template: &'static str,
+    /// The Rust type of the values stored under the keys.
+    pub value_type: &'static str,
+}
+
+impl KeyDeclaration {
+    /// The literal prefix shared by all keys matching the template.
+    pub fn prefix(&self) -> &'static str {
+        match self.template.find('{') {
+            Some(i) => &self.template[..i],
+            None => self.template,
+        }
+    }
+
+    /// Whether `key` matches the template.
+    pub fn matches(&self, key: &[u8]) -> bool {
+        matches_template(self.template.as_bytes(), key)
+    }
+}
+
+fn matches_template(template: &[u8], key: &[u8]) -> bool {
+    match template.iter().position(|&b| b == b'{') {
+        None => template == key,
+        Some(start) => {
+            let (literal, rest) = template.split_at(start);
+            let Some(key) = key.strip_prefix(literal) else {
+                return false;
+            };
+            let rest = match rest.iter().position(|&b| b == b'}') {
+                Some(end) => &rest[end + 1..],
+                None => return false,
+            };
+            if rest.is_empty() {
+                return !key.is_empty();
+            }
+            // Try every non-empty segment-local match for the placeholder.
+            (1..=key.len())
+                .take_while(|&i| key[i - 1] != b'/')
+                .any(|i| matches_template(rest, &key[i..]))
+        }
+    }
+}
+
+/// A registry of the state keys declared by every component of an application.
+///
+/// Components declare the key families they own with [`StateKeySchema::component`]. Declaring a
+/// key family whose prefix overlaps one owned by a different component is an error, so that
+/// adding a new component can't silently collide with the keys of an existing one.
+#[derive(Clone, Debug, Default)]
+pub struct StateKeySchema {
+    declarations: Vec<KeyDeclaration>,
+}
+
+impl StateKeySchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts declaring the keys owned by the component `name`.
+    pub fn component(&mut self, name: &'static str) -> ComponentKeys<'_> {
+        ComponentKeys { schema: self, name }
+    }
+
+    fn declare(&mut self, declaration: KeyDeclaration) -> Result<()> {
+        let prefix = declaration.prefix();
+        for existing in &self.declarations {
+            if existing.store != declaration.store {
+                continue;
+            }
+            if existing.template == declaration.template {
+                bail!(
+                    "state key {} is declared twice, by {} and {}",
+                    declaration.template,
+                    existing.component,
+                    declaration.component
+                );
+            }
+            if existing.component != declaration.component
+                && (existing.prefix().starts_with(prefix) || prefix.starts_with(existing.prefix()))
+            {
+                bail!(
+                    "state key {} of component {} collides with {} of component {}",
+                    declaration.template,
+                    declaration.component,
+                    existing.template,
+                    existing.component
+                );
+            }
+        }
+        self.declarations.push(declaration);
+        Ok(())
+    }
+
+    /// All the declared key families, in declaration order.
+    pub fn declarations(&self) -> &[KeyDeclaration] {
+        &self.declarations
+    }
+
+    /// Finds the declaration describing `key`, preferring the one with the longest prefix if
+    /// several templates match.
+    pub fn lookup(&self, store: Store, key: &[u8]) -> Option<&KeyDeclaration> {
+        self.declarations
+            .iter()
+            .filter(|d| d.store == store && d.matches(key))
+            .max_by_key(|d| d.prefix().len())
+    }
+
+    /// A storage guard allowing writes only to the declared key families.
+    pub fn key_guard(&self) -> KeyGuard {
+        let prefixes = |store| {
+            self.declarations
+                .iter()
+                .filter(move |d| d.store == store)
+                .map(|d| d.prefix())
+        };
+        KeyGuard::new(
+            prefixes(Store::Verifiable).map(str::to_string),
+            prefixes(Store::Nonverifiable).map(|p| p.as_bytes().to_vec()),
+        )
+    }
+}
+
+/// Declares the keys owned by a single component, returned by [`StateKeySchema::component`].
+pub struct ComponentKeys<'a> {
+    schema: &'a mut StateKeySchema,
+    name: &'static str,
+}
+
+impl<'a> ComponentKeys<'a> {
+    /// Declares a family of keys in the verifiable store holding values of type `T`.
+    pub fn verifiable<T: ?Sized>(&mut self, template: &'static str) -> Result<&mut Self> {
+        self.declare::<T>(Store::Verifiable, template)
+    }
+
+    /// Declares a family of keys in the nonverifiable store holding values of type `T`.
+    pub fn nonverifiable<T: ?Sized>(&mut self, template: &'static str) -> Result<&mut Self> {
+        self.declare::<T>(Store::Nonverifiable, template)
+    }
+
+    fn declare<T: ?Sized>(&mut self, store: Store, template: &'static str) -> Result<&mut Self> {
+        self.schema.declare(KeyDeclaration {
+            component: self.name,
+            store,
+            template,
+            value_type: std::any::type_name::<T>(),
+        })?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_match_keys() -> Result<()> {
+        let mut schema = StateKeySchema::new();
+        schema
+            .component("governance")
+            .verifiable::<u64>("governance/next_proposal_id")?
+            .verifiable::<String>("governance/proposal/{proposal_id}/state")?
+            .verifiable::<u64>("governance/proposal/{proposal_id}/voted_nullifiers/{nullifier}")?;
+        schema
+            .component("dex")
+            .nonverifiable::<[u8]>("dex/ra/{from}/{key}")?;
+
+        let lookup = |store, key: &[u8]| schema.lookup(store, key).map(|d| d.template);
+        assert_eq!(
+            lookup(Store::Verifiable, b"governance/proposal/0001/state"),
+            Some("governance/proposal/{proposal_id}/state")
+        );
+        assert_eq!(
+            lookup(
+                Store::Verifiable,
+                b"governance/proposal/0001/voted_nullifiers/abcd"
+            ),
+            Some("governance/proposal/{proposal_id}/voted_nullifiers/{nullifier}")
+        );
+        assert_eq!(
+            lookup(Store::Verifiable, b"governance/proposal/0001/other"),
+            None
+        );
+        assert_eq!(
+            lookup(Store::Verifiable, b"governance/next_proposal_id/extra"),
+            None
+        );
+        // Binary keys can contain slashes in their final placeholder.
+        assert_eq!(
+            lookup(Store::Nonverifiable, b"dex/ra/\x01/\x02/\x03"),
+            Some("dex/ra/{from}/{key}")
+        );
+        assert_eq!(lookup(Store::Nonverifiable, b"dex/ra/\x01/"), None);
+        assert_eq!(lookup(Store::Verifiable, b"dex/ra/\x01/\x02"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn colliding_components_are_rejected() -> Result<()> {
+        let mut schema = StateKeySchema::new();
+        schema.component("dex").verifiable::<u64>("dex/params")?;
+        assert!(schema
+            .component("auction")
+            .verifiable::<u64>("dex/params/{id}")
+            .is_err());
+        assert!(schema.component("oracle").verifiable::<u64>("de").is_err());
+        // The same prefix in the other store doesn't collide.
+        schema
+            .component("auction")
+            .nonverifiable::<u64>("dex/params/{id}")?;
+        // Nor do overlapping prefixes within a component.
+        schema
+            .component("dex")
+            .verifiable::<u64>("dex/params/{id}")?;
+        Ok(())
+    }
+}
@@ -119,6 +119,40 @@ impl Snapshot {
         ))
     }
 
+    /// Returns the value corresponding to the key, along with an ICS23 membership
+    /// proof up to the current JMT root hash.
+    ///
+    /// # Errors
+    /// Returns an error if the key is not present in this snapshot.
+    pub async fn get_with_existence_proof(&self, key: Vec<u8>) -> Result<(Vec<u8>, MerkleProof)> {
+        let (value, proof) = self.get_with_proof(key.clone()).await?;
+        let Some(value) = value else {
+            anyhow::bail!(
+                "cannot prove membership of absent key (key={}, version={})",
+                String::from_utf8_lossy(&key),
+                self.version()
+            )
+        };
+        Ok((value, proof))
+    }
+
+    /// Returns an ICS23 non-membership proof for the key, up to the current JMT
+    /// root hash.
+    ///
+    /// # Errors
+    /// Returns an error if the key is present in this snapshot.
+    pub async fn get_non_existence_proof(&self, key: Vec<u8>) -> Result<MerkleProof> {
+        let (value, proof) = self.get_with_proof(key.clone()).await?;
+        if value.is_some() {
+            anyhow::bail!(
+                "cannot prove non-membership of present key (key={}, version={})",
+                String::from_utf8_lossy(&key),
+                self.version()
+            )
+        }
+        Ok(proof)
+    }
+
     pub fn prefix_version(&self, prefix: &str) -> Result<Option<jmt::Version>> {
         let Some(config) = self
             .0
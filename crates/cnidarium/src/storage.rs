@@ -1,6 +1,7 @@
 use std::{path::PathBuf, sync::Arc};
 
 use anyhow::{bail, ensure, Result};
+use ibc_types::core::commitment::MerkleProof;
 use parking_lot::RwLock;
 use rocksdb::{Options, DB};
 use std::collections::HashMap;
@@ -17,7 +18,9 @@ use crate::{
 };
 use crate::{snapshot_cache::SnapshotCache, StagedWriteBatch, StateDelta};
 
+mod guard;
 mod temp;
+pub use guard::KeyGuard;
 pub use temp::TempStorage;
 
 /// A handle for a storage instance, backed by RocksDB.
@@ -44,6 +47,8 @@ struct Inner {
     /// This is used by `Storage::release` to wait for the task to terminate.
     jh_dispatcher: Option<tokio::task::JoinHandle<()>>,
     db: Arc<DB>,
+    /// The key prefixes commits are allowed to write to, checked in debug builds.
+    key_guard: RwLock<Option<KeyGuard>>,
 }
 
 impl Storage {
@@ -225,6 +230,7 @@ impl Storage {
                         multistore_config,
                         snapshots,
                         db: shared_db,
+                        key_guard: RwLock::new(None),
                     })))
                 })
             })
@@ -268,6 +274,32 @@ impl Storage {
         self.0.snapshots.read().get(version)
     }
 
+    /// Returns some value corresponding to the key at the given `version`, along
+    /// with an ICS23 existence proof up to the JMT root hash at that version. If
+    /// the key is not present, returns `None` and a non-existence proof.
+    ///
+    /// Proofs can only be produced for versions retained in the snapshot cache.
+    ///
+    /// # Errors
+    /// Returns an error if no snapshot is available for `version`.
+    pub async fn get_with_proof_at(
+        &self,
+        key: Vec<u8>,
+        version: jmt::Version,
+    ) -> Result<(Option<Vec<u8>>, MerkleProof)> {
+        let Some(snapshot) = self.snapshot(version) else {
+            bail!("no snapshot available for version {version}")
+        };
+        snapshot.get_with_proof(key).await
+    }
+
+    /// Installs a [`KeyGuard`], restricting which keys later commits may write to.
+    ///
+    /// The guard is only checked in debug builds.
+    pub fn set_key_guard(&self, guard: KeyGuard) {
+        *self.0.key_guard.write() = Some(guard);
+    }
+
     /// Prepares a commit for the provided [`StateDelta`], returning a [`StagedWriteBatch`].
     /// The batch can be committed to the database using the [`Storage::commit_batch`] method.
     pub async fn prepare_commit(&self, delta: StateDelta<Snapshot>) -> Result<StagedWriteBatch> {
@@ -288,6 +320,11 @@ impl Storage {
             prev_storage_version
         );
 
+        #[cfg(debug_assertions)]
+        if let Some(guard) = self.0.key_guard.read().as_ref() {
+            guard.check(&changes)?;
+        }
+
         self.prepare_commit_inner(snapshot, changes, next_storage_version, false)
             .await
     }
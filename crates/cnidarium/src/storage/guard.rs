@@ -0,0 +1,59 @@
+use anyhow::{bail, Result};
+
+use crate::{cache::Cache, EscapedByteSlice};
+
+/// The key prefixes that commits to a [`Storage`](super::Storage) are allowed to write to.
+///
+/// Once installed with [`Storage::set_key_guard`](super::Storage::set_key_guard), debug builds
+/// reject any commit that writes a key outside of these prefixes, so that a component writing to
+/// a key it never declared (and which might collide with another component's keys) is caught
+/// early. Deletions are always allowed, so that migrations can clean up retired keys.
+///
+/// Release builds don't check the guard, to keep it out of the commit path.
+#[derive(Clone, Debug, Default)]
+pub struct KeyGuard {
+    verifiable: Vec<String>,
+    nonverifiable: Vec<Vec<u8>>,
+}
+
+impl KeyGuard {
+    /// Creates a guard allowing writes to keys starting with any of the given prefixes.
+    pub fn new(
+        verifiable: impl IntoIterator<Item = String>,
+        nonverifiable: impl IntoIterator<Item = Vec<u8>>,
+    ) -> Self {
+        Self {
+            verifiable: verifiable.into_iter().collect(),
+            nonverifiable: nonverifiable.into_iter().collect(),
+        }
+    }
+
+    /// Checks that every write in `cache` is to a key with an allowed prefix.
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    pub(crate) fn check(&self, cache: &Cache) -> Result<()> {
+        for (key, value) in cache.unwritten_changes() {
+            if value.is_some()
+                && !self
+                    .verifiable
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix.as_str()))
+            {
+                bail!("write to undeclared verifiable state key {key}");
+            }
+        }
+        for (key, value) in cache.nonverifiable_changes() {
+            if value.is_some()
+                && !self
+                    .nonverifiable
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix))
+            {
+                bail!(
+                    "write to undeclared nonverifiable state key {:?}",
+                    EscapedByteSlice(key)
+                );
+            }
+        }
+        Ok(())
+    }
+}
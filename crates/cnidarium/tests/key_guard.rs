@@ -0,0 +1,41 @@
+use anyhow::Result;
+use cnidarium::{KeyGuard, StateDelta, StateWrite, Storage};
+use tempfile;
+use tokio;
+
+#[tokio::test]
+#[cfg(debug_assertions)]
+/// Checks that once a key guard is installed, commits writing to undeclared keys are rejected,
+/// while writes to declared prefixes and deletions of undeclared keys go through.
+pub async fn test_key_guard_rejects_undeclared_writes() -> Result<()> {
+    let _ = tracing_subscriber::fmt::try_init();
+    let tmpdir = tempfile::tempdir()?;
+    let db_path = tmpdir.into_path();
+    let storage = Storage::load(db_path, vec![]).await?;
+
+    // Before a guard is installed, anything goes.
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    delta.put_raw("legacy/key".to_string(), b"value".to_vec());
+    storage.commit(delta).await?;
+
+    storage.set_key_guard(KeyGuard::new(
+        vec!["component/".to_string()],
+        vec![b"component/".to_vec()],
+    ));
+
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    delta.put_raw("component/key".to_string(), b"value".to_vec());
+    delta.nonverifiable_put_raw(b"component/key".to_vec(), b"value".to_vec());
+    delta.delete("legacy/key".to_string());
+    storage.commit(delta).await?;
+
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    delta.put_raw("other/key".to_string(), b"value".to_vec());
+    assert!(storage.commit(delta).await.is_err());
+
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    delta.nonverifiable_put_raw(b"other/key".to_vec(), b"value".to_vec());
+    assert!(storage.commit(delta).await.is_err());
+
+    Ok(())
+}
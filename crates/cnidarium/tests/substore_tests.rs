@@ -129,6 +129,82 @@ async fn test_substore_proofs() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+/// Test that we can produce membership and non-membership proofs against a
+/// past version of the tree, and that they verify against that version's root.
+async fn test_substore_proofs_at_version() -> anyhow::Result<()> {
+    let _ = tracing_subscriber::fmt::try_init();
+    let tmpdir = tempfile::tempdir()?;
+    let db_path = tmpdir.into_path();
+    let substore_prefixes = vec!["ibc", "prefix_b"]
+        .into_iter()
+        .map(|s| s.to_string())
+        .collect();
+    let storage = Storage::load(db_path, substore_prefixes).await?;
+
+    pub static PENUMBRA_PROOF_SPECS: Lazy<Vec<ics23::ProofSpec>> =
+        Lazy::new(|| vec![cnidarium::ics23_spec(), cnidarium::ics23_spec()]);
+
+    // Version 0: write `ibc/key_1`.
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    let value_1 = "value_1".as_bytes().to_vec();
+    delta.put_raw("ibc/key_1".to_string(), value_1.clone());
+    let root_0 = storage.commit(delta).await?;
+    let version_0 = storage.latest_version();
+
+    // Version 1: write `ibc/key_2`.
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    delta.put_raw("ibc/key_2".to_string(), "value_2".as_bytes().to_vec());
+    storage.commit(delta).await?;
+
+    // At version 0, `ibc/key_2` did not exist yet.
+    let (value, nex_proof) = storage
+        .get_with_proof_at("ibc/key_2".into(), version_0)
+        .await?;
+    assert_eq!(value, None, "key should not exist at version 0");
+    nex_proof
+        .verify_non_membership(
+            &PENUMBRA_PROOF_SPECS,
+            MerkleRoot {
+                hash: root_0.0.to_vec(),
+            },
+            MerklePath {
+                key_path: vec!["ibc".to_string(), "key_2".to_string()],
+            },
+        )
+        .expect("non-existence proof should verify against the old root");
+
+    // ...but `ibc/key_1` did.
+    let snapshot_0 = storage.snapshot(version_0).expect("version 0 is cached");
+    let (value, proof) = snapshot_0
+        .get_with_existence_proof("ibc/key_1".into())
+        .await?;
+    assert_eq!(value, value_1);
+    proof.verify_membership(
+        &PENUMBRA_PROOF_SPECS,
+        MerkleRoot {
+            hash: root_0.0.to_vec(),
+        },
+        MerklePath {
+            key_path: vec!["ibc".to_string(), "key_1".to_string()],
+        },
+        value,
+        0,
+    )?;
+
+    // Asking for the wrong kind of proof is an error.
+    assert!(snapshot_0
+        .get_with_existence_proof("ibc/key_2".into())
+        .await
+        .is_err());
+    assert!(snapshot_0
+        .get_non_existence_proof("ibc/key_1".into())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 /// Test that we can create a storage with multiple substores, that we can write to them, and that
 /// we can read from them.
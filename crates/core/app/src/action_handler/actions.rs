@@ -4,13 +4,12 @@ use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
 use penumbra_ibc::component::StateReadExt as _;
-use penumbra_shielded_pool::component::Ics20Transfer;
 use penumbra_transaction::Action;
 use penumbra_txhash::TransactionContext;
 
 mod submit;
 
-use crate::PenumbraHost;
+use crate::{PenumbraAppHandler, PenumbraHost};
 
 use super::AppActionHandler;
 use cnidarium_component::ActionHandler as _;
@@ -42,7 +41,7 @@ impl AppActionHandler for Action {
             Action::IbcRelay(action) => {
                 action
                     .clone()
-                    .with_handler::<Ics20Transfer, PenumbraHost>()
+                    .with_handler::<PenumbraAppHandler, PenumbraHost>()
                     .check_stateless(())
                     .await
             }
@@ -80,7 +79,7 @@ impl AppActionHandler for Action {
 
                 action
                     .clone()
-                    .with_handler::<Ics20Transfer, PenumbraHost>()
+                    .with_handler::<PenumbraAppHandler, PenumbraHost>()
                     .check_stateful(state)
                     .await
             }
@@ -123,7 +122,7 @@ impl AppActionHandler for Action {
             Action::IbcRelay(action) => {
                 action
                     .clone()
-                    .with_handler::<Ics20Transfer, PenumbraHost>()
+                    .with_handler::<PenumbraAppHandler, PenumbraHost>()
                     .execute(state)
                     .await
             }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use cnidarium::{ArcStateDeltaExt, Snapshot, StateDelta, StateRead, StateWrite, Storage};
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochFuture, EpochSchedule};
 use ibc_types::core::connection::ChainId;
 use jmt::RootHash;
 use penumbra_community_pool::component::{CommunityPool, StateWriteExt as _};
@@ -47,6 +47,33 @@ pub mod state_key;
 /// The inter-block state being written to by the application.
 type InterBlockState = Arc<StateDelta<Snapshot>>;
 
+/// Returns the order in which components' epoch transitions are run.
+///
+/// Components are registered in the order they have historically been run in;
+/// the ordering constraints each component declares via
+/// [`EpochHandler::RUN_AFTER`](cnidarium_component::EpochHandler::RUN_AFTER)
+/// are then enforced on top of that.
+fn epoch_schedule<S: StateWrite + 'static>() -> Result<EpochSchedule<S>> {
+    EpochSchedule::new()
+        .register::<Distributions>()
+        // The IBC component is not a `Component` (its `begin_block` is
+        // generic over the host interface), and has no epoch dependencies.
+        .register_fn("ibc", &[], ibc_end_epoch::<S>)
+        .register::<Dex>()
+        .register::<CommunityPool>()
+        .register::<Governance>()
+        .register::<ShieldedPool>()
+        .register::<Staking>()
+        .register::<Fee>()
+        .register::<Funding>()
+        .build()
+        .context("epoch handler dependencies are not satisfiable")
+}
+
+fn ibc_end_epoch<S: StateWrite + 'static>(state: &mut Arc<S>) -> EpochFuture<'_> {
+    Box::pin(Ibc::end_epoch(state))
+}
+
 /// The Penumbra application, written as a bundle of [`Component`]s.
 ///
 /// The [`App`] is not a [`Component`], but
@@ -54,6 +81,7 @@ type InterBlockState = Arc<StateDelta<Snapshot>>;
 /// commits the changes to the persistent storage and resets its subcomponents.
 pub struct App {
     state: InterBlockState,
+    epoch_schedule: EpochSchedule<StateDelta<InterBlockState>>,
 }
 
 impl App {
@@ -73,7 +101,10 @@ impl App {
             anyhow::bail!("chain is halted, refusing to restart");
         }
 
-        Ok(Self { state })
+        Ok(Self {
+            state,
+            epoch_schedule: epoch_schedule()?,
+        })
     }
 
     // StateDelta::apply only works when the StateDelta wraps an underlying
@@ -485,33 +516,10 @@ impl App {
 
             let mut arc_state_tx = Arc::new(state_tx);
 
-            Distributions::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on Distributions component");
-            Ibc::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on IBC component");
-            Dex::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on dex component");
-            CommunityPool::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on Community Pool component");
-            Governance::end_epoch(&mut arc_state_tx)
+            self.epoch_schedule
+                .end_epoch(&mut arc_state_tx)
                 .await
-                .expect("able to call end_epoch on Governance component");
-            ShieldedPool::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on shielded pool component");
-            Staking::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on Staking component");
-            Fee::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on Fee component");
-            Funding::end_epoch(&mut arc_state_tx)
-                .await
-                .expect("able to call end_epoch on Funding component");
+                .expect("able to end epoch for all components");
 
             let mut state_tx = Arc::try_unwrap(arc_state_tx)
                 .expect("components did not retain copies of shared state");
@@ -771,3 +779,30 @@ pub trait StateWriteExt: StateWrite {
 }
 
 impl<T: StateWrite + ?Sized> StateWriteExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The epoch handlers must keep running in the order they always have:
+    /// reordering them changes the resulting state, forking the chain.
+    #[test]
+    fn epoch_schedule_preserves_historical_order() -> Result<()> {
+        let schedule = epoch_schedule::<StateDelta<Snapshot>>()?;
+        assert_eq!(
+            schedule.order().collect::<Vec<_>>(),
+            [
+                "distributions",
+                "ibc",
+                "dex",
+                "community_pool",
+                "governance",
+                "shielded_pool",
+                "staking",
+                "fee",
+                "funding",
+            ]
+        );
+        Ok(())
+    }
+}
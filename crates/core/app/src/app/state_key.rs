@@ -26,3 +26,57 @@ pub mod counters {
         "application/counters/halt_count"
     }
 }
+
+/// Declares the state keys owned by the application itself, and those written by the
+/// application on behalf of components that can't depend on the types involved.
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    use penumbra_proto::core::app::v1::TransactionsByHeightResponse;
+
+    schema
+        .component("app")
+        .verifiable::<String>(data::chain_id())?
+        .nonverifiable::<TransactionsByHeightResponse>(
+            "cometbft-data/transactions_by_height/{block_height}",
+        )?;
+    // Community Pool spend transactions are stored under the governance component's keys, but
+    // governance can't depend on the transaction crate.
+    schema
+        .component("governance")
+        .verifiable::<penumbra_transaction::Transaction>(
+            "governance/proposal/{proposal_id}/community_pool_transaction",
+        )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cnidarium_component::Store;
+
+    #[test]
+    fn state_key_schema_has_no_collisions() -> anyhow::Result<()> {
+        let schema = crate::state_key_schema()?;
+
+        let lookup = |store, key: &str| {
+            schema
+                .lookup(store, key.as_bytes())
+                .map(|d| (d.component, d.template))
+        };
+        assert_eq!(
+            lookup(
+                Store::Verifiable,
+                &penumbra_governance::state_key::proposal_state(7)
+            ),
+            Some(("governance", "governance/proposal/{proposal_id}/state"))
+        );
+        assert_eq!(
+            lookup(
+                Store::Nonverifiable,
+                &cometbft_data::transactions_by_height(7)
+            ),
+            Some(("app", "cometbft-data/transactions_by_height/{block_height}"))
+        );
+        assert_eq!(lookup(Store::Verifiable, "auction/params"), None);
+        Ok(())
+    }
+}
@@ -11,6 +11,10 @@ use penumbra_shielded_pool::genesis::Content as ShieldedPoolContent;
 use penumbra_stake::genesis::Content as StakeContent;
 use serde::{Deserialize, Serialize};
 
+mod builder;
+
+pub use builder::GenesisBuilder;
+
 /// The application state at genesis.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(try_from = "pb::GenesisAppState", into = "pb::GenesisAppState")]
@@ -0,0 +1,193 @@
+use std::collections::BTreeSet;
+
+use anyhow::{ensure, Context};
+use penumbra_community_pool::genesis::Content as CommunityPoolContent;
+use penumbra_dex::genesis::Content as DexContent;
+use penumbra_distributions::genesis::Content as DistributionsContent;
+use penumbra_fee::genesis::Content as FeeContent;
+use penumbra_funding::genesis::Content as FundingContent;
+use penumbra_governance::genesis::Content as GovernanceContent;
+use penumbra_ibc::genesis::{Content as IBCContent, GenesisClient};
+use penumbra_sct::genesis::Content as SctContent;
+use penumbra_shielded_pool::genesis::{Allocation, Content as ShieldedPoolContent};
+use penumbra_stake::{genesis::Content as StakeContent, validator::Validator};
+
+use super::Content;
+use crate::params::AppParameters;
+
+/// A builder for the genesis [`Content`] of a new chain.
+///
+/// This assembles the chain parameters, initial allocations, genesis
+/// validators, and IBC clients that make up a genesis, and checks them for
+/// consistency, so that custom networks can be described in code rather than by
+/// editing genesis JSON.
+///
+/// ```
+/// # use penumbra_app::genesis::GenesisBuilder;
+/// let content = GenesisBuilder::new("penumbra-devnet")
+///     .update_params(|params| {
+///         params.sct_params.epoch_duration = 100;
+///         params.stake_params.unbonding_delay = 201;
+///     })
+///     .build()
+///     .expect("genesis is valid");
+/// assert_eq!(content.sct_content.sct_params.epoch_duration, 100);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenesisBuilder {
+    params: AppParameters,
+    allocations: Vec<Allocation>,
+    validators: Vec<Validator>,
+    ibc_clients: Vec<GenesisClient>,
+}
+
+impl GenesisBuilder {
+    /// Starts building a genesis for the given chain ID, with the default parameters.
+    pub fn new(chain_id: impl Into<String>) -> Self {
+        Self {
+            params: AppParameters {
+                chain_id: chain_id.into(),
+                ..Default::default()
+            },
+            allocations: Vec::new(),
+            validators: Vec::new(),
+            ibc_clients: Vec::new(),
+        }
+    }
+
+    /// Replaces the chain parameters.
+    ///
+    /// The chain ID given to [`GenesisBuilder::new`] is kept.
+    pub fn with_app_params(mut self, params: AppParameters) -> Self {
+        self.params = AppParameters {
+            chain_id: self.params.chain_id,
+            ..params
+        };
+        self
+    }
+
+    /// Modifies the chain parameters in place.
+    pub fn update_params(mut self, update: impl FnOnce(&mut AppParameters)) -> Self {
+        update(&mut self.params);
+        self
+    }
+
+    /// Adds an initial allocation of funds.
+    pub fn with_allocation(mut self, allocation: Allocation) -> Self {
+        self.allocations.push(allocation);
+        self
+    }
+
+    /// Adds several initial allocations of funds.
+    pub fn with_allocations(mut self, allocations: impl IntoIterator<Item = Allocation>) -> Self {
+        self.allocations.extend(allocations);
+        self
+    }
+
+    /// Adds a genesis validator.
+    ///
+    /// A genesis validator only enters the active set if it's also given an
+    /// allocation of its delegation tokens.
+    pub fn with_validator(mut self, validator: Validator) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Adds several genesis validators.
+    pub fn with_validators(mut self, validators: impl IntoIterator<Item = Validator>) -> Self {
+        self.validators.extend(validators);
+        self
+    }
+
+    /// Adds a light client of a counterparty chain.
+    ///
+    /// Clients are assigned IDs in the order they're added, starting from
+    /// `07-tendermint-0`.
+    pub fn with_ibc_client(mut self, client: GenesisClient) -> Self {
+        self.ibc_clients.push(client);
+        self
+    }
+
+    /// The chain parameters the genesis will be built with.
+    pub fn app_params(&self) -> &AppParameters {
+        &self.params
+    }
+
+    /// Checks the genesis for consistency, and builds it.
+    pub fn build(self) -> anyhow::Result<Content> {
+        let Self {
+            params,
+            allocations,
+            validators,
+            ibc_clients,
+        } = self;
+
+        params
+            .check_valid()
+            .context("invalid genesis chain parameters")?;
+
+        for allocation in &allocations {
+            ensure!(
+                !allocation.raw_denom.is_empty(),
+                "genesis allocation to {} has an empty denom",
+                allocation.address
+            );
+        }
+
+        let mut identity_keys = BTreeSet::new();
+        let mut consensus_keys = BTreeSet::new();
+        for validator in &validators {
+            ensure!(
+                identity_keys.insert(validator.identity_key),
+                "duplicate genesis validator identity key {}",
+                validator.identity_key
+            );
+            ensure!(
+                consensus_keys.insert(validator.consensus_key.to_bytes()),
+                "genesis validator {} reuses another validator's consensus key",
+                validator.identity_key
+            );
+        }
+        ensure!(
+            ibc_clients.is_empty() || params.ibc_params.ibc_enabled,
+            "genesis IBC clients require IBC to be enabled"
+        );
+
+        Ok(Content {
+            chain_id: params.chain_id,
+            community_pool_content: CommunityPoolContent {
+                community_pool_params: params.community_pool_params,
+            },
+            distributions_content: DistributionsContent {
+                distributions_params: params.distributions_params,
+            },
+            fee_content: FeeContent {
+                fee_params: params.fee_params,
+            },
+            funding_content: FundingContent {
+                funding_params: params.funding_params,
+            },
+            governance_content: GovernanceContent {
+                governance_params: params.governance_params,
+            },
+            ibc_content: IBCContent {
+                ibc_params: params.ibc_params,
+                clients: ibc_clients,
+            },
+            sct_content: SctContent {
+                sct_params: params.sct_params,
+            },
+            shielded_pool_content: ShieldedPoolContent {
+                shielded_pool_params: params.shielded_pool_params,
+                allocations,
+            },
+            stake_content: StakeContent {
+                stake_params: params.stake_params,
+                validators: validators.into_iter().map(Into::into).collect(),
+            },
+            dex_content: DexContent {
+                dex_params: params.dex_params,
+            },
+        })
+    }
+}
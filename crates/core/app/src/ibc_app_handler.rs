@@ -0,0 +1,202 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use cnidarium::{StateRead, StateWrite};
+use ibc_types::core::channel::{
+    msgs::{
+        MsgAcknowledgement, MsgChannelCloseConfirm, MsgChannelCloseInit, MsgChannelOpenAck,
+        MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket, MsgTimeout,
+    },
+    PortId, Version,
+};
+use penumbra_ibc::{
+    component::app_handler::{AppHandler, AppHandlerCheck, AppHandlerExecute},
+    ics27,
+};
+use penumbra_shielded_pool::component::Ics20Transfer;
+
+use crate::ica_host::IcaHost;
+
+/// The implementation of [`penumbra_ibc::component::app_handler::AppHandler`] for Penumbra.
+/// It passes each channel and packet event to the IBC application bound to the event's port:
+/// ICS-27 interchain accounts on the `icahost` port, and ICS-20 transfers otherwise.
+#[derive(Clone)]
+pub struct PenumbraAppHandler {}
+
+fn is_ica_host(port_id: &PortId) -> bool {
+    port_id.as_str() == ics27::HOST_PORT_ID
+}
+
+#[async_trait]
+impl AppHandlerCheck for PenumbraAppHandler {
+    async fn chan_open_init_check<S: StateRead>(state: S, msg: &MsgChannelOpenInit) -> Result<()> {
+        if is_ica_host(&msg.port_id_on_a) {
+            IcaHost::chan_open_init_check(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_init_check(state, msg).await
+        }
+    }
+
+    async fn chan_open_try_check<S: StateRead>(state: S, msg: &MsgChannelOpenTry) -> Result<()> {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_open_try_check(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_try_check(state, msg).await
+        }
+    }
+
+    async fn chan_open_try_version<S: StateRead>(
+        state: S,
+        msg: &MsgChannelOpenTry,
+    ) -> Result<Version> {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_open_try_version(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_try_version(state, msg).await
+        }
+    }
+
+    async fn chan_open_ack_check<S: StateRead>(state: S, msg: &MsgChannelOpenAck) -> Result<()> {
+        if is_ica_host(&msg.port_id_on_a) {
+            IcaHost::chan_open_ack_check(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_ack_check(state, msg).await
+        }
+    }
+
+    async fn chan_open_confirm_check<S: StateRead>(
+        state: S,
+        msg: &MsgChannelOpenConfirm,
+    ) -> Result<()> {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_open_confirm_check(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_confirm_check(state, msg).await
+        }
+    }
+
+    async fn chan_close_confirm_check<S: StateRead>(
+        state: S,
+        msg: &MsgChannelCloseConfirm,
+    ) -> Result<()> {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_close_confirm_check(state, msg).await
+        } else {
+            Ics20Transfer::chan_close_confirm_check(state, msg).await
+        }
+    }
+
+    async fn chan_close_init_check<S: StateRead>(
+        state: S,
+        msg: &MsgChannelCloseInit,
+    ) -> Result<()> {
+        if is_ica_host(&msg.port_id_on_a) {
+            IcaHost::chan_close_init_check(state, msg).await
+        } else {
+            Ics20Transfer::chan_close_init_check(state, msg).await
+        }
+    }
+
+    async fn recv_packet_check<S: StateRead>(state: S, msg: &MsgRecvPacket) -> Result<()> {
+        if is_ica_host(&msg.packet.port_on_b) {
+            IcaHost::recv_packet_check(state, msg).await
+        } else {
+            Ics20Transfer::recv_packet_check(state, msg).await
+        }
+    }
+
+    async fn timeout_packet_check<S: StateRead>(state: S, msg: &MsgTimeout) -> Result<()> {
+        if is_ica_host(&msg.packet.port_on_a) {
+            IcaHost::timeout_packet_check(state, msg).await
+        } else {
+            Ics20Transfer::timeout_packet_check(state, msg).await
+        }
+    }
+
+    async fn acknowledge_packet_check<S: StateRead>(
+        state: S,
+        msg: &MsgAcknowledgement,
+    ) -> Result<()> {
+        if is_ica_host(&msg.packet.port_on_a) {
+            IcaHost::acknowledge_packet_check(state, msg).await
+        } else {
+            Ics20Transfer::acknowledge_packet_check(state, msg).await
+        }
+    }
+}
+
+#[async_trait]
+impl AppHandlerExecute for PenumbraAppHandler {
+    async fn chan_open_init_execute<S: StateWrite>(state: S, msg: &MsgChannelOpenInit) {
+        if is_ica_host(&msg.port_id_on_a) {
+            IcaHost::chan_open_init_execute(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_init_execute(state, msg).await
+        }
+    }
+
+    async fn chan_open_try_execute<S: StateWrite>(state: S, msg: &MsgChannelOpenTry) {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_open_try_execute(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_try_execute(state, msg).await
+        }
+    }
+
+    async fn chan_open_ack_execute<S: StateWrite>(state: S, msg: &MsgChannelOpenAck) {
+        if is_ica_host(&msg.port_id_on_a) {
+            IcaHost::chan_open_ack_execute(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_ack_execute(state, msg).await
+        }
+    }
+
+    async fn chan_open_confirm_execute<S: StateWrite>(state: S, msg: &MsgChannelOpenConfirm) {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_open_confirm_execute(state, msg).await
+        } else {
+            Ics20Transfer::chan_open_confirm_execute(state, msg).await
+        }
+    }
+
+    async fn chan_close_confirm_execute<S: StateWrite>(state: S, msg: &MsgChannelCloseConfirm) {
+        if is_ica_host(&msg.port_id_on_b) {
+            IcaHost::chan_close_confirm_execute(state, msg).await
+        } else {
+            Ics20Transfer::chan_close_confirm_execute(state, msg).await
+        }
+    }
+
+    async fn chan_close_init_execute<S: StateWrite>(state: S, msg: &MsgChannelCloseInit) {
+        if is_ica_host(&msg.port_id_on_a) {
+            IcaHost::chan_close_init_execute(state, msg).await
+        } else {
+            Ics20Transfer::chan_close_init_execute(state, msg).await
+        }
+    }
+
+    async fn recv_packet_execute<S: StateWrite>(state: S, msg: &MsgRecvPacket) -> Result<()> {
+        if is_ica_host(&msg.packet.port_on_b) {
+            IcaHost::recv_packet_execute(state, msg).await
+        } else {
+            Ics20Transfer::recv_packet_execute(state, msg).await
+        }
+    }
+
+    async fn timeout_packet_execute<S: StateWrite>(state: S, msg: &MsgTimeout) -> Result<()> {
+        if is_ica_host(&msg.packet.port_on_a) {
+            IcaHost::timeout_packet_execute(state, msg).await
+        } else {
+            Ics20Transfer::timeout_packet_execute(state, msg).await
+        }
+    }
+
+    async fn acknowledge_packet_execute<S: StateWrite>(state: S, msg: &MsgAcknowledgement) {
+        if is_ica_host(&msg.packet.port_on_a) {
+            IcaHost::acknowledge_packet_execute(state, msg).await
+        } else {
+            Ics20Transfer::acknowledge_packet_execute(state, msg).await
+        }
+    }
+}
+
+impl AppHandler for PenumbraAppHandler {}
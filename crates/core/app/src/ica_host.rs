@@ -0,0 +1,355 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use cnidarium::{StateDelta, StateRead, StateWrite};
+use cnidarium_component::ActionHandler as _;
+use ibc_types::core::channel::{
+    channel::{Order as ChannelOrder, State as ChannelState},
+    msgs::{
+        MsgAcknowledgement, MsgChannelCloseConfirm, MsgChannelCloseInit, MsgChannelOpenAck,
+        MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket, MsgTimeout,
+    },
+    Version,
+};
+use penumbra_asset::Balance;
+use penumbra_ibc::{
+    component::{
+        app_handler::{AppHandler, AppHandlerCheck, AppHandlerExecute},
+        packet::WriteAcknowledgement as _,
+        ChannelStateReadExt as _, ConnectionStateReadExt as _, InterchainAccountStateReadExt as _,
+        InterchainAccountStateWriteExt as _, StateReadExt as _,
+    },
+    ics27::{self, Acknowledgement, Metadata, PacketData},
+};
+use penumbra_proto::{
+    core::component::{ibc::v1 as pb_ibc, stake::v1 as pb_stake},
+    DomainType, Name as _,
+};
+use penumbra_shielded_pool::Ics20Withdrawal;
+use penumbra_stake::{Delegate, Undelegate};
+
+/// The ICS-27 interchain accounts host, bound to the `icahost` port.
+///
+/// Controller chains open an ordered channel to register an interchain account, then send
+/// packets carrying transactions for the host to execute on the account's behalf.
+#[derive(Clone)]
+pub struct IcaHost {}
+
+/// A message an interchain account may execute on Penumbra.
+///
+/// The account authorizes the message itself, by sending it over its channel, and pays for it
+/// from its transparent balance rather than from notes.
+#[derive(Clone, Debug)]
+enum HostMessage {
+    Delegate(Delegate),
+    Undelegate(Undelegate),
+    Ics20Withdrawal(Ics20Withdrawal),
+}
+
+impl TryFrom<&pbjson_types::Any> for HostMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(message: &pbjson_types::Any) -> Result<Self> {
+        if message.type_url == pb_stake::Delegate::type_url() {
+            Ok(Self::Delegate(Delegate::decode(message.value.clone())?))
+        } else if message.type_url == pb_stake::Undelegate::type_url() {
+            Ok(Self::Undelegate(Undelegate::decode(message.value.clone())?))
+        } else if message.type_url == pb_ibc::Ics20Withdrawal::type_url() {
+            Ok(Self::Ics20Withdrawal(Ics20Withdrawal::decode(
+                message.value.clone(),
+            )?))
+        } else {
+            anyhow::bail!(
+                "message type {} is not allowed for interchain accounts",
+                message.type_url
+            );
+        }
+    }
+}
+
+impl HostMessage {
+    /// The value the message consumes from, and provides to, the account executing it.
+    fn balance(&self) -> Balance {
+        match self {
+            HostMessage::Delegate(delegate) => delegate.balance(),
+            HostMessage::Undelegate(undelegate) => undelegate.balance(),
+            HostMessage::Ics20Withdrawal(withdrawal) => withdrawal.balance(),
+        }
+    }
+
+    async fn execute<S: StateWrite>(&self, mut state: S, account: &str) -> Result<()> {
+        // The account's balance takes the place of the notes a transaction would spend and
+        // create, so debit what the message consumes before executing it.
+        let balance = self.balance();
+        for value in balance.required() {
+            state.debit_ica_balance(account, value).await?;
+        }
+
+        match self {
+            HostMessage::Delegate(delegate) => {
+                delegate.check_stateless(()).await?;
+                delegate.check_and_execute(&mut state).await?;
+            }
+            HostMessage::Undelegate(undelegate) => {
+                undelegate.check_stateless(()).await?;
+                undelegate.check_and_execute(&mut state).await?;
+            }
+            HostMessage::Ics20Withdrawal(withdrawal) => {
+                if !state
+                    .get_ibc_params()
+                    .await?
+                    .outbound_ics20_transfers_enabled
+                {
+                    anyhow::bail!("outbound ICS20 withdrawals are not enabled");
+                }
+                withdrawal.check_stateless(()).await?;
+                withdrawal.check_and_execute(&mut state).await?;
+            }
+        }
+
+        for value in balance.provided() {
+            state.credit_ica_balance(account, value).await?;
+        }
+
+        Ok(())
+    }
+}
+
+// see: https://github.com/cosmos/ibc/tree/main/spec/app/ics-027-interchain-accounts
+#[async_trait]
+impl AppHandlerCheck for IcaHost {
+    async fn chan_open_init_check<S: StateRead>(
+        _state: S,
+        _msg: &MsgChannelOpenInit,
+    ) -> Result<()> {
+        anyhow::bail!("interchain account channels must be opened by the controller chain");
+    }
+
+    async fn chan_open_try_check<S: StateRead>(state: S, msg: &MsgChannelOpenTry) -> Result<()> {
+        if !state.get_ibc_params().await?.ics27_host_enabled {
+            anyhow::bail!("hosting interchain accounts is not enabled");
+        }
+        if msg.ordering != ChannelOrder::Ordered {
+            anyhow::bail!("channel order must be ordered for interchain accounts");
+        }
+        if !msg
+            .port_id_on_a
+            .as_str()
+            .starts_with(ics27::CONTROLLER_PORT_PREFIX)
+        {
+            anyhow::bail!("counterparty port must be an interchain account controller port");
+        }
+
+        let connection_id = &msg.connection_hops_on_b[0];
+        let connection = state
+            .get_connection(connection_id)
+            .await?
+            .context("connection not found for channel")?;
+        let controller_connection_id = connection
+            .counterparty
+            .connection_id
+            .as_ref()
+            .context("no counterparty connection id for channel")?;
+
+        let metadata = Metadata::try_from(&msg.version_supported_on_a)?;
+        metadata.validate_for_host(connection_id, controller_connection_id)?;
+        if !metadata.address.is_empty()
+            && metadata.address != ics27::account_address(connection_id, &msg.port_id_on_a)
+        {
+            anyhow::bail!("interchain account address does not match the registered account");
+        }
+
+        // A controller may only reopen its account's channel once the previous one has closed.
+        if let Some(channel_id) = state
+            .ica_active_channel(connection_id, &msg.port_id_on_a)
+            .await?
+        {
+            let channel = state
+                .get_channel(&channel_id, &msg.port_id_on_b)
+                .await?
+                .context("active interchain account channel not found")?;
+            if channel.state_matches(&ChannelState::Open) {
+                anyhow::bail!("interchain account already has open channel {channel_id}");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn chan_open_try_version<S: StateRead>(
+        _state: S,
+        msg: &MsgChannelOpenTry,
+    ) -> Result<Version> {
+        // Return the account address to the controller with the negotiated version.
+        let metadata = Metadata {
+            address: ics27::account_address(&msg.connection_hops_on_b[0], &msg.port_id_on_a),
+            ..Metadata::try_from(&msg.version_supported_on_a)?
+        };
+
+        Ok(Version::from(&metadata))
+    }
+
+    async fn chan_open_ack_check<S: StateRead>(_state: S, _msg: &MsgChannelOpenAck) -> Result<()> {
+        anyhow::bail!("interchain account channels must be opened by the controller chain");
+    }
+
+    async fn chan_open_confirm_check<S: StateRead>(
+        _state: S,
+        _msg: &MsgChannelOpenConfirm,
+    ) -> Result<()> {
+        // accept channel confirmations, the version was validated in chan_open_try
+        Ok(())
+    }
+
+    async fn chan_close_confirm_check<S: StateRead>(
+        _state: S,
+        _msg: &MsgChannelCloseConfirm,
+    ) -> Result<()> {
+        // the controller may close the channel, and reopen it later
+        Ok(())
+    }
+
+    async fn chan_close_init_check<S: StateRead>(
+        _state: S,
+        _msg: &MsgChannelCloseInit,
+    ) -> Result<()> {
+        anyhow::bail!("interchain account channels cannot be closed by the host chain");
+    }
+
+    async fn recv_packet_check<S: StateRead>(_state: S, _msg: &MsgRecvPacket) -> Result<()> {
+        // all checks on recv_packet done in execute
+        Ok(())
+    }
+
+    async fn timeout_packet_check<S: StateRead>(_state: S, _msg: &MsgTimeout) -> Result<()> {
+        anyhow::bail!("interchain account host does not send packets");
+    }
+
+    async fn acknowledge_packet_check<S: StateRead>(
+        _state: S,
+        _msg: &MsgAcknowledgement,
+    ) -> Result<()> {
+        anyhow::bail!("interchain account host does not send packets");
+    }
+}
+
+async fn recv_packet_inner<S: StateWrite>(mut state: S, msg: &MsgRecvPacket) -> Result<()> {
+    if !state.get_ibc_params().await?.ics27_host_enabled {
+        anyhow::bail!("hosting interchain accounts is not enabled");
+    }
+
+    let channel = state
+        .get_channel(&msg.packet.chan_on_b, &msg.packet.port_on_b)
+        .await?
+        .context("channel not found")?;
+
+    // Only the controller that registered the account may execute transactions with it, over the
+    // account's active channel.
+    let active_channel = state
+        .ica_active_channel(&channel.connection_hops[0], &msg.packet.port_on_a)
+        .await?;
+    if active_channel.as_ref() != Some(&msg.packet.chan_on_b) {
+        anyhow::bail!("packet was not received on an active interchain account channel");
+    }
+    let account = state
+        .ica_account(&channel.connection_hops[0], &msg.packet.port_on_a)
+        .await?
+        .context("no interchain account registered for controller")?;
+    tracing::debug!(%account, "executing interchain account packet");
+
+    let messages = PacketData::decode(&msg.packet.data)?
+        .messages()?
+        .iter()
+        .map(HostMessage::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    // Execute the messages in a fork, so that either all of them are applied or none are.
+    let mut fork = StateDelta::new(&mut state);
+    for message in &messages {
+        message.execute(&mut fork, &account).await?;
+    }
+    let (state, events) = fork.apply();
+    for event in events {
+        state.record(event);
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl AppHandlerExecute for IcaHost {
+    async fn chan_open_init_execute<S: StateWrite>(_state: S, _msg: &MsgChannelOpenInit) {}
+
+    async fn chan_open_try_execute<S: StateWrite>(mut state: S, msg: &MsgChannelOpenTry) {
+        let account = state.put_ica_account(&msg.connection_hops_on_b[0], &msg.port_id_on_a);
+        tracing::debug!(%account, "registered interchain account");
+    }
+
+    async fn chan_open_ack_execute<S: StateWrite>(_state: S, _msg: &MsgChannelOpenAck) {}
+
+    async fn chan_open_confirm_execute<S: StateWrite>(mut state: S, msg: &MsgChannelOpenConfirm) {
+        let channel = state
+            .get_channel(&msg.chan_id_on_b, &msg.port_id_on_b)
+            .await
+            .expect("able to retrieve channel in chan_open_confirm_execute")
+            .expect("channel exists after it was confirmed");
+
+        state.put_ica_active_channel(
+            &channel.connection_hops[0],
+            &channel.counterparty().port_id,
+            &msg.chan_id_on_b,
+        );
+    }
+
+    async fn chan_close_confirm_execute<S: StateWrite>(_state: S, _msg: &MsgChannelCloseConfirm) {}
+    async fn chan_close_init_execute<S: StateWrite>(_state: S, _msg: &MsgChannelCloseInit) {}
+
+    async fn recv_packet_execute<S: StateWrite>(mut state: S, msg: &MsgRecvPacket) -> Result<()> {
+        // recv packet should never fail a transaction, but it should record a failure acknowledgement.
+        let ack: Vec<u8> = match recv_packet_inner(&mut state, msg).await {
+            Ok(_) => Acknowledgement::success().into(),
+            Err(e) => {
+                tracing::debug!("couldnt execute interchain account packet: {:#}", e);
+                Acknowledgement::Error(e.to_string()).into()
+            }
+        };
+
+        state
+            .write_acknowledgement(&msg.packet, &ack)
+            .await
+            .context("able to write acknowledgement")?;
+
+        Ok(())
+    }
+
+    async fn timeout_packet_execute<S: StateWrite>(_state: S, _msg: &MsgTimeout) -> Result<()> {
+        Ok(())
+    }
+
+    async fn acknowledge_packet_execute<S: StateWrite>(_state: S, _msg: &MsgAcknowledgement) {}
+}
+
+impl AppHandler for IcaHost {}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_proto::core::component::governance::v1 as pb_governance;
+
+    use super::*;
+
+    #[test]
+    fn only_allowed_messages_are_decoded() {
+        let vote = pbjson_types::Any {
+            type_url: pb_governance::ValidatorVote::type_url(),
+            value: Default::default(),
+        };
+        let err = HostMessage::try_from(&vote).expect_err("validator votes are not allowed");
+        assert!(err.to_string().contains("is not allowed"));
+
+        // Allowed messages are still decoded, so malformed ones are rejected.
+        let delegate = pbjson_types::Any {
+            type_url: pb_stake::Delegate::type_url(),
+            value: vec![0xff].into(),
+        };
+        assert!(HostMessage::try_from(&delegate).is_err());
+    }
+}
@@ -10,12 +10,14 @@ pub mod server;
 
 mod action_handler;
 mod community_pool_ext;
+mod ibc_app_handler;
+mod ica_host;
 mod penumbra_host_chain;
 
 pub use crate::{
     action_handler::AppActionHandler, app::StateWriteExt,
-    community_pool_ext::CommunityPoolStateReadExt, metrics::register_metrics,
-    penumbra_host_chain::PenumbraHost,
+    community_pool_ext::CommunityPoolStateReadExt, ibc_app_handler::PenumbraAppHandler,
+    metrics::register_metrics, penumbra_host_chain::PenumbraHost,
 };
 
 use once_cell::sync::Lazy;
@@ -34,5 +36,26 @@ pub static SUBSTORE_PREFIXES: Lazy<Vec<String>> = Lazy::new(|| {
 /// The substore prefix used for storing historical CometBFT block data.
 pub static COMETBFT_SUBSTORE_PREFIX: &'static str = "cometbft-data";
 
+/// Builds the registry of the state keys declared by the application and all of its components.
+///
+/// Declaring keys that collide with those of another component is an error, so this doubles as a
+/// check that a newly added component doesn't overlap the existing ones.
+pub fn state_key_schema() -> anyhow::Result<cnidarium_component::StateKeySchema> {
+    let mut schema = cnidarium_component::StateKeySchema::new();
+    app::state_key::declare_schema(&mut schema)?;
+    penumbra_sct::state_key::declare_schema(&mut schema)?;
+    penumbra_shielded_pool::state_key::declare_schema(&mut schema)?;
+    penumbra_distributions::component::state_key::declare_schema(&mut schema)?;
+    penumbra_stake::state_key::declare_schema(&mut schema)?;
+    penumbra_ibc::component::state_key::declare_schema(&mut schema)?;
+    penumbra_dex::state_key::declare_schema(&mut schema)?;
+    penumbra_community_pool::component::state_key::declare_schema(&mut schema)?;
+    penumbra_governance::state_key::declare_schema(&mut schema)?;
+    penumbra_fee::state_key::declare_schema(&mut schema)?;
+    penumbra_funding::component::declare_schema(&mut schema)?;
+    penumbra_compact_block::state_key::declare_schema(&mut schema)?;
+    Ok(schema)
+}
+
 /// Temporary compat wrapper for duplicate trait impls
 pub struct Compat<'a, T>(&'a T);
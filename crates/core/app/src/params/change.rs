@@ -52,6 +52,7 @@ impl AppParameters {
                     ibc_enabled: _,
                     inbound_ics20_transfers_enabled: _,
                     outbound_ics20_transfers_enabled: _,
+                    ics27_host_enabled: _,
                 },
             sct_params: SctParameters { epoch_duration },
             shielded_pool_params:
@@ -147,6 +148,7 @@ impl AppParameters {
                     ibc_enabled,
                     inbound_ics20_transfers_enabled,
                     outbound_ics20_transfers_enabled,
+                    ics27_host_enabled,
                 },
             sct_params: SctParameters { epoch_duration },
             shielded_pool_params:
@@ -221,6 +223,10 @@ impl AppParameters {
                     || *ibc_enabled,
                 "IBC must be enabled if either inbound or outbound ICS20 transfers are enabled",
             ),
+            (
+                !*ics27_host_enabled || *ibc_enabled,
+                "IBC must be enabled if hosting ICS27 interchain accounts is enabled",
+            ),
             (
                 *proposal_voting_blocks >= 1,
                 "proposal voting blocks must be at least 1",
@@ -1,5 +1,5 @@
 use {
-    self::common::{BuilderExt, TestNodeExt},
+    self::common::{BuilderExt, TempStorageExt, TestNodeExt},
     anyhow::anyhow,
     cnidarium::TempStorage,
     decaf377_rdsa::{SigningKey, SpendAuth, VerificationKey},
@@ -31,7 +31,7 @@ const EPOCH_DURATION: u64 = 8;
 async fn app_can_define_and_delegate_to_a_validator() -> anyhow::Result<()> {
     // Install a test logger, acquire some temporary storage, and start the test node.
     let guard = common::set_tracing_subscriber();
-    let storage = TempStorage::new().await?;
+    let storage = TempStorage::new().await?.with_key_guard()?;
 
     // Configure an AppState with slightly shorter epochs than usual.
     let app_state =
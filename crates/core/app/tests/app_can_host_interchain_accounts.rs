@@ -0,0 +1,400 @@
+use {
+    self::common::{BuilderExt, TempStorageExt},
+    anyhow::Context,
+    base64::prelude::*,
+    cnidarium::{StateDelta, TempStorage},
+    ibc_types::{
+        core::{
+            channel::{
+                channel::{Order, State as ChannelState},
+                msgs::{MsgChannelOpenConfirm, MsgChannelOpenTry, MsgRecvPacket},
+                ChannelEnd, ChannelId, Counterparty, Packet, PortId, TimeoutHeight, Version,
+            },
+            client::{ClientId, Height},
+            commitment::{MerkleProof, MerkleRoot},
+            connection::{self, ChainId, ConnectionEnd, ConnectionId, State as ConnectionState},
+        },
+        lightclients::tendermint::{
+            client_state::{AllowUpdate, ClientState as TendermintClientState},
+            consensus_state::ConsensusState as TendermintConsensusState,
+            TrustThreshold,
+        },
+        path::{ChannelEndPath, CommitmentPath},
+        timestamp::Timestamp,
+    },
+    penumbra_app::{
+        genesis::{self, AppState},
+        server::consensus::Consensus,
+        PenumbraAppHandler, PenumbraHost,
+    },
+    penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID},
+    penumbra_ibc::{
+        component::{
+            ChannelStateReadExt as _, ChannelStateWriteExt as _, ClientStateWriteExt as _,
+            ConnectionStateWriteExt as _, ConsensusStateWriteExt as _,
+            InterchainAccountStateReadExt as _, InterchainAccountStateWriteExt as _,
+        },
+        ics27::{self, CosmosTx, Metadata, PacketData, PacketType},
+        params::IBCParameters,
+        IbcRelay, IBC_COMMITMENT_PREFIX, IBC_PROOF_SPECS, IBC_SUBSTORE_PREFIX,
+    },
+    penumbra_mock_consensus::TestNode,
+    penumbra_num::Amount,
+    penumbra_proto::{core::component::stake::v1 as pb_stake, DomainType, Name as _},
+    penumbra_sct::component::clock::EpochRead as _,
+    penumbra_stake::{
+        component::validator_handler::ValidatorDataRead as _, Delegate, DelegationToken,
+    },
+    std::time::Duration,
+};
+
+mod common;
+
+/// The client the host tracks the controller chain with.
+const CLIENT_ID: &str = "07-tendermint-0";
+/// The port the controller registers its interchain account from.
+const CONTROLLER_PORT_ID: &str = "icacontroller-test";
+
+/// A stand-in for the controller chain, whose IBC state is proven to the host.
+struct Controller {
+    storage: TempStorage,
+}
+
+impl Controller {
+    async fn new() -> anyhow::Result<Self> {
+        let storage = TempStorage::new_with_prefixes(vec![IBC_SUBSTORE_PREFIX.to_string()]).await?;
+        Ok(Self { storage })
+    }
+
+    /// Commits the given writes, returning the new root of the controller's state.
+    async fn commit(
+        &self,
+        f: impl FnOnce(&mut StateDelta<cnidarium::Snapshot>),
+    ) -> anyhow::Result<MerkleRoot> {
+        let mut delta = StateDelta::new(self.storage.latest_snapshot());
+        f(&mut delta);
+        let root = self.storage.commit(delta).await?;
+        Ok(MerkleRoot {
+            hash: root.0.to_vec(),
+        })
+    }
+
+    /// Proves the value at `path` in the controller's latest state.
+    async fn prove(&self, path: String) -> anyhow::Result<MerkleProof> {
+        let key = format!("{IBC_SUBSTORE_PREFIX}/{path}");
+        let (value, proof) = self
+            .storage
+            .latest_snapshot()
+            .get_with_proof(key.into_bytes())
+            .await?;
+        value.context("proven value is present")?;
+        Ok(proof)
+    }
+}
+
+/// Executes a relayed IBC message against the host's latest state, and commits it.
+async fn relay(storage: &TempStorage, msg: IbcRelay) -> anyhow::Result<()> {
+    let action = msg.with_handler::<PenumbraAppHandler, PenumbraHost>();
+    action.check_stateless(()).await?;
+    let mut delta = StateDelta::new(storage.latest_snapshot());
+    action.execute(&mut delta).await?;
+    storage.commit(delta).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn app_can_host_interchain_accounts() -> anyhow::Result<()> {
+    // Install a test logger, acquire some temporary storage, and start the test node.
+    let guard = common::set_tracing_subscriber();
+    let storage = TempStorage::new().await?.with_key_guard()?;
+
+    // Configure an AppState with interchain account hosting enabled.
+    let app_state = AppState::Content(genesis::Content {
+        ibc_content: penumbra_ibc::genesis::Content {
+            ibc_params: IBCParameters {
+                ics27_host_enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // Start the test node, and execute a block so that the host has a height and a timestamp.
+    let mut node = {
+        let consensus = Consensus::new(storage.as_ref().clone());
+        TestNode::builder()
+            .single_validator()
+            .with_penumbra_auto_app_state(app_state)?
+            .init_chain(consensus)
+            .await
+    }?;
+    node.block().execute().await?;
+
+    let client_id: ClientId = CLIENT_ID.parse()?;
+    let host_connection_id = ConnectionId::new(0);
+    let controller_connection_id = ConnectionId::new(7);
+    let host_port_id: PortId = ics27::HOST_PORT_ID.parse()?;
+    let controller_port_id: PortId = CONTROLLER_PORT_ID.parse()?;
+    let controller_channel_id = ChannelId::new(0);
+
+    // The controller opens its end of the channel, proposing the account's metadata.
+    let controller = Controller::new().await?;
+    let proposed_version = Version::from(&Metadata {
+        version: ics27::VERSION.to_string(),
+        controller_connection_id: controller_connection_id.to_string(),
+        host_connection_id: host_connection_id.to_string(),
+        address: String::new(),
+        encoding: ics27::ENCODING_PROTO3.to_string(),
+        tx_type: ics27::TX_TYPE_SDK_MULTI_MSG.to_string(),
+    });
+    let init_root = controller
+        .commit(|state| {
+            state.put_channel(
+                &controller_channel_id,
+                &controller_port_id,
+                ChannelEnd {
+                    state: ChannelState::Init,
+                    ordering: Order::Ordered,
+                    remote: Counterparty::new(host_port_id.clone(), None),
+                    connection_hops: vec![controller_connection_id.clone()],
+                    version: proposed_version.clone(),
+                },
+            )
+        })
+        .await?;
+    let proof_chan_init = controller
+        .prove(ChannelEndPath::new(&controller_port_id, &controller_channel_id).to_string())
+        .await?;
+
+    // The host tracks the controller with a client, over an open connection.
+    {
+        let mut delta = StateDelta::new(storage.latest_snapshot());
+        let block_time = delta.get_block_timestamp().await?;
+        delta.put_client(
+            &client_id,
+            TendermintClientState::new(
+                ChainId::from_string("controller"),
+                TrustThreshold::ONE_THIRD,
+                Duration::from_secs(14 * 24 * 60 * 60),
+                Duration::from_secs(21 * 24 * 60 * 60),
+                Duration::from_secs(10),
+                Height::new(0, 2)?,
+                IBC_PROOF_SPECS.clone(),
+                vec![],
+                AllowUpdate {
+                    after_expiry: true,
+                    after_misbehaviour: true,
+                },
+                None,
+            )?,
+        );
+        delta
+            .put_verified_consensus_state::<PenumbraHost>(
+                Height::new(0, 1)?,
+                client_id.clone(),
+                TendermintConsensusState::new(init_root, block_time, tendermint::Hash::None),
+            )
+            .await?;
+        delta
+            .put_new_connection(
+                &host_connection_id,
+                ConnectionEnd {
+                    state: ConnectionState::Open,
+                    client_id: client_id.clone(),
+                    counterparty: connection::Counterparty {
+                        client_id: client_id.clone(),
+                        connection_id: Some(controller_connection_id.clone()),
+                        prefix: IBC_COMMITMENT_PREFIX.clone(),
+                    },
+                    versions: vec![connection::Version::default()],
+                    delay_period: Duration::ZERO,
+                },
+            )
+            .await?;
+        storage.commit(delta).await?;
+    }
+
+    // The host accepts the channel, registering the interchain account.
+    relay(
+        &storage,
+        IbcRelay::ChannelOpenTry(MsgChannelOpenTry {
+            port_id_on_b: host_port_id.clone(),
+            connection_hops_on_b: vec![host_connection_id.clone()],
+            port_id_on_a: controller_port_id.clone(),
+            chan_id_on_a: controller_channel_id.clone(),
+            version_supported_on_a: proposed_version,
+            proof_chan_end_on_a: proof_chan_init,
+            proof_height_on_a: Height::new(0, 1)?,
+            ordering: Order::Ordered,
+            signer: "relayer".to_string(),
+        }),
+    )
+    .await?;
+
+    let host_channel_id = ChannelId::new(0);
+    let host_channel = storage
+        .latest_snapshot()
+        .get_channel(&host_channel_id, &host_port_id)
+        .await?
+        .context("host channel was opened")?;
+    let account = storage
+        .latest_snapshot()
+        .ica_account(&host_connection_id, &controller_port_id)
+        .await?
+        .context("interchain account was registered")?;
+    assert_eq!(
+        Metadata::try_from(&host_channel.version)?.address,
+        account,
+        "the negotiated version should carry the account address"
+    );
+
+    // Fund the interchain account with some staking tokens.
+    let funds = Amount::from(10_000_000u64);
+    {
+        let mut delta = StateDelta::new(storage.latest_snapshot());
+        delta
+            .credit_ica_balance(
+                &account,
+                Value {
+                    amount: funds,
+                    asset_id: *STAKING_TOKEN_ASSET_ID,
+                },
+            )
+            .await?;
+        storage.commit(delta).await?;
+    }
+
+    // The controller asks the host to delegate some of the account's funds to the validator.
+    let snapshot = storage.latest_snapshot();
+    let [validator] = snapshot
+        .validator_identity_keys()
+        .await?
+        .try_into()
+        .map_err(|keys| anyhow::anyhow!("expected one key, got: {keys:?}"))?;
+    let unbonded_amount = Amount::from(1_000_000u64);
+    let delegate = Delegate {
+        validator_identity: validator,
+        epoch_index: snapshot.get_current_epoch().await?.index,
+        unbonded_amount,
+        delegation_amount: snapshot
+            .get_validator_rate(&validator)
+            .await?
+            .context("validator has rate data")?
+            .delegation_amount(unbonded_amount),
+    };
+    let tx = CosmosTx {
+        messages: vec![pbjson_types::Any {
+            type_url: pb_stake::Delegate::type_url(),
+            value: delegate.encode_to_vec().into(),
+        }],
+    };
+    let packet = Packet {
+        sequence: 1u64.into(),
+        port_on_a: controller_port_id.clone(),
+        chan_on_a: controller_channel_id.clone(),
+        port_on_b: host_port_id.clone(),
+        chan_on_b: host_channel_id.clone(),
+        data: serde_json::to_vec(&PacketData {
+            packet_type: PacketType::ExecuteTx,
+            data: BASE64_STANDARD.encode(prost::Message::encode_to_vec(&tx)),
+            memo: String::new(),
+        })?,
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: Timestamp::none(),
+    };
+
+    // The controller learns the host accepted the channel, opens its end, and sends the packet.
+    let open_root = controller
+        .commit(|state| {
+            state.put_channel(
+                &controller_channel_id,
+                &controller_port_id,
+                ChannelEnd {
+                    state: ChannelState::Open,
+                    ordering: Order::Ordered,
+                    remote: Counterparty::new(host_port_id.clone(), Some(host_channel_id.clone())),
+                    connection_hops: vec![controller_connection_id.clone()],
+                    version: host_channel.version.clone(),
+                },
+            );
+            state.put_packet_commitment(&packet);
+        })
+        .await?;
+    let proof_chan_open = controller
+        .prove(ChannelEndPath::new(&controller_port_id, &controller_channel_id).to_string())
+        .await?;
+    let proof_commitment = controller
+        .prove(
+            CommitmentPath::new(&controller_port_id, &controller_channel_id, 1u64.into())
+                .to_string(),
+        )
+        .await?;
+    {
+        let mut delta = StateDelta::new(storage.latest_snapshot());
+        let block_time = delta.get_block_timestamp().await?;
+        delta
+            .put_verified_consensus_state::<PenumbraHost>(
+                Height::new(0, 2)?,
+                client_id.clone(),
+                TendermintConsensusState::new(open_root, block_time, tendermint::Hash::None),
+            )
+            .await?;
+        storage.commit(delta).await?;
+    }
+
+    // The host confirms the channel, and executes the packet on the account's behalf.
+    relay(
+        &storage,
+        IbcRelay::ChannelOpenConfirm(MsgChannelOpenConfirm {
+            port_id_on_b: host_port_id.clone(),
+            chan_id_on_b: host_channel_id.clone(),
+            proof_chan_end_on_a: proof_chan_open,
+            proof_height_on_a: Height::new(0, 2)?,
+            signer: "relayer".to_string(),
+        }),
+    )
+    .await?;
+    relay(
+        &storage,
+        IbcRelay::RecvPacket(MsgRecvPacket {
+            packet,
+            proof_commitment_on_a: proof_commitment,
+            proof_height_on_a: Height::new(0, 2)?,
+            signer: "relayer".to_string(),
+        }),
+    )
+    .await?;
+
+    // The delegation was paid for from, and its tokens credited to, the account's balance.
+    let snapshot = storage.latest_snapshot();
+    assert_eq!(
+        snapshot
+            .ica_balance(&account, &STAKING_TOKEN_ASSET_ID)
+            .await?,
+        funds
+            .checked_sub(&unbonded_amount)
+            .expect("funds cover the delegation"),
+        "the delegated stake should be debited from the account"
+    );
+    assert_eq!(
+        snapshot
+            .ica_balance(&account, &DelegationToken::from(validator).id())
+            .await?,
+        delegate.delegation_amount,
+        "the delegation tokens should be credited to the account"
+    );
+    assert!(
+        snapshot
+            .get_packet_acknowledgement(&host_port_id, &host_channel_id, 1)
+            .await?
+            .is_some(),
+        "the host should acknowledge the packet"
+    );
+
+    // Free our temporary storage.
+    drop(storage);
+    drop(guard);
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use {
-    self::common::BuilderExt,
+    self::common::{BuilderExt, TempStorageExt},
     anyhow::anyhow,
     cnidarium::TempStorage,
     penumbra_app::{genesis::AppState, server::consensus::Consensus},
@@ -23,7 +23,7 @@ mod common;
 async fn app_can_spend_notes_and_detect_outputs() -> anyhow::Result<()> {
     // Install a test logger, acquire some temporary storage, and start the test node.
     let guard = common::set_tracing_subscriber();
-    let storage = TempStorage::new().await?;
+    let storage = TempStorage::new().await?.with_key_guard()?;
     let mut test_node = {
         let app_state = AppState::default();
         let consensus = Consensus::new(storage.as_ref().clone());
@@ -1,5 +1,5 @@
 use {
-    self::common::{BuilderExt, TestNodeExt},
+    self::common::{BuilderExt, TempStorageExt, TestNodeExt},
     anyhow::anyhow,
     ark_ff::UniformRand,
     cnidarium::TempStorage,
@@ -36,7 +36,7 @@ const UNBONDING_DELAY: u64 = 4;
 async fn app_can_undelegate_from_a_validator() -> anyhow::Result<()> {
     // Install a test logger, acquire some temporary storage, and start the test node.
     let guard = common::set_tracing_subscriber();
-    let storage = TempStorage::new().await?;
+    let storage = TempStorage::new().await?.with_key_guard()?;
 
     // Helper function to get the latest block height.
     let get_latest_height = || async {
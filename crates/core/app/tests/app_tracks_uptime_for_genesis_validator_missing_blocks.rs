@@ -1,7 +1,7 @@
 mod common;
 
 use {
-    self::common::BuilderExt,
+    self::common::{BuilderExt, TempStorageExt},
     anyhow::Context,
     cnidarium::TempStorage,
     penumbra_app::{genesis::AppState, server::consensus::Consensus},
@@ -15,7 +15,7 @@ use {
 async fn app_tracks_uptime_for_genesis_validator_missing_blocks() -> anyhow::Result<()> {
     // Install a test logger, acquire some temporary storage, and start the test node.
     let guard = common::set_tracing_subscriber();
-    let storage = TempStorage::new().await?;
+    let storage = TempStorage::new().await?.with_key_guard()?;
 
     // Start the test node.
     let mut node = {
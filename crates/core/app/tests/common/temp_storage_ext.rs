@@ -7,12 +7,19 @@ use {
 
 #[async_trait]
 pub trait TempStorageExt: Sized {
+    /// Rejects writes to state keys that aren't declared in [`penumbra_app::state_key_schema`].
+    fn with_key_guard(self) -> anyhow::Result<Self>;
     async fn apply_genesis(self, genesis: AppState) -> anyhow::Result<Self>;
     async fn apply_default_genesis(self) -> anyhow::Result<Self>;
 }
 
 #[async_trait]
 impl TempStorageExt for TempStorage {
+    fn with_key_guard(self) -> anyhow::Result<Self> {
+        self.set_key_guard(penumbra_app::state_key_schema()?.key_guard());
+        Ok(self)
+    }
+
     async fn apply_genesis(self, genesis: AppState) -> anyhow::Result<Self> {
         // Check that we haven't already applied a genesis state:
         if self.latest_version() != u64::MAX {
@@ -1,5 +1,5 @@
 use {
-    self::common::BuilderExt,
+    self::common::{BuilderExt, TempStorageExt},
     cnidarium::TempStorage,
     penumbra_app::{genesis::AppState, server::consensus::Consensus},
     penumbra_mock_consensus::TestNode,
@@ -15,7 +15,7 @@ mod common;
 async fn mock_consensus_can_send_a_sequence_of_empty_blocks() -> anyhow::Result<()> {
     // Install a test logger, acquire some temporary storage, and start the test node.
     let guard = common::set_tracing_subscriber();
-    let storage = TempStorage::new().await?;
+    let storage = TempStorage::new().await?.with_key_guard()?;
     let mut test_node = {
         let app_state = AppState::default();
         let consensus = Consensus::new(storage.as_ref().clone());
@@ -0,0 +1,75 @@
+use {
+    self::common::{BuilderExt, TempStorageExt},
+    cnidarium::TempStorage,
+    penumbra_app::{
+        app::StateReadExt as _,
+        genesis::{AppState, GenesisBuilder},
+        server::consensus::Consensus,
+    },
+    penumbra_keys::test_keys,
+    penumbra_mock_client::MockClient,
+    penumbra_mock_consensus::TestNode,
+    penumbra_shielded_pool::genesis::Allocation,
+    tap::TapFallible,
+};
+
+mod common;
+
+/// Exercises that a genesis assembled with [`GenesisBuilder`] can be used to start a chain.
+#[tokio::test]
+async fn mock_consensus_can_start_from_a_built_genesis() -> anyhow::Result<()> {
+    // Install a test logger, and acquire some temporary storage.
+    let guard = common::set_tracing_subscriber();
+    let storage = TempStorage::new().await?.with_key_guard()?;
+
+    // Build a genesis with custom economic parameters, and a single allocation to the test wallet.
+    let content = GenesisBuilder::new("penumbra-test-genesis-builder")
+        .update_params(|params| {
+            params.sct_params.epoch_duration = 10;
+            params.stake_params.unbonding_delay = 21;
+            params.stake_params.active_validator_limit = 7;
+        })
+        .with_allocation(Allocation {
+            raw_amount: 1_000u128.into(),
+            raw_denom: "upenumbra".to_string(),
+            address: *test_keys::ADDRESS_0,
+        })
+        .build()?;
+
+    // Start the test node from that genesis.
+    let test_node = {
+        let consensus = Consensus::new(storage.as_ref().clone());
+        TestNode::builder()
+            .single_validator()
+            .with_penumbra_auto_app_state(AppState::Content(content))?
+            .init_chain(consensus)
+            .await
+            .tap_ok(|e| tracing::info!(hash = %e.last_app_hash_hex(), "finished init chain"))?
+    };
+
+    // The chain should have started with the customized parameters...
+    let snapshot = storage.latest_snapshot();
+    let params = snapshot.get_app_params().await?;
+    assert_eq!(params.chain_id, "penumbra-test-genesis-builder");
+    assert_eq!(params.sct_params.epoch_duration, 10);
+    assert_eq!(params.stake_params.unbonding_delay, 21);
+    assert_eq!(params.stake_params.active_validator_limit, 7);
+
+    // ...and the test wallet should hold exactly the allocated note.
+    let client = MockClient::new(test_keys::SPEND_KEY.clone())
+        .with_sync_to_storage(&storage)
+        .await?;
+    let amounts: Vec<_> = client
+        .notes
+        .values()
+        .map(|note| u128::from(note.amount()))
+        .collect();
+    assert_eq!(amounts, vec![1_000]);
+
+    // Free our temporary storage.
+    drop(test_node);
+    drop(storage);
+    drop(guard);
+
+    Ok(())
+}
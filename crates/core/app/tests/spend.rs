@@ -29,7 +29,11 @@ use tendermint::abci;
 async fn spend_happy_path() -> anyhow::Result<()> {
     let mut rng = rand_chacha::ChaChaRng::seed_from_u64(1312);
 
-    let storage = TempStorage::new().await?.apply_default_genesis().await?;
+    let storage = TempStorage::new()
+        .await?
+        .with_key_guard()?
+        .apply_default_genesis()
+        .await?;
     let mut state = Arc::new(StateDelta::new(storage.latest_snapshot()));
 
     let height = 1;
@@ -108,6 +112,8 @@ async fn invalid_dummy_spend() {
     let storage = TempStorage::new()
         .await
         .unwrap()
+        .with_key_guard()
+        .expect("can install the state key guard")
         .apply_default_genesis()
         .await
         .unwrap();
@@ -209,6 +215,8 @@ async fn spend_duplicate_nullifier_previous_transaction() {
     let storage = TempStorage::new()
         .await
         .expect("can start new temp storage")
+        .with_key_guard()
+        .expect("can install the state key guard")
         .apply_default_genesis()
         .await
         .expect("can apply default genesis");
@@ -300,6 +308,8 @@ async fn spend_duplicate_nullifier_same_transaction() {
     let storage = TempStorage::new()
         .await
         .expect("can start new temp storage")
+        .with_key_guard()
+        .expect("can install the state key guard")
         .apply_default_genesis()
         .await
         .expect("can apply default genesis");
@@ -31,7 +31,11 @@ use tendermint::abci;
 async fn swap_and_swap_claim() -> anyhow::Result<()> {
     let mut rng = rand_chacha::ChaChaRng::seed_from_u64(1312);
 
-    let storage = TempStorage::new().await?.apply_default_genesis().await?;
+    let storage = TempStorage::new()
+        .await?
+        .with_key_guard()?
+        .apply_default_genesis()
+        .await?;
     let mut state = Arc::new(StateDelta::new(storage.latest_snapshot()));
 
     let height = 1;
@@ -145,6 +149,8 @@ async fn swap_claim_duplicate_nullifier_previous_transaction() {
     let storage = TempStorage::new()
         .await
         .unwrap()
+        .with_key_guard()
+        .expect("can install the state key guard")
         .apply_default_genesis()
         .await
         .unwrap();
@@ -268,7 +274,11 @@ async fn swap_claim_duplicate_nullifier_previous_transaction() {
 async fn swap_with_nonzero_fee() -> anyhow::Result<()> {
     let mut rng = rand_chacha::ChaChaRng::seed_from_u64(1312);
 
-    let storage = TempStorage::new().await?.apply_default_genesis().await?;
+    let storage = TempStorage::new()
+        .await?
+        .with_key_guard()?
+        .apply_default_genesis()
+        .await?;
     let mut state = Arc::new(StateDelta::new(storage.latest_snapshot()));
 
     let height = 1;
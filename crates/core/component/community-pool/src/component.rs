@@ -10,7 +10,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use cnidarium::StateWrite;
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 use tendermint::v0_37::abci;
 use tracing::instrument;
 pub use view::{StateReadExt, StateWriteExt};
@@ -52,3 +52,7 @@ impl Component for CommunityPool {
         Ok(())
     }
 }
+
+impl EpochHandler for CommunityPool {
+    const NAME: &'static str = "community_pool";
+}
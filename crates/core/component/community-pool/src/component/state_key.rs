@@ -16,3 +16,12 @@ pub fn all_assets_balance() -> &'static str {
     // note: this must be the prefix of the above.
     "community_pool/asset/"
 }
+
+/// Declares this component's state keys in the application's key schema.
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    schema
+        .component("community_pool")
+        .verifiable::<crate::params::CommunityPoolParameters>(community_pool_params())?
+        .verifiable::<penumbra_num::Amount>("community_pool/asset/{asset_id}")?;
+    Ok(())
+}
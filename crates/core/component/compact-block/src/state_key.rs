@@ -12,3 +12,12 @@ pub fn prefix() -> &'static str {
 pub fn height(height: u64) -> String {
     format!("{height:020}")
 }
+
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    schema
+        .component("compact_block")
+        .nonverifiable::<crate::CompactBlock>("compactblock/{height}")?;
+    Ok(())
+}
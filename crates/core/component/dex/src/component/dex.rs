@@ -3,7 +3,7 @@ use std::{collections::BTreeMap, sync::Arc};
 use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 use penumbra_asset::{asset, Value, STAKING_TOKEN_ASSET_ID};
 use penumbra_num::Amount;
 use penumbra_proto::{StateReadProto, StateWriteProto};
@@ -136,6 +136,10 @@ impl Component for Dex {
     }
 }
 
+impl EpochHandler for Dex {
+    const NAME: &'static str = "dex";
+}
+
 /// Extension trait providing read access to dex data.
 #[async_trait]
 pub trait StateReadExt: StateRead {
@@ -79,6 +79,31 @@ pub fn aggregate_value() -> &'static str {
     "dex/aggregate_value"
 }
 
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    use crate::{lp::position::Position, BatchSwapOutputData, DexParameters, SwapExecution};
+    use penumbra_num::Amount;
+
+    schema
+        .component("dex")
+        .verifiable::<DexParameters>(config::dex_params())?
+        .verifiable::<Amount>("dex/value_balance/{asset_id}")?
+        .verifiable::<Position>("dex/position/{position_id}")?
+        .verifiable::<BatchSwapOutputData>("dex/output/{height}/{asset_1}/{asset_2}")?
+        .verifiable::<SwapExecution>("dex/arb_execution/{height}")?
+        .nonverifiable::<SwapExecution>("dex/swap_execution/{height}/{start}/{end}")?
+        // The engine indices below have binary keys, so their variable part is matched as a whole.
+        .nonverifiable::<asset::Id>("dex/ra/{from_and_liquidity}")?
+        .nonverifiable::<Amount>("dex/ab/{pair}")?
+        .nonverifiable::<()>("dex/pi/{pair_price_and_id}")?
+        .nonverifiable::<u16>("dex/internal/counter/num_positions/{pair}")?
+        .nonverifiable::<()>(
+            "dex/internal/eviction_queue/inventory_index{pair_inventory_and_id}",
+        )?;
+    Ok(())
+}
+
 pub(crate) mod engine {
     use super::*;
     use crate::lp::BareTradingFunction;
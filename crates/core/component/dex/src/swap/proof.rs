@@ -29,7 +29,7 @@ use crate::{
     TradingPair,
 };
 
-use penumbra_proof_params::{DummyWitness, GROTH16_PROOF_LENGTH_BYTES};
+use penumbra_proof_params::{create_proof, DummyWitness, GROTH16_PROOF_LENGTH_BYTES};
 
 /// The public inputs to a [`SwapProof`].
 #[derive(Clone, Debug)]
@@ -204,10 +204,7 @@ impl SwapProof {
         private: SwapProofPrivate,
     ) -> anyhow::Result<Self> {
         let circuit = SwapCircuit { public, private };
-        let proof = Groth16::<Bls12_377, LibsnarkReduction>::create_proof_with_reduction(
-            circuit, pk, blinding_r, blinding_s,
-        )
-        .map_err(|err| anyhow::anyhow!(err))?;
+        let proof = create_proof(circuit, pk, blinding_r, blinding_s)?;
         let mut proof_bytes = [0u8; GROTH16_PROOF_LENGTH_BYTES];
         Proof::serialize_compressed(&proof, &mut proof_bytes[..]).expect("can serialize Proof");
         Ok(Self(proof_bytes))
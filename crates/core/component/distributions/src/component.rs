@@ -8,7 +8,7 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use cnidarium::StateWrite;
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 use penumbra_asset::STAKING_TOKEN_DENOM;
 use penumbra_num::Amount;
 use tendermint::v0_37::abci;
@@ -55,6 +55,10 @@ impl Component for Distributions {
     }
 }
 
+impl EpochHandler for Distributions {
+    const NAME: &'static str = "distributions";
+}
+
 #[async_trait]
 trait DistributionManager: StateWriteExt {
     /// Compute the total new issuance of staking tokens for this epoch.
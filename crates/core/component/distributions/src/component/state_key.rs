@@ -10,3 +10,11 @@ pub fn distributions_parameters() -> &'static str {
 pub fn distributions_parameters_updated() -> &'static str {
     "distributions/parameters_updated"
 }
+
+/// Declares this component's state keys in the application's key schema.
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    schema
+        .component("distributions")
+        .verifiable::<crate::DistributionsParameters>(distributions_parameters())?;
+    Ok(())
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 use crate::genesis;
 use async_trait::async_trait;
 use cnidarium::StateWrite;
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 use tendermint::abci;
 use tracing::instrument;
 pub use view::{StateReadExt, StateWriteExt};
@@ -50,3 +50,7 @@ impl Component for Fee {
         Ok(())
     }
 }
+
+impl EpochHandler for Fee {
+    const NAME: &'static str = "fee";
+}
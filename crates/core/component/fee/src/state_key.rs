@@ -13,3 +13,13 @@ pub fn gas_prices_changed() -> &'static str {
 pub fn fee_params_updated() -> &'static str {
     "fee/fee_params_updated"
 }
+
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    schema
+        .component("fee")
+        .verifiable::<crate::FeeParameters>(fee_params())?
+        .verifiable::<crate::GasPrices>(gas_prices())?;
+    Ok(())
+}
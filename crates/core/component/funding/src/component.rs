@@ -3,6 +3,7 @@ mod state_key;
 pub mod view;
 use ::metrics::{gauge, histogram};
 pub use metrics::register_metrics;
+pub use state_key::declare_schema;
 
 /* Component implementation */
 use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
@@ -14,7 +15,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::StateWrite;
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 use tendermint::v0_37::abci;
 use tracing::instrument;
 
@@ -158,3 +159,12 @@ impl Component for Funding {
         Ok(())
     }
 }
+
+impl EpochHandler for Funding {
+    const NAME: &'static str = "funding";
+
+    /// Funding rewards are paid out of the funding queue and base rate prepared
+    /// by the staking component, within the issuance budget computed by the
+    /// distributions component.
+    const RUN_AFTER: &'static [&'static str] = &["distributions", "staking"];
+}
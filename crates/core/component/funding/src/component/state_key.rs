@@ -5,3 +5,11 @@ pub fn funding_parameters() -> &'static str {
 pub fn funding_parameters_updated() -> &'static str {
     "funding/parameters_updated"
 }
+
+/// Declares this component's state keys in the application's key schema.
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    schema
+        .component("funding")
+        .verifiable::<crate::FundingParameters>(funding_parameters())?;
+    Ok(())
+}
@@ -8,7 +8,7 @@ use penumbra_proto::StateWriteProto as _;
 use tendermint::v0_37::abci;
 use tracing::instrument;
 
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 
 use crate::{
     proposal_state::{
@@ -74,6 +74,10 @@ impl Component for Governance {
     }
 }
 
+impl EpochHandler for Governance {
+    const NAME: &'static str = "governance";
+}
+
 #[instrument(skip(state))]
 pub async fn enact_all_passed_proposals<S: StateWrite>(mut state: S) -> Result<()> {
     // For every unfinished proposal, conclude those that finish in this block
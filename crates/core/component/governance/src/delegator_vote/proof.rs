@@ -21,7 +21,9 @@ use penumbra_keys::keys::{
     AuthorizationKeyVar, Bip44Path, IncomingViewingKeyVar, NullifierKey, NullifierKeyVar,
     RandomizedVerificationKey, SeedPhrase, SpendAuthRandomizerVar, SpendKey,
 };
-use penumbra_proof_params::{DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES};
+use penumbra_proof_params::{
+    create_proof, DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES,
+};
 use penumbra_proto::{core::component::governance::v1 as pb, DomainType};
 use penumbra_sct::{Nullifier, NullifierVar};
 use penumbra_shielded_pool::{note, Note, Rseed};
@@ -332,10 +334,7 @@ impl DelegatorVoteProof {
         private: DelegatorVoteProofPrivate,
     ) -> anyhow::Result<Self> {
         let circuit = DelegatorVoteCircuit { public, private };
-        let proof = Groth16::<Bls12_377, LibsnarkReduction>::create_proof_with_reduction(
-            circuit, pk, blinding_r, blinding_s,
-        )
-        .map_err(|err| anyhow::anyhow!(err))?;
+        let proof = create_proof(circuit, pk, blinding_r, blinding_s)?;
         let mut proof_bytes = [0u8; GROTH16_PROOF_LENGTH_BYTES];
         Proof::serialize_compressed(&proof, &mut proof_bytes[..]).expect("can serialize Proof");
         Ok(Self(proof_bytes))
@@ -147,3 +147,48 @@ pub mod halt {
         "governance/counters/halt_count"
     }
 }
+
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    use crate::{
+        params::GovernanceParameters, proposal::ChangedAppParametersSet,
+        proposal_state::State as ProposalState, Proposal, Tally, ValidatorVoteReason, Vote,
+    };
+    use penumbra_num::Amount;
+    use penumbra_stake::rate::RateData;
+
+    schema
+        .component("governance")
+        .verifiable::<GovernanceParameters>(governance_params())?
+        .verifiable::<u64>(next_proposal_id())?
+        .verifiable::<Proposal>("governance/proposal/{proposal_id}/data")?
+        .verifiable::<ProposalState>("governance/proposal/{proposal_id}/state")?
+        .verifiable::<Amount>("governance/proposal/{proposal_id}/deposit_amount")?
+        .verifiable::<u64>("governance/proposal/{proposal_id}/voting_start")?
+        .verifiable::<u64>("governance/proposal/{proposal_id}/voting_start_position")?
+        .verifiable::<u64>("governance/proposal/{proposal_id}/voting_end")?
+        .verifiable::<u64>("governance/proposal/{proposal_id}/voted_nullifiers/{nullifier}")?
+        .verifiable::<RateData>(
+            "governance/proposal/{proposal_id}/rate_data_at_start/{identity_key}",
+        )?
+        .verifiable::<Amount>(
+            "governance/proposal/{proposal_id}/voting_power_at_start/{identity_key}",
+        )?
+        .verifiable::<()>("governance/unfinished_proposals/{proposal_id}")?
+        .verifiable::<Vote>("governance/validator_vote/{proposal_id}/{identity_key}")?
+        .verifiable::<ValidatorVoteReason>(
+            "governance/validator_vote_reason/{proposal_id}/{identity_key}",
+        )?
+        .verifiable::<Tally>("governance/tallied_delegator_votes/{proposal_id}/{identity_key}")?
+        .verifiable::<Tally>(
+            "governance/untallied_delegator_vote/{proposal_id}/{identity_key}/{nullifier}",
+        )?
+        .verifiable::<u64>(
+            "governance/deliver_community_pool_transactions/{block_height}/{proposal_id}",
+        )?
+        .verifiable::<ChangedAppParametersSet>("app/change_app_params/{block_height}/")?
+        .verifiable::<u64>(halt::halt_count())?
+        .nonverifiable::<u64>(upgrades::next_upgrade())?;
+    Ok(())
+}
@@ -5,6 +5,7 @@ mod client_counter;
 mod connection;
 mod connection_counter;
 mod ics02_validation;
+mod interchain_account;
 
 #[cfg(feature = "rpc")]
 pub mod rpc;
@@ -25,11 +26,16 @@ use msg_handler::MsgHandler;
 
 pub use self::metrics::register_metrics;
 pub use channel::StateReadExt as ChannelStateReadExt;
+pub use channel::StateWriteExt as ChannelStateWriteExt;
+pub use client::ConsensusStateWriteExt;
 pub use client::StateReadExt as ClientStateReadExt;
 pub use client::StateWriteExt as ClientStateWriteExt;
 pub use connection::StateReadExt as ConnectionStateReadExt;
 pub use connection::StateWriteExt as ConnectionStateWriteExt;
 pub use host_interface::HostInterface;
+pub use interchain_account::{
+    StateReadExt as InterchainAccountStateReadExt, StateWriteExt as InterchainAccountStateWriteExt,
+};
 pub use view::{StateReadExt, StateWriteExt};
 
 pub use ibc_component::Ibc;
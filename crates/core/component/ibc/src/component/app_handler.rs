@@ -5,15 +5,26 @@
 /// that they have subscribed to, and apply application-specific state transition logic.
 ///
 /// The primary IBC application is the Ics20 transfer application, which allows for interchain
-/// token transfers.
+/// token transfers. Penumbra also hosts Ics27 interchain accounts on the `icahost` port.
 use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
-use ibc_types::core::channel::msgs::{
-    MsgAcknowledgement, MsgChannelCloseConfirm, MsgChannelCloseInit, MsgChannelOpenAck,
-    MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket, MsgTimeout,
+use ibc_types::core::channel::{
+    msgs::{
+        MsgAcknowledgement, MsgChannelCloseConfirm, MsgChannelCloseInit, MsgChannelOpenAck,
+        MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket, MsgTimeout,
+    },
+    PortId, Version,
 };
 
+use crate::ics27;
+
+/// Returns true if `port_id` is bound to one of the IBC applications Penumbra runs, so that
+/// channel and packet events on it should be passed to the app handler.
+pub fn is_bound_port(port_id: &PortId) -> bool {
+    *port_id == PortId::transfer() || port_id.as_str() == ics27::HOST_PORT_ID
+}
+
 /// AppHandlerCheck defines the interface for an IBC application to consume IBC channel and packet
 /// events, and apply their validation logic. This validation logic is used for stateful validation
 /// only.
@@ -21,6 +32,15 @@ use ibc_types::core::channel::msgs::{
 pub trait AppHandlerCheck: Send + Sync {
     async fn chan_open_init_check<S: StateRead>(state: S, msg: &MsgChannelOpenInit) -> Result<()>;
     async fn chan_open_try_check<S: StateRead>(state: S, msg: &MsgChannelOpenTry) -> Result<()>;
+    /// Returns the version to record for the channel being opened by `msg`, once
+    /// `chan_open_try_check` has accepted it. By default, this is the version proposed by the
+    /// counterparty.
+    async fn chan_open_try_version<S: StateRead>(
+        _state: S,
+        msg: &MsgChannelOpenTry,
+    ) -> Result<Version> {
+        Ok(msg.version_supported_on_a.clone())
+    }
     async fn chan_open_ack_check<S: StateRead>(state: S, msg: &MsgChannelOpenAck) -> Result<()>;
     async fn chan_open_confirm_check<S: StateRead>(
         state: S,
@@ -146,6 +146,45 @@ pub trait ConsensusStateWriteExt: StateWrite + Sized {
 
         Ok(())
     }
+
+    /// Records the initial consensus state of a client created at genesis.
+    ///
+    /// There's no block height or time to record the consensus state as
+    /// processed at yet, so this records it as processed at height zero, at the
+    /// consensus state's own timestamp.
+    fn put_genesis_consensus_state(
+        &mut self,
+        height: Height,
+        client_id: ClientId,
+        consensus_state: TendermintConsensusState,
+    ) -> Result<()> {
+        let processed_time: ibc_types::timestamp::Timestamp = consensus_state.timestamp.into();
+
+        self.put(
+            IBC_COMMITMENT_PREFIX
+                .apply_string(ClientConsensusStatePath::new(&client_id, &height).to_string()),
+            consensus_state,
+        );
+
+        self.put_proto::<u64>(
+            state_key::client_processed_times(&client_id, &height),
+            processed_time.nanoseconds(),
+        );
+
+        self.put(
+            state_key::client_processed_heights(&client_id, &height),
+            ibc_types::core::client::Height::new(0, 0)?,
+        );
+
+        self.put_verified_heights(
+            &client_id,
+            VerifiedHeights {
+                heights: vec![height],
+            },
+        );
+
+        Ok(())
+    }
 }
 
 impl<T: StateWrite> ConsensusStateWriteExt for T {}
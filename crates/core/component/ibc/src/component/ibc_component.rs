@@ -3,13 +3,17 @@ use std::sync::Arc;
 use anyhow::Result;
 use cnidarium::StateWrite;
 use ibc_types::{
-    core::client::Height, lightclients::tendermint::ConsensusState as TendermintConsensusState,
+    core::client::{ClientId, Height},
+    lightclients::tendermint::{client_type, ConsensusState as TendermintConsensusState},
 };
 use tendermint::abci;
 use tracing::instrument;
 
 use crate::{
-    component::{client::StateWriteExt as _, client_counter::ClientCounter},
+    component::{
+        client::{ConsensusStateWriteExt as _, StateWriteExt as _},
+        client_counter::ClientCounter,
+    },
     genesis, StateWriteExt as _,
 };
 
@@ -26,7 +30,22 @@ impl Ibc {
         match app_state {
             Some(genesis) => {
                 state.put_ibc_params(genesis.ibc_params.clone());
-                state.put_client_counter(ClientCounter(0))
+
+                for (index, client) in genesis.clients.iter().enumerate() {
+                    let client_id = ClientId::new(client_type(), index as u64)
+                        .expect("genesis client ids are valid");
+                    tracing::info!(%client_id, chain_id = %client.client_state.chain_id, "creating genesis client");
+
+                    state.put_client(&client_id, client.client_state.clone());
+                    state
+                        .put_genesis_consensus_state(
+                            client.client_state.latest_height(),
+                            client_id,
+                            client.consensus_state.clone(),
+                        )
+                        .expect("can record genesis consensus state");
+                }
+                state.put_client_counter(ClientCounter(genesis.clients.len() as u64))
             }
             None => { /* perform upgrade specific check */ }
         }
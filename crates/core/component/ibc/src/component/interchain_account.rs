@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use cnidarium::{StateRead, StateWrite};
+use ibc_types::core::{
+    channel::{ChannelId, PortId},
+    connection::ConnectionId,
+};
+use penumbra_asset::{asset, Value};
+use penumbra_num::Amount;
+use penumbra_proto::{StateReadProto, StateWriteProto};
+
+use crate::ics27;
+
+use super::state_key;
+
+#[async_trait]
+pub trait StateReadExt: StateRead {
+    /// Returns the address of the interchain account registered by the controller port
+    /// `controller_port_id` on the host connection `connection_id`, if there is one.
+    async fn ica_account(
+        &self,
+        connection_id: &ConnectionId,
+        controller_port_id: &PortId,
+    ) -> Result<Option<String>> {
+        self.get_proto(&state_key::ics27_account(connection_id, controller_port_id))
+            .await
+    }
+
+    /// Returns true if `address` is the address of a registered interchain account.
+    async fn is_ica_account(&self, address: &str) -> Result<bool> {
+        Ok(self
+            .get_proto::<String>(&state_key::ics27_account_owner(address))
+            .await?
+            .is_some())
+    }
+
+    /// Returns the channel the controller port `controller_port_id` last opened to its
+    /// interchain account on the host connection `connection_id`.
+    async fn ica_active_channel(
+        &self,
+        connection_id: &ConnectionId,
+        controller_port_id: &PortId,
+    ) -> Result<Option<ChannelId>> {
+        self.get_proto::<String>(&state_key::ics27_active_channel(
+            connection_id,
+            controller_port_id,
+        ))
+        .await?
+        .map(|channel_id| ChannelId::from_str(&channel_id).context("invalid channel id"))
+        .transpose()
+    }
+
+    /// Returns the amount of `asset_id` held by the interchain account at `address`.
+    async fn ica_balance(&self, address: &str, asset_id: &asset::Id) -> Result<Amount> {
+        Ok(self
+            .get(&state_key::ics27_balance(address, asset_id))
+            .await?
+            .unwrap_or_else(Amount::zero))
+    }
+}
+
+impl<T: StateRead + ?Sized> StateReadExt for T {}
+
+#[async_trait]
+pub trait StateWriteExt: StateWrite {
+    /// Registers the interchain account of the controller port `controller_port_id` on the host
+    /// connection `connection_id`, returning its address.
+    fn put_ica_account(
+        &mut self,
+        connection_id: &ConnectionId,
+        controller_port_id: &PortId,
+    ) -> String {
+        let address = ics27::account_address(connection_id, controller_port_id);
+        self.put_proto(
+            state_key::ics27_account(connection_id, controller_port_id),
+            address.clone(),
+        );
+        self.put_proto(
+            state_key::ics27_account_owner(&address),
+            format!("{connection_id}/{controller_port_id}"),
+        );
+        address
+    }
+
+    fn put_ica_active_channel(
+        &mut self,
+        connection_id: &ConnectionId,
+        controller_port_id: &PortId,
+        channel_id: &ChannelId,
+    ) {
+        self.put_proto(
+            state_key::ics27_active_channel(connection_id, controller_port_id),
+            channel_id.to_string(),
+        );
+    }
+
+    /// Adds `value` to the balance of the interchain account at `address`.
+    async fn credit_ica_balance(&mut self, address: &str, value: Value) -> Result<()> {
+        let balance = self
+            .ica_balance(address, &value.asset_id)
+            .await?
+            .checked_add(&value.amount)
+            .context("overflow crediting interchain account")?;
+        self.put(state_key::ics27_balance(address, &value.asset_id), balance);
+        Ok(())
+    }
+
+    /// Removes `value` from the balance of the interchain account at `address`, failing if the
+    /// account doesn't hold enough of the asset.
+    async fn debit_ica_balance(&mut self, address: &str, value: Value) -> Result<()> {
+        let balance = self
+            .ica_balance(address, &value.asset_id)
+            .await?
+            .checked_sub(&value.amount)
+            .with_context(|| {
+                format!(
+                    "interchain account {address} holds less than {} of asset {}",
+                    value.amount, value.asset_id
+                )
+            })?;
+        self.put(state_key::ics27_balance(address, &value.asset_id), balance);
+        Ok(())
+    }
+}
+
+impl<T: StateWrite + ?Sized> StateWriteExt for T {}
@@ -7,12 +7,15 @@ use ibc_types::core::{
     connection::State as ConnectionState,
 };
 
-use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
-    channel::{StateReadExt as _, StateWriteExt as _},
-    connection::StateReadExt as _,
-    proof_verification::{commit_packet, PacketProofVerifier},
-    HostInterface, MsgHandler,
+use crate::{
+    component::{
+        app_handler::{AppHandlerCheck, AppHandlerExecute},
+        channel::{StateReadExt as _, StateWriteExt as _},
+        connection::StateReadExt as _,
+        proof_verification::{commit_packet, PacketProofVerifier},
+        HostInterface, MsgHandler,
+    },
+    ics27,
 };
 
 #[async_trait]
@@ -78,7 +81,9 @@ impl MsgHandler for MsgAcknowledgement {
         }
 
         let transfer = PortId::transfer();
-        if self.packet.port_on_b == transfer {
+        if self.packet.port_on_b == transfer
+            || self.packet.port_on_a.as_str() == ics27::HOST_PORT_ID
+        {
             AH::acknowledge_packet_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -118,7 +123,9 @@ impl MsgHandler for MsgAcknowledgement {
         );
 
         let transfer = PortId::transfer();
-        if self.packet.port_on_b == transfer {
+        if self.packet.port_on_b == transfer
+            || self.packet.port_on_a.as_str() == ics27::HOST_PORT_ID
+        {
             AH::acknowledge_packet_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
@@ -4,13 +4,13 @@ use cnidarium::StateWrite;
 use ibc_types::core::{
     channel::{
         channel::State as ChannelState, events, msgs::MsgChannelCloseConfirm, ChannelEnd,
-        Counterparty, PortId,
+        Counterparty,
     },
     connection::State as ConnectionState,
 };
 
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::{StateReadExt as _, StateWriteExt as _},
     connection::StateReadExt as _,
     proof_verification::ChannelProofVerifier,
@@ -87,8 +87,7 @@ impl MsgHandler for MsgChannelCloseConfirm {
             )
             .await?;
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_b == transfer {
+        if is_bound_port(&self.port_id_on_b) {
             AH::chan_close_confirm_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -112,8 +111,7 @@ impl MsgHandler for MsgChannelCloseConfirm {
         );
 
         // TODO: should this be part of the handler?
-        let transfer = PortId::transfer();
-        if self.port_id_on_b == transfer {
+        if is_bound_port(&self.port_id_on_b) {
             AH::chan_close_confirm_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
@@ -3,11 +3,11 @@ use async_trait::async_trait;
 use cnidarium::StateWrite;
 use ibc_types::core::{
     channel::channel::State as ChannelState, channel::events, channel::msgs::MsgChannelCloseInit,
-    channel::PortId, connection::State as ConnectionState,
+    connection::State as ConnectionState,
 };
 
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::{StateReadExt as _, StateWriteExt as _},
     connection::StateReadExt as _,
     HostInterface, MsgHandler,
@@ -50,8 +50,7 @@ impl MsgHandler for MsgChannelCloseInit {
         if !connection.state_matches(&ConnectionState::Open) {
             anyhow::bail!("connection for channel is not open");
         }
-        let transfer = PortId::transfer();
-        if self.port_id_on_a == transfer {
+        if is_bound_port(&self.port_id_on_a) {
             AH::chan_close_init_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -75,8 +74,7 @@ impl MsgHandler for MsgChannelCloseInit {
             .into(),
         );
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_a == transfer {
+        if is_bound_port(&self.port_id_on_a) {
             AH::chan_close_init_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
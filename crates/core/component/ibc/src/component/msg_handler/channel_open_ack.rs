@@ -3,12 +3,12 @@ use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
 use ibc_types::core::{
     channel::channel::State as ChannelState, channel::events, channel::msgs::MsgChannelOpenAck,
-    channel::ChannelEnd, channel::Counterparty, channel::PortId, connection::ConnectionEnd,
+    channel::ChannelEnd, channel::Counterparty, connection::ConnectionEnd,
     connection::State as ConnectionState,
 };
 
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::{StateReadExt as _, StateWriteExt as _},
     connection::StateReadExt as _,
     proof_verification::ChannelProofVerifier,
@@ -71,8 +71,7 @@ impl MsgHandler for MsgChannelOpenAck {
             )
             .await?;
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_a == transfer {
+        if is_bound_port(&self.port_id_on_a) {
             AH::chan_open_ack_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -98,8 +97,7 @@ impl MsgHandler for MsgChannelOpenAck {
             .into(),
         );
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_a == transfer {
+        if is_bound_port(&self.port_id_on_a) {
             AH::chan_open_ack_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
@@ -3,12 +3,11 @@ use async_trait::async_trait;
 use cnidarium::StateWrite;
 use ibc_types::core::{
     channel::channel::State as ChannelState, channel::events, channel::msgs::MsgChannelOpenConfirm,
-    channel::ChannelEnd, channel::Counterparty, channel::PortId,
-    connection::State as ConnectionState,
+    channel::ChannelEnd, channel::Counterparty, connection::State as ConnectionState,
 };
 
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::{StateReadExt as _, StateWriteExt as _},
     connection::StateReadExt as _,
     proof_verification::ChannelProofVerifier,
@@ -82,8 +81,7 @@ impl MsgHandler for MsgChannelOpenConfirm {
             )
             .await?;
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_b == transfer {
+        if is_bound_port(&self.port_id_on_b) {
             AH::chan_open_confirm_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -107,8 +105,7 @@ impl MsgHandler for MsgChannelOpenConfirm {
             .into(),
         );
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_b == transfer {
+        if is_bound_port(&self.port_id_on_b) {
             AH::chan_open_confirm_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
@@ -8,7 +8,7 @@ use ibc_types::core::channel::{
 
 use crate::component::HostInterface;
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::{StateReadExt as _, StateWriteExt as _},
     connection::StateReadExt as _,
     MsgHandler,
@@ -41,8 +41,7 @@ impl MsgHandler for MsgChannelOpenInit {
 
         // TODO: do we want to do capability authentication?
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_a == transfer {
+        if is_bound_port(&self.port_id_on_a) {
             AH::chan_open_init_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -75,8 +74,7 @@ impl MsgHandler for MsgChannelOpenInit {
             .into(),
         );
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_a == transfer {
+        if is_bound_port(&self.port_id_on_a) {
             AH::chan_open_init_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
@@ -4,13 +4,12 @@ use cnidarium::{StateRead, StateWrite};
 use ibc_types::core::{
     channel::{
         channel::State as ChannelState, events, msgs::MsgChannelOpenTry, ChannelEnd, Counterparty,
-        PortId,
     },
     connection::{ConnectionEnd, State as ConnectionState},
 };
 
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::StateWriteExt,
     connection::StateReadExt,
     proof_verification::ChannelProofVerifier,
@@ -37,7 +36,6 @@ impl MsgHandler for MsgChannelOpenTry {
         let connection_on_b = verify_connections_open(&state, self).await?;
 
         // TODO: do we want to do capability authentication?
-        // TODO: version intersection
 
         let expected_channel_on_a = ChannelEnd {
             state: ChannelState::Init,
@@ -64,12 +62,12 @@ impl MsgHandler for MsgChannelOpenTry {
             )
             .await?;
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_b == transfer {
+        let version = if is_bound_port(&self.port_id_on_b) {
             AH::chan_open_try_check(&mut state, self).await?;
+            AH::chan_open_try_version(&mut state, self).await?
         } else {
             anyhow::bail!("invalid port id");
-        }
+        };
 
         let channel_id = state
             .next_channel_id()
@@ -80,7 +78,7 @@ impl MsgHandler for MsgChannelOpenTry {
             ordering: self.ordering,
             remote: Counterparty::new(self.port_id_on_a.clone(), Some(self.chan_id_on_a.clone())),
             connection_hops: self.connection_hops_on_b.clone(),
-            version: self.version_supported_on_a.clone(),
+            version,
         };
 
         state.put_channel(&channel_id, &self.port_id_on_b, new_channel.clone());
@@ -104,8 +102,7 @@ impl MsgHandler for MsgChannelOpenTry {
             .into(),
         );
 
-        let transfer = PortId::transfer();
-        if self.port_id_on_b == transfer {
+        if is_bound_port(&self.port_id_on_b) {
             AH::chan_open_try_execute(state, self).await;
         } else {
             anyhow::bail!("invalid port id");
@@ -6,14 +6,13 @@ use ibc_types::core::{
         channel::{Order as ChannelOrder, State as ChannelState},
         events,
         msgs::MsgRecvPacket,
-        PortId,
     },
     client::Height as IBCHeight,
     connection::State as ConnectionState,
 };
 
 use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
+    app_handler::{is_bound_port, AppHandlerCheck, AppHandlerExecute},
     channel::{StateReadExt as _, StateWriteExt},
     connection::StateReadExt as _,
     proof_verification::PacketProofVerifier,
@@ -108,8 +107,7 @@ impl MsgHandler for MsgRecvPacket {
             anyhow::bail!("packet has already been processed");
         }
 
-        let transfer = PortId::transfer();
-        if self.packet.port_on_b == transfer {
+        if is_bound_port(&self.packet.port_on_b) {
             AH::recv_packet_check(&mut state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -149,9 +147,8 @@ impl MsgHandler for MsgRecvPacket {
             .into(),
         );
 
-        let transfer = PortId::transfer();
         // todo: should this be part of the app handler logic?
-        if self.packet.port_on_b == transfer {
+        if is_bound_port(&self.packet.port_on_b) {
             AH::recv_packet_execute(state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -8,13 +8,16 @@ use ibc_types::core::channel::{
     PortId,
 };
 
-use crate::component::{
-    app_handler::{AppHandlerCheck, AppHandlerExecute},
-    channel::{StateReadExt as _, StateWriteExt},
-    client::StateReadExt,
-    connection::StateReadExt as _,
-    proof_verification::{commit_packet, PacketProofVerifier},
-    HostInterface, MsgHandler,
+use crate::{
+    component::{
+        app_handler::{AppHandlerCheck, AppHandlerExecute},
+        channel::{StateReadExt as _, StateWriteExt},
+        client::StateReadExt,
+        connection::StateReadExt as _,
+        proof_verification::{commit_packet, PacketProofVerifier},
+        HostInterface, MsgHandler,
+    },
+    ics27,
 };
 
 #[async_trait]
@@ -103,7 +106,9 @@ impl MsgHandler for MsgTimeout {
         }
 
         let transfer = PortId::transfer();
-        if self.packet.port_on_b == transfer {
+        if self.packet.port_on_b == transfer
+            || self.packet.port_on_a.as_str() == ics27::HOST_PORT_ID
+        {
             H::timeout_packet_check(&mut state, self)
                 .await
                 .context("failed to execute handler for timeout_packet_check")?;
@@ -142,7 +147,9 @@ impl MsgHandler for MsgTimeout {
         );
 
         let transfer = PortId::transfer();
-        if self.packet.port_on_b == transfer {
+        if self.packet.port_on_b == transfer
+            || self.packet.port_on_a.as_str() == ics27::HOST_PORT_ID
+        {
             H::timeout_packet_execute(state, self).await?;
         } else {
             anyhow::bail!("invalid port id");
@@ -1,4 +1,4 @@
-use cnidarium::Storage;
+use cnidarium::{Snapshot, Storage};
 use tonic::transport::server::Routes;
 
 use super::HostInterface;
@@ -9,6 +9,11 @@ mod consensus_query;
 
 use std::marker::PhantomData;
 
+/// The gRPC metadata key used to request query results, and their proofs, at a
+/// specific height. This follows the Cosmos SDK convention, so that relayers can
+/// query Penumbra the same way they query other chains.
+const QUERY_HEIGHT_HEADER: &str = "x-cosmos-block-height";
+
 // TODO: hide and replace with a routes() constructor that
 // bundles up all the internal services
 #[derive(Clone)]
@@ -24,6 +29,39 @@ impl<HI: HostInterface> IbcQuery<HI> {
             _marker: PhantomData,
         }
     }
+
+    /// Returns the snapshot a query should be answered from.
+    ///
+    /// If the request carries a non-zero height in the `x-cosmos-block-height`
+    /// metadata, the snapshot at that height is used, so that the returned
+    /// membership and non-membership proofs verify against that height's
+    /// root. Otherwise, the latest snapshot is used.
+    fn snapshot_for_request<T>(
+        &self,
+        request: &tonic::Request<T>,
+    ) -> Result<Snapshot, tonic::Status> {
+        let Some(height) = request.metadata().get(QUERY_HEIGHT_HEADER) else {
+            return Ok(self.storage.latest_snapshot());
+        };
+
+        let height: u64 = height
+            .to_str()
+            .ok()
+            .and_then(|height| height.parse().ok())
+            .ok_or_else(|| {
+                tonic::Status::invalid_argument(format!(
+                    "invalid {QUERY_HEIGHT_HEADER} header: {height:?}"
+                ))
+            })?;
+
+        if height == 0 {
+            return Ok(self.storage.latest_snapshot());
+        }
+
+        self.storage.snapshot(height).ok_or_else(|| {
+            tonic::Status::not_found(format!("no snapshot available for height {height}"))
+        })
+    }
 }
 
 pub fn routes(_storage: Storage) -> Routes {
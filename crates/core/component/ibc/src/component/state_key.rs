@@ -1,4 +1,9 @@
-use ibc_types::{core::channel::ChannelId, core::client::ClientId, core::client::Height};
+use ibc_types::{
+    core::channel::{ChannelId, PortId},
+    core::client::ClientId,
+    core::client::Height,
+    core::connection::ConnectionId,
+};
 
 use penumbra_asset::asset;
 
@@ -26,3 +31,74 @@ pub fn counter() -> &'static str {
 pub fn ics20_value_balance(channel_id: &ChannelId, asset_id: &asset::Id) -> String {
     format!("ibc/ics20-value-balance/{channel_id}/{asset_id}")
 }
+pub fn ics27_account(connection_id: &ConnectionId, controller_port_id: &PortId) -> String {
+    format!("ibc/ics27-account/{connection_id}/{controller_port_id}")
+}
+pub fn ics27_active_channel(connection_id: &ConnectionId, controller_port_id: &PortId) -> String {
+    format!("ibc/ics27-active-channel/{connection_id}/{controller_port_id}")
+}
+pub fn ics27_account_owner(address: &str) -> String {
+    format!("ibc/ics27-account-owner/{address}")
+}
+pub fn ics27_balance(address: &str, asset_id: &asset::Id) -> String {
+    format!("ibc/ics27-balance/{address}/{asset_id}")
+}
+
+/// Declares this component's state keys in the application's key schema.
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    use super::{
+        client_counter::{ClientCounter, VerifiedHeights},
+        connection_counter::ConnectionCounter,
+    };
+    use crate::params::IBCParameters;
+    use ibc_types::{
+        core::{
+            channel::ChannelEnd,
+            connection::{ClientPaths, ConnectionEnd},
+        },
+        lightclients::tendermint::{
+            client_state::ClientState as TendermintClientState,
+            consensus_state::ConsensusState as TendermintConsensusState,
+        },
+    };
+    use penumbra_num::Amount;
+
+    schema
+        .component("ibc")
+        .verifiable::<IBCParameters>(ibc_params())?
+        .verifiable::<Height>("ibc/clients/{client_id}/processedHeights/{height}")?
+        .verifiable::<u64>("ibc/clients/{client_id}/processedTimes/{height}")?
+        .verifiable::<ConnectionCounter>(counter())?
+        .verifiable::<Amount>("ibc/ics20-value-balance/{channel_id}/{asset_id}")?
+        .verifiable::<String>("ibc/ics27-account/{connection_id}/{controller_port_id}")?
+        .verifiable::<String>("ibc/ics27-active-channel/{connection_id}/{controller_port_id}")?
+        .verifiable::<String>("ibc/ics27-account-owner/{address}")?
+        .verifiable::<Amount>("ibc/ics27-balance/{address}/{asset_id}")?
+        .verifiable::<ClientCounter>("ibc_client_counter")?
+        .verifiable::<u64>("ibc_channel_counter")?
+        // Implementation details of the Penumbra ICS2 implementation, outside the IBC namespace.
+        .verifiable::<VerifiedHeights>("penumbra_verified_heights/{client_id}/verified_heights")?
+        .verifiable::<TendermintConsensusState>("penumbra_consensus_states/{height}")?
+        // The paths defined by the IBC spec, under the IBC commitment prefix.
+        .verifiable::<String>("ibc-data/clients/{client_id}/clientType")?
+        .verifiable::<TendermintClientState>("ibc-data/clients/{client_id}/clientState")?
+        .verifiable::<TendermintConsensusState>(
+            "ibc-data/clients/{client_id}/consensusStates/{height}",
+        )?
+        .verifiable::<ClientPaths>("ibc-data/clients/{client_id}/connections")?
+        .verifiable::<ConnectionEnd>("ibc-data/connections/{connection_id}")?
+        .verifiable::<ChannelEnd>("ibc-data/channelEnds/ports/{port_id}/channels/{channel_id}")?
+        .verifiable::<[u8]>("ibc-data/nextSequenceSend/ports/{port_id}/channels/{channel_id}")?
+        .verifiable::<[u8]>("ibc-data/nextSequenceRecv/ports/{port_id}/channels/{channel_id}")?
+        .verifiable::<[u8]>("ibc-data/nextSequenceAck/ports/{port_id}/channels/{channel_id}")?
+        .verifiable::<[u8]>(
+            "ibc-data/commitments/ports/{port_id}/channels/{channel_id}/sequences/{sequence}",
+        )?
+        .verifiable::<[u8]>(
+            "ibc-data/receipts/ports/{port_id}/channels/{channel_id}/sequences/{sequence}",
+        )?
+        .verifiable::<[u8]>(
+            "ibc-data/acks/ports/{port_id}/channels/{channel_id}/sequences/{sequence}",
+        )?;
+    Ok(())
+}
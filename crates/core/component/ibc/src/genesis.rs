@@ -1,4 +1,8 @@
 use anyhow::Context;
+use ibc_types::lightclients::tendermint::{
+    client_state::ClientState as TendermintClientState,
+    consensus_state::ConsensusState as TendermintConsensusState,
+};
 use penumbra_proto::{penumbra::core::component::ibc::v1 as pb, DomainType};
 use serde::{Deserialize, Serialize};
 
@@ -9,12 +13,27 @@ use crate::params::IBCParameters;
 pub struct Content {
     /// The initial configuration parameters for the IBC component.
     pub ibc_params: IBCParameters,
+    /// Light clients to create at genesis, in order of client ID.
+    pub clients: Vec<GenesisClient>,
+}
+
+/// A light client of a counterparty chain, created at genesis.
+///
+/// This lets a devnet start out with a client of a known counterparty, rather
+/// than requiring a relayer to create it.
+#[derive(Debug, Clone)]
+pub struct GenesisClient {
+    /// The initial client state.
+    pub client_state: TendermintClientState,
+    /// The consensus state at the client state's latest height.
+    pub consensus_state: TendermintConsensusState,
 }
 
 impl From<Content> for pb::GenesisContent {
     fn from(value: Content) -> Self {
         pb::GenesisContent {
             ibc_params: Some(value.ibc_params.into()),
+            clients: value.clients.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -28,6 +47,11 @@ impl TryFrom<pb::GenesisContent> for Content {
                 .ibc_params
                 .context("ibc params not present in protobuf message")?
                 .try_into()?,
+            clients: msg
+                .clients
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
         })
     }
 }
@@ -35,3 +59,52 @@ impl TryFrom<pb::GenesisContent> for Content {
 impl DomainType for Content {
     type Proto = pb::GenesisContent;
 }
+
+impl From<GenesisClient> for pb::GenesisClient {
+    fn from(value: GenesisClient) -> Self {
+        let client_state: ibc_proto::google::protobuf::Any = value.client_state.into();
+        let consensus_state: ibc_proto::google::protobuf::Any = value.consensus_state.into();
+        pb::GenesisClient {
+            client_state: Some(pbjson_types::Any {
+                type_url: client_state.type_url,
+                value: client_state.value.into(),
+            }),
+            consensus_state: Some(pbjson_types::Any {
+                type_url: consensus_state.type_url,
+                value: consensus_state.value.into(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<pb::GenesisClient> for GenesisClient {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: pb::GenesisClient) -> Result<Self, Self::Error> {
+        let client_state = msg
+            .client_state
+            .context("client state not present in protobuf message")?;
+        let consensus_state = msg
+            .consensus_state
+            .context("consensus state not present in protobuf message")?;
+
+        Ok(GenesisClient {
+            client_state: ibc_proto::google::protobuf::Any {
+                type_url: client_state.type_url,
+                value: client_state.value.to_vec(),
+            }
+            .try_into()
+            .context("genesis client state must be a Tendermint client state")?,
+            consensus_state: ibc_proto::google::protobuf::Any {
+                type_url: consensus_state.type_url,
+                value: consensus_state.value.to_vec(),
+            }
+            .try_into()
+            .context("genesis consensus state must be a Tendermint consensus state")?,
+        })
+    }
+}
+
+impl DomainType for GenesisClient {
+    type Proto = pb::GenesisClient;
+}
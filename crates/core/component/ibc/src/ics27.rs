@@ -0,0 +1,250 @@
+//! Wire types for hosting ICS-27 interchain accounts.
+//!
+//! See: https://github.com/cosmos/ibc/tree/main/spec/app/ics-027-interchain-accounts
+use anyhow::{Context, Result};
+use base64::prelude::*;
+use ibc_types::core::{
+    channel::{PortId, Version},
+    connection::ConnectionId,
+};
+use serde::{Deserialize, Serialize};
+
+/// The port interchain account channels are opened on, on the host chain.
+pub const HOST_PORT_ID: &str = "icahost";
+/// The prefix of the ports interchain account channels are opened from, on the controller chain.
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+/// The ICS-27 application version.
+pub const VERSION: &str = "ics27-1";
+/// The only packet data encoding supported by the host.
+pub const ENCODING_PROTO3: &str = "proto3";
+/// The only transaction type supported by the host.
+pub const TX_TYPE_SDK_MULTI_MSG: &str = "sdk_multi_msg";
+
+/// Returns the interchain account address registered for the controller port
+/// `controller_port_id` on the host connection `host_connection_id`.
+///
+/// The account's holdings are kept in the clear by the host, under this address, rather than as
+/// notes: ICS-20 transfers to the address credit the account's balance.
+pub fn account_address(host_connection_id: &ConnectionId, controller_port_id: &PortId) -> String {
+    let hash = blake2b_simd::Params::new()
+        .personal(b"Penumbra_ICAAddr")
+        .to_state()
+        .update(host_connection_id.as_str().as_bytes())
+        .update(b"/")
+        .update(controller_port_id.as_str().as_bytes())
+        .finalize();
+    hex::encode(hash.as_bytes())
+}
+
+/// The channel version metadata negotiated during the interchain account channel handshake.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    pub version: String,
+    pub controller_connection_id: String,
+    pub host_connection_id: String,
+    #[serde(default)]
+    pub address: String,
+    pub encoding: String,
+    pub tx_type: String,
+}
+
+impl Metadata {
+    /// Checks that the controller proposed metadata the host supports, for a channel on the host
+    /// connection `host_connection_id`, whose counterparty is `controller_connection_id`.
+    pub fn validate_for_host(
+        &self,
+        host_connection_id: &ConnectionId,
+        controller_connection_id: &ConnectionId,
+    ) -> Result<()> {
+        if self.version != VERSION {
+            anyhow::bail!("interchain account version must be {VERSION}");
+        }
+        if self.encoding != ENCODING_PROTO3 {
+            anyhow::bail!("unsupported interchain account encoding {}", self.encoding);
+        }
+        if self.tx_type != TX_TYPE_SDK_MULTI_MSG {
+            anyhow::bail!("unsupported interchain account tx type {}", self.tx_type);
+        }
+        if self.host_connection_id != host_connection_id.as_str() {
+            anyhow::bail!(
+                "host connection id {} does not match channel connection {}",
+                self.host_connection_id,
+                host_connection_id
+            );
+        }
+        if self.controller_connection_id != controller_connection_id.as_str() {
+            anyhow::bail!(
+                "controller connection id {} does not match counterparty connection {}",
+                self.controller_connection_id,
+                controller_connection_id
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&Version> for Metadata {
+    type Error = anyhow::Error;
+
+    fn try_from(version: &Version) -> Result<Self> {
+        serde_json::from_str(&version.to_string())
+            .context("couldn't decode interchain account version metadata")
+    }
+}
+
+impl From<&Metadata> for Version {
+    fn from(metadata: &Metadata) -> Self {
+        Version::new(serde_json::to_string(metadata).expect("can serialize metadata"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PacketType {
+    #[serde(rename = "TYPE_UNSPECIFIED")]
+    Unspecified,
+    #[serde(rename = "TYPE_EXECUTE_TX")]
+    ExecuteTx,
+}
+
+/// The packet data sent by the controller chain to the host.
+///
+/// NOTE: like ICS-20 packet data, this is JSON on the wire, with the proto3-encoded transaction
+/// base64-encoded in `data`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketData {
+    #[serde(rename = "type")]
+    pub packet_type: PacketType,
+    #[serde(default)]
+    pub data: String,
+    #[serde(default)]
+    pub memo: String,
+}
+
+impl PacketData {
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).context("couldn't decode interchain account packet data")
+    }
+
+    /// Returns the messages of the transaction carried by an `ExecuteTx` packet.
+    pub fn messages(&self) -> Result<Vec<pbjson_types::Any>> {
+        if self.packet_type != PacketType::ExecuteTx {
+            anyhow::bail!("unsupported interchain account packet type");
+        }
+        let data = BASE64_STANDARD
+            .decode(&self.data)
+            .context("couldn't decode interchain account packet data")?;
+        let tx: CosmosTx = prost::Message::decode(data.as_slice())
+            .context("couldn't decode interchain account transaction")?;
+        if tx.messages.is_empty() {
+            anyhow::bail!("interchain account transaction has no messages");
+        }
+
+        Ok(tx.messages)
+    }
+}
+
+/// The transaction executed by the host on behalf of an interchain account.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CosmosTx {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<pbjson_types::Any>,
+}
+
+/// The acknowledgement the host writes for a received packet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Acknowledgement {
+    /// The base64-encoded result of executing the packet.
+    Result(String),
+    Error(String),
+}
+
+impl Acknowledgement {
+    /// Penumbra actions have no response messages, so like ICS-20, a successful execution is
+    /// acknowledged with a single `0x01` byte.
+    pub fn success() -> Self {
+        Self::Result(BASE64_STANDARD.encode([1u8]))
+    }
+}
+
+impl From<Acknowledgement> for Vec<u8> {
+    fn from(ack: Acknowledgement) -> Self {
+        serde_json::to_vec(&ack).expect("can serialize acknowledgement")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> Metadata {
+        Metadata {
+            version: VERSION.to_string(),
+            controller_connection_id: "connection-7".to_string(),
+            host_connection_id: "connection-0".to_string(),
+            address: String::new(),
+            encoding: ENCODING_PROTO3.to_string(),
+            tx_type: TX_TYPE_SDK_MULTI_MSG.to_string(),
+        }
+    }
+
+    #[test]
+    fn metadata_is_checked_against_channel_connections() {
+        let host = ConnectionId::new(0);
+        let controller = ConnectionId::new(7);
+
+        // Controllers usually leave out the address when opening a new account.
+        let version = Version::new(
+            r#"{"version":"ics27-1","controller_connection_id":"connection-7","host_connection_id":"connection-0","encoding":"proto3","tx_type":"sdk_multi_msg"}"#
+                .to_string(),
+        );
+        let decoded = Metadata::try_from(&version).expect("valid metadata");
+        assert_eq!(decoded, metadata());
+        decoded
+            .validate_for_host(&host, &controller)
+            .expect("metadata matches connections");
+
+        assert!(decoded.validate_for_host(&controller, &host).is_err());
+        assert!(Metadata {
+            encoding: "proto3json".to_string(),
+            ..metadata()
+        }
+        .validate_for_host(&host, &controller)
+        .is_err());
+    }
+
+    #[test]
+    fn execute_tx_packet_data_decodes_messages() {
+        let message = pbjson_types::Any {
+            type_url: "/penumbra.core.component.stake.v1.Delegate".to_string(),
+            value: vec![1, 2, 3].into(),
+        };
+        let tx = CosmosTx {
+            messages: vec![message.clone()],
+        };
+        let packet_data = format!(
+            r#"{{"type":"TYPE_EXECUTE_TX","data":"{}","memo":""}}"#,
+            BASE64_STANDARD.encode(prost::Message::encode_to_vec(&tx))
+        );
+
+        let decoded = PacketData::decode(packet_data.as_bytes()).expect("valid packet data");
+        assert_eq!(decoded.messages().expect("valid tx"), vec![message]);
+
+        let empty = PacketData {
+            packet_type: PacketType::ExecuteTx,
+            data: String::new(),
+            memo: String::new(),
+        };
+        assert!(empty.messages().is_err());
+    }
+
+    #[test]
+    fn acknowledgements_use_ibc_go_json_format() {
+        let success: Vec<u8> = Acknowledgement::success().into();
+        assert_eq!(success, br#"{"result":"AQ=="}"#.to_vec());
+
+        let error: Vec<u8> = Acknowledgement::Error("denied".to_string()).into();
+        assert_eq!(error, br#"{"error":"denied"}"#.to_vec());
+    }
+}
@@ -14,6 +14,7 @@ pub use component::ibc_action_with_handler::IbcRelayWithHandlers;
 pub mod genesis;
 mod ibc_action;
 mod ibc_token;
+pub mod ics27;
 pub mod params;
 mod version;
 
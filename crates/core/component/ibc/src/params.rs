@@ -11,6 +11,8 @@ pub struct IBCParameters {
     pub inbound_ics20_transfers_enabled: bool,
     /// Whether outbound ICS-20 transfers are enabled
     pub outbound_ics20_transfers_enabled: bool,
+    /// Whether hosting ICS-27 interchain accounts is enabled
+    pub ics27_host_enabled: bool,
 }
 
 impl DomainType for IBCParameters {
@@ -25,6 +27,7 @@ impl TryFrom<pb::IbcParameters> for IBCParameters {
             ibc_enabled: msg.ibc_enabled,
             inbound_ics20_transfers_enabled: msg.inbound_ics20_transfers_enabled,
             outbound_ics20_transfers_enabled: msg.outbound_ics20_transfers_enabled,
+            ics27_host_enabled: msg.ics27_host_enabled,
         })
     }
 }
@@ -35,6 +38,7 @@ impl From<IBCParameters> for pb::IbcParameters {
             ibc_enabled: params.ibc_enabled,
             inbound_ics20_transfers_enabled: params.inbound_ics20_transfers_enabled,
             outbound_ics20_transfers_enabled: params.outbound_ics20_transfers_enabled,
+            ics27_host_enabled: params.ics27_host_enabled,
         }
     }
 }
@@ -45,6 +49,7 @@ impl Default for IBCParameters {
             ibc_enabled: true,
             inbound_ics20_transfers_enabled: true,
             outbound_ics20_transfers_enabled: true,
+            ics27_host_enabled: false,
         }
     }
 }
@@ -73,3 +73,21 @@ pub mod ambient {
         "sct/ambient/current_source"
     }
 }
+
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    schema
+        .component("sct")
+        .verifiable::<crate::params::SctParameters>(config::sct_params())?
+        .verifiable::<u64>(block_manager::block_height())?
+        .verifiable::<String>(block_manager::block_timestamp())?
+        .verifiable::<crate::epoch::Epoch>("sct/epoch_manager/epoch_by_height/{height}")?
+        .verifiable::<u64>("sct/tree/anchor_lookup/{anchor}")?
+        .verifiable::<penumbra_tct::Root>("sct/tree/anchor_by_height/{height}")?
+        .verifiable::<crate::NullificationInfo>(
+            "sct/nullifier_set/spent_nullifier_lookup/{nullifier}",
+        )?
+        .nonverifiable::<Vec<u8>>(tree::state_commitment_tree())?;
+    Ok(())
+}
@@ -6,7 +6,7 @@ use anyhow::anyhow;
 use anyhow::Result;
 use async_trait::async_trait;
 use cnidarium::{StateRead, StateWrite};
-use cnidarium_component::Component;
+use cnidarium_component::{Component, EpochHandler};
 use penumbra_proto::StateReadProto as _;
 use penumbra_proto::StateWriteProto as _;
 use penumbra_sct::CommitmentSource;
@@ -73,6 +73,10 @@ impl Component for ShieldedPool {
         Ok(())
     }
 }
+
+impl EpochHandler for ShieldedPool {
+    const NAME: &'static str = "shielded_pool";
+}
 /// Extension trait providing read access to shielded pool data.
 #[async_trait]
 pub trait StateReadExt: StateRead {
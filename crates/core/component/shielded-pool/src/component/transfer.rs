@@ -32,7 +32,7 @@ use penumbra_ibc::component::{
     packet::{
         IBCPacket, SendPacketRead as _, SendPacketWrite as _, Unchecked, WriteAcknowledgement as _,
     },
-    state_key,
+    state_key, InterchainAccountStateReadExt as _, InterchainAccountStateWriteExt as _,
 };
 
 // returns a bool indicating if the provided denom was issued locally or if it was bridged in.
@@ -245,6 +245,36 @@ impl AppHandlerCheck for Ics20Transfer {
     }
 }
 
+/// The recipient of an inbound ICS-20 transfer.
+enum Receiver {
+    /// A Penumbra address, which receives the transfer as a newly minted note.
+    Address(Address),
+    /// An interchain account hosted on Penumbra, whose balance is credited with the transfer.
+    InterchainAccount(String),
+}
+
+impl Receiver {
+    async fn parse<S: StateRead>(state: S, receiver: &str) -> Result<Self> {
+        if state.is_ica_account(receiver).await? {
+            Ok(Receiver::InterchainAccount(receiver.to_string()))
+        } else {
+            Ok(Receiver::Address(Address::from_str(receiver)?))
+        }
+    }
+
+    async fn credit<S: StateWrite>(
+        &self,
+        mut state: S,
+        value: Value,
+        source: CommitmentSource,
+    ) -> Result<()> {
+        match self {
+            Receiver::Address(address) => state.mint_note(value, address, source).await,
+            Receiver::InterchainAccount(account) => state.credit_ica_balance(account, value).await,
+        }
+    }
+}
+
 // the main entry point for ICS20 transfer packet handling
 async fn recv_transfer_packet_inner<S: StateWrite>(
     mut state: S,
@@ -268,7 +298,7 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
         .amount
         .try_into()
         .context("couldnt decode amount in ICS20 transfer")?;
-    let receiver_address = Address::from_str(&packet_data.receiver)?;
+    let receiver = Receiver::parse(&state, &packet_data.receiver).await?;
 
     // NOTE: here we assume we are chain A.
 
@@ -312,10 +342,10 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             anyhow::bail!("transfer coins failed");
         }
 
-        state
-            .mint_note(
+        receiver
+            .credit(
+                &mut state,
                 value,
-                &receiver_address,
                 CommitmentSource::Ics20Transfer {
                     packet_seq: msg.packet.sequence.0,
                     // We are chain A
@@ -366,10 +396,10 @@ async fn recv_transfer_packet_inner<S: StateWrite>(
             asset_id: denom.id(),
         };
 
-        state
-            .mint_note(
+        receiver
+            .credit(
+                &mut state,
                 value,
-                &receiver_address,
                 CommitmentSource::Ics20Transfer {
                     packet_seq: msg.packet.sequence.0,
                     // We are chain A
@@ -17,7 +17,9 @@ use penumbra_num::{
     fixpoint::{U128x128, U128x128Var},
     Amount, AmountVar,
 };
-use penumbra_proof_params::{DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES};
+use penumbra_proof_params::{
+    create_proof, DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES,
+};
 
 /// The public input for a [`ConvertProof`].
 #[derive(Clone, Debug)]
@@ -203,9 +205,7 @@ impl ConvertProof {
         private: ConvertProofPrivate,
     ) -> Result<Self> {
         let circuit = ConvertCircuit::new(public, private);
-        let proof = Groth16::<Bls12_377, LibsnarkReduction>::create_proof_with_reduction(
-            circuit, pk, blinding_r, blinding_s,
-        )?;
+        let proof = create_proof(circuit, pk, blinding_r, blinding_s)?;
         let mut proof_bytes = [0u8; GROTH16_PROOF_LENGTH_BYTES];
         Proof::serialize_compressed(&proof, &mut proof_bytes[..]).expect("can serialize Proof");
         Ok(Self(proof_bytes))
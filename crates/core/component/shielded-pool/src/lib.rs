@@ -32,5 +32,6 @@ pub use nullifier_derivation::{
 };
 pub use output::{Output, OutputCircuit, OutputPlan, OutputProof, OutputView};
 pub use spend::{
-    Spend, SpendCircuit, SpendPlan, SpendProof, SpendProofPrivate, SpendProofPublic, SpendView,
+    Spend, SpendCircuit, SpendPlan, SpendProof, SpendProofPrivate, SpendProofPublic,
+    SpendProofPublicInputs, SpendView,
 };
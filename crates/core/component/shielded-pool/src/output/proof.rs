@@ -26,7 +26,9 @@ use penumbra_asset::{
     balance::{commitment::BalanceCommitmentVar, BalanceVar},
     Value,
 };
-use penumbra_proof_params::{DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES};
+use penumbra_proof_params::{
+    create_proof, DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES,
+};
 
 /// The public input for an [`OutputProof`].
 #[derive(Clone, Debug)]
@@ -188,10 +190,7 @@ impl OutputProof {
         private: OutputProofPrivate,
     ) -> anyhow::Result<Self> {
         let circuit = OutputCircuit::new(public, private);
-        let proof = Groth16::<Bls12_377, LibsnarkReduction>::create_proof_with_reduction(
-            circuit, pk, blinding_r, blinding_s,
-        )
-        .map_err(|err| anyhow::anyhow!(err))?;
+        let proof = create_proof(circuit, pk, blinding_r, blinding_s)?;
         let mut proof_bytes = [0u8; GROTH16_PROOF_LENGTH_BYTES];
         Proof::serialize_compressed(&proof, &mut proof_bytes[..]).expect("can serialize Proof");
         Ok(Self(proof_bytes))
@@ -5,5 +5,7 @@ mod view;
 
 pub use action::{Body, Spend};
 pub use plan::SpendPlan;
-pub use proof::{SpendCircuit, SpendProof, SpendProofPrivate, SpendProofPublic};
+pub use proof::{
+    SpendCircuit, SpendProof, SpendProofPrivate, SpendProofPublic, SpendProofPublicInputs,
+};
 pub use view::SpendView;
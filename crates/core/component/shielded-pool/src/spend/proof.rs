@@ -34,7 +34,9 @@ use penumbra_keys::keys::{
     AuthorizationKeyVar, Bip44Path, IncomingViewingKeyVar, NullifierKey, NullifierKeyVar,
     RandomizedVerificationKey, SeedPhrase, SpendAuthRandomizerVar, SpendKey,
 };
-use penumbra_proof_params::{DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES};
+use penumbra_proof_params::{
+    create_proof, DummyWitness, VerifyingKeyExt, GROTH16_PROOF_LENGTH_BYTES,
+};
 use penumbra_sct::{Nullifier, NullifierVar};
 use tap::Tap;
 
@@ -51,6 +53,84 @@ pub struct SpendProofPublic {
     pub rk: VerificationKey<SpendAuth>,
 }
 
+/// The public inputs of a [`SpendProof`], as seen by the proof system.
+///
+/// The public inputs must be given to the proof system in the same order when proving and when
+/// verifying: anchor, balance commitment, nullifier, and randomized verification key.  This is
+/// the only place that order is defined: the [`SpendCircuit`] allocates the public inputs through
+/// this type, and [`SpendProof::verify`] uses [`SpendProofPublicInputs::to_field_elements`].
+#[derive(Clone, Debug)]
+pub struct SpendProofPublicInputs(SpendProofPublic);
+
+/// The public inputs of a [`SpendProof`], allocated in a constraint system.
+struct SpendProofPublicInputVars {
+    anchor: FqVar,
+    balance_commitment: BalanceCommitmentVar,
+    nullifier: NullifierVar,
+    rk: RandomizedVerificationKey,
+}
+
+impl From<SpendProofPublic> for SpendProofPublicInputs {
+    fn from(public: SpendProofPublic) -> Self {
+        Self(public)
+    }
+}
+
+impl SpendProofPublicInputs {
+    /// Encodes the public inputs as field elements, in the order expected by the circuit.
+    pub fn to_field_elements(&self) -> Result<Vec<Fq>, VerificationError> {
+        let SpendProofPublic {
+            anchor: Root(anchor),
+            balance_commitment: Commitment(balance_commitment),
+            nullifier: Nullifier(nullifier),
+            rk,
+        } = &self.0;
+        let element_rk = decaf377::Encoding(rk.to_bytes())
+            .vartime_decompress()
+            .map_err(VerificationError::DecompressRk)?;
+
+        /// Shorthand helper, convert expressions into field elements.
+        macro_rules! to_field_elements {
+            ($fe:expr, $err:expr) => {
+                $fe.to_field_elements().ok_or($err)?
+            };
+        }
+
+        use VerificationError::*;
+        Ok([
+            to_field_elements!(Fq::from(*anchor), Anchor),
+            to_field_elements!(balance_commitment, BalanceCommitment),
+            to_field_elements!(nullifier, Nullifier),
+            to_field_elements!(element_rk, Rk),
+        ]
+        .into_iter()
+        .flatten()
+        .collect())
+    }
+
+    /// Allocates the public inputs in the constraint system, in the same order as
+    /// [`SpendProofPublicInputs::to_field_elements`].
+    fn allocate(
+        self,
+        cs: ConstraintSystemRef<Fq>,
+    ) -> ark_relations::r1cs::Result<SpendProofPublicInputVars> {
+        let SpendProofPublic {
+            anchor,
+            balance_commitment,
+            nullifier,
+            rk,
+        } = self.0;
+        Ok(SpendProofPublicInputVars {
+            anchor: FqVar::new_input(cs.clone(), || Ok(Fq::from(anchor)))?,
+            balance_commitment: BalanceCommitmentVar::new_input(cs.clone(), || {
+                Ok(balance_commitment)
+            })?,
+            nullifier: NullifierVar::new_input(cs.clone(), || Ok(nullifier))?,
+            rk: RandomizedVerificationKey::new_input(cs, || Ok(rk))?,
+        })
+    }
+}
+
 /// The private input for a [`SpendProof`].
 #[derive(Clone, Debug)]
 pub struct SpendProofPrivate {
@@ -169,12 +249,12 @@ impl ConstraintSynthesizer<Fq> for SpendCircuit {
         let nk_var = NullifierKeyVar::new_witness(cs.clone(), || Ok(self.private.nk))?;
 
         // Public inputs
-        let anchor_var = FqVar::new_input(cs.clone(), || Ok(Fq::from(self.public.anchor)))?;
-        let claimed_balance_commitment_var =
-            BalanceCommitmentVar::new_input(cs.clone(), || Ok(self.public.balance_commitment))?;
-        let claimed_nullifier_var =
-            NullifierVar::new_input(cs.clone(), || Ok(self.public.nullifier))?;
-        let rk_var = RandomizedVerificationKey::new_input(cs.clone(), || Ok(self.public.rk))?;
+        let SpendProofPublicInputVars {
+            anchor: anchor_var,
+            balance_commitment: claimed_balance_commitment_var,
+            nullifier: claimed_nullifier_var,
+            rk: rk_var,
+        } = SpendProofPublicInputs::from(self.public).allocate(cs.clone())?;
 
         // Note commitment integrity.
         let note_commitment_var = note_var.commit()?;
@@ -306,10 +386,7 @@ impl SpendProof {
         private: SpendProofPrivate,
     ) -> anyhow::Result<Self> {
         let circuit = SpendCircuit { public, private };
-        let proof = Groth16::<Bls12_377, LibsnarkReduction>::create_proof_with_reduction(
-            circuit, pk, blinding_r, blinding_s,
-        )
-        .map_err(|err| anyhow::anyhow!(err))?;
+        let proof = create_proof(circuit, pk, blinding_r, blinding_s)?;
         let mut proof_bytes = [0u8; GROTH16_PROOF_LENGTH_BYTES];
         Proof::serialize_compressed(&proof, &mut proof_bytes[..]).expect("can serialize Proof");
         Ok(Self(proof_bytes))
@@ -322,37 +399,13 @@ impl SpendProof {
     pub fn verify(
         &self,
         vk: &PreparedVerifyingKey<Bls12_377>,
-        SpendProofPublic {
-            anchor: Root(anchor),
-            balance_commitment: Commitment(balance_commitment),
-            nullifier: Nullifier(nullifier),
-            rk,
-        }: SpendProofPublic,
+        public: SpendProofPublic,
     ) -> Result<(), VerificationError> {
         let proof = Proof::deserialize_compressed_unchecked(&self.0[..])
             .map_err(VerificationError::ProofDeserialize)?;
-        let element_rk = decaf377::Encoding(rk.to_bytes())
-            .vartime_decompress()
-            .map_err(VerificationError::DecompressRk)?;
-
-        /// Shorthand helper, convert expressions into field elements.
-        macro_rules! to_field_elements {
-            ($fe:expr, $err:expr) => {
-                $fe.to_field_elements().ok_or($err)?
-            };
-        }
-
-        use VerificationError::*;
-        let public_inputs = [
-            to_field_elements!(Fq::from(anchor), Anchor),
-            to_field_elements!(balance_commitment, BalanceCommitment),
-            to_field_elements!(nullifier, Nullifier),
-            to_field_elements!(element_rk, Rk),
-        ]
-        .into_iter()
-        .flatten()
-        .collect::<Vec<_>>()
-        .tap(|public_inputs| tracing::trace!(?public_inputs));
+        let public_inputs = SpendProofPublicInputs::from(public)
+            .to_field_elements()?
+            .tap(|public_inputs| tracing::trace!(?public_inputs));
 
         let start = std::time::Instant::now();
         Groth16::<Bls12_377, LibsnarkReduction>::verify_with_processed_vk(
@@ -901,6 +954,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn public_inputs_are_allocated_in_canonical_order() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let circuit = SpendCircuit::with_dummy_witness();
+        let public_inputs = SpendProofPublicInputs::from(circuit.public.clone())
+            .to_field_elements()
+            .expect("can encode public inputs");
+
+        let cs = ConstraintSystem::new_ref();
+        circuit
+            .generate_constraints(cs.clone())
+            .expect("can generate constraints from circuit");
+
+        // The first instance variable is always the constant one.
+        let cs = cs.borrow().expect("constraint system is not shared");
+        assert_eq!(cs.instance_assignment[1..], public_inputs[..]);
+    }
+
     struct MerkleProofCircuit {
         /// Witness: Inclusion proof for the note commitment.
         state_commitment_proof: tct::Proof,
@@ -22,3 +22,17 @@ pub fn shielded_pool_params() -> &'static str {
 pub fn shielded_pool_params_updated() -> &'static str {
     "shielded_pool/params_updated"
 }
+
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    use crate::fmd;
+
+    schema
+        .component("shielded_pool")
+        .verifiable::<asset::Metadata>("shielded_pool/assets/{asset_id}/denom")?
+        .verifiable::<crate::params::ShieldedPoolParameters>(shielded_pool_params())?
+        .verifiable::<fmd::Parameters>(fmd::state_key::parameters::current())?
+        .verifiable::<fmd::Parameters>(fmd::state_key::parameters::previous())?;
+    Ok(())
+}
@@ -0,0 +1,475 @@
+//! Stateful property tests for the shielded pool component.
+//!
+//! These tests generate random traces of operations against the shielded pool
+//! (outputs, valid and invalid spends, rolled-back transactions, ending blocks
+//! and claiming anchors), execute them against a real [`cnidarium`] state
+//! through the `Spend` and `Output` [`ActionHandler`]s, and check after every
+//! step that the chain state agrees with a simple reference model.  Because
+//! traces are plain `Vec<Op>`s, proptest shrinks failures down to a minimal
+//! sequence of operations.
+//!
+//! Each action carries a real proof, so traces are kept short.  The checks
+//! exercised are:
+//!
+//! - value conservation: the notes recorded by the chain are exactly the
+//!   notes created by valid outputs, and the value of the notes the chain
+//!   considers unspent is exactly the value the model considers unspent;
+//! - nullifier uniqueness: a nullifier can be revealed at most once;
+//! - anchor validity: every SCT root produced at the end of a block remains a
+//!   valid anchor, and spends against roots that were never produced, against
+//!   the wrong root, with the wrong value balance, or of notes that were never
+//!   created are all rejected, without affecting the state.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use ark_ff::UniformRand;
+use cnidarium::{ArcStateDeltaExt, StateDelta, StateWrite, TempStorage};
+use cnidarium_component::ActionHandler;
+use decaf377::Fr;
+use penumbra_asset::{asset, Value};
+use penumbra_keys::{keys::OutgoingViewingKey, test_keys, PayloadKey};
+use penumbra_num::Amount;
+use penumbra_sct::{
+    component::{
+        clock::EpochManager,
+        source::SourceContext,
+        tree::{SctManager, SctRead, VerificationExt},
+    },
+    epoch::Epoch,
+    Nullifier,
+};
+use penumbra_shielded_pool::{component::NoteManager, Note, OutputPlan, Spend, SpendPlan};
+use penumbra_tct as tct;
+use penumbra_txhash::{EffectHash, TransactionContext};
+use proptest::{prelude::*, test_runner::TestCaseError};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The asset denominations notes are created in.
+const DENOMS: [&str; 2] = ["gm", "gn"];
+
+/// The ways a spend can be invalid, besides double-spending its note.
+#[derive(Clone, Copy, Debug)]
+enum Fault {
+    /// The transaction claims an anchor the proof wasn't made against.
+    WrongAnchor,
+    /// The balance commitment doesn't match the spent note's value.
+    WrongValueBalance,
+    /// The note was never created on chain, so the proof is made against a
+    /// tree the chain never produced.
+    UnknownNote,
+}
+
+/// A single step of a trace.
+#[derive(Clone, Debug)]
+enum Op {
+    /// Create a new note of the given value with an `Output` action.
+    Output { denom: usize, amount: u64 },
+    /// Spend the `index`th note created so far (modulo the number of notes),
+    /// against the latest anchor.
+    ///
+    /// This is invalid, and must be rejected, if the note was already spent or
+    /// if the spend has a `fault`.
+    Spend { index: usize, fault: Option<Fault> },
+    /// Execute a transaction containing the given outputs and spends, then
+    /// discard it instead of applying it.
+    Rollback {
+        outputs: Vec<(usize, u64)>,
+        spends: Vec<usize>,
+    },
+    /// Seal the current block in the SCT, producing a new anchor.
+    EndBlock,
+    /// Claim the `index`th anchor produced so far (modulo the number of
+    /// anchors), which must be accepted.
+    ClaimAnchor { index: usize },
+    /// Claim an anchor that was never produced, which must be rejected.
+    ClaimBogusAnchor { seed: u64 },
+}
+
+fn fault() -> impl Strategy<Value = Option<Fault>> {
+    prop_oneof![
+        3 => Just(None),
+        1 => Just(Some(Fault::WrongAnchor)),
+        1 => Just(Some(Fault::WrongValueBalance)),
+        1 => Just(Some(Fault::UnknownNote)),
+    ]
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (0..DENOMS.len(), 0..1_000_000u64)
+            .prop_map(|(denom, amount)| Op::Output { denom, amount }),
+        4 => (any::<usize>(), fault()).prop_map(|(index, fault)| Op::Spend { index, fault }),
+        1 => (
+            prop::collection::vec((0..DENOMS.len(), 0..1_000_000u64), 0..2),
+            prop::collection::vec(any::<usize>(), 0..2),
+        )
+            .prop_map(|(outputs, spends)| Op::Rollback { outputs, spends }),
+        3 => Just(Op::EndBlock),
+        1 => any::<usize>().prop_map(|index| Op::ClaimAnchor { index }),
+        1 => any::<u64>().prop_map(|seed| Op::ClaimBogusAnchor { seed }),
+    ]
+}
+
+/// A note created during the trace, as tracked by the model.
+#[derive(Clone, Debug)]
+struct ModelNote {
+    note: Note,
+    nullifier: Nullifier,
+    spent: bool,
+}
+
+/// The reference model of the shielded pool.
+#[derive(Debug)]
+struct Model {
+    notes: Vec<ModelNote>,
+    /// A mirror of the chain's state commitment tree.
+    sct: tct::Tree,
+    /// The anchors produced so far, with the tree they are the root of.
+    anchors: Vec<(u64, tct::Tree)>,
+    height: u64,
+    /// The total value ever created, per asset.
+    created: BTreeMap<asset::Id, Amount>,
+    rng: StdRng,
+}
+
+impl Model {
+    fn new(seed: u64) -> Self {
+        Self {
+            notes: Vec::new(),
+            sct: tct::Tree::new(),
+            anchors: Vec::new(),
+            height: 1,
+            created: BTreeMap::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn value(denom: usize, amount: u64) -> Value {
+        let asset_id = asset::Cache::with_known_assets()
+            .get_unit(DENOMS[denom])
+            .expect("denom is known")
+            .id();
+        Value {
+            amount: amount.into(),
+            asset_id,
+        }
+    }
+
+    /// Builds an `Output` action creating a note of the given value.
+    fn output(&mut self, denom: usize, amount: u64) -> (penumbra_shielded_pool::Output, Note) {
+        let plan = OutputPlan::new(
+            &mut self.rng,
+            Self::value(denom, amount),
+            *test_keys::ADDRESS_0,
+        );
+        let ovk: &OutgoingViewingKey = test_keys::FULL_VIEWING_KEY.outgoing();
+        let output = plan.output(ovk, &PayloadKey::random_key(&mut self.rng));
+        (output, plan.output_note())
+    }
+
+    /// Builds a `Spend` action spending `note`, which is witnessed in `tree`,
+    /// along with the context of the transaction containing it.
+    fn spend(&mut self, note: &Note, tree: &tct::Tree) -> (Spend, TransactionContext) {
+        let auth_path = tree.witness(note.commit()).expect("note is witnessed");
+        let anchor = tree.root();
+        let plan = SpendPlan::new(&mut self.rng, note.clone(), auth_path.position());
+        let effect_hash = EffectHash([0u8; 64]);
+        let rsk = test_keys::SPEND_KEY
+            .spend_auth_key()
+            .randomize(&plan.randomizer);
+        let auth_sig = rsk.sign(&mut self.rng, effect_hash.as_ref());
+        let spend = plan.spend(&test_keys::FULL_VIEWING_KEY, auth_sig, auth_path, anchor);
+        (
+            spend,
+            TransactionContext {
+                anchor,
+                effect_hash,
+            },
+        )
+    }
+
+    /// The value of the notes the model considers (un)spent, per asset.
+    fn balance(&self, spent: bool) -> BTreeMap<asset::Id, Amount> {
+        let mut balance = BTreeMap::<asset::Id, Amount>::new();
+        for note in self.notes.iter().filter(|n| n.spent == spent) {
+            *balance.entry(note.note.asset_id()).or_default() += note.note.amount();
+        }
+        balance
+    }
+}
+
+/// Builds a tree no real block could have produced, containing only a
+/// commitment that was never created by the trace.
+fn bogus_tree(commitment: tct::StateCommitment) -> tct::Tree {
+    let mut bogus = tct::Tree::new();
+    bogus
+        .insert(tct::Witness::Keep, commitment)
+        .expect("can insert into empty tree");
+    bogus.end_block().expect("can end block");
+    bogus
+}
+
+/// Executes an `Output` action as part of a transaction.
+async fn execute_output<S: StateWrite>(
+    state: &mut S,
+    output: &penumbra_shielded_pool::Output,
+) -> anyhow::Result<()> {
+    output.check_stateless(()).await?;
+    output.check_and_execute(&mut *state).await
+}
+
+/// Executes a `Spend` action as part of a transaction, including the
+/// transaction-level check that its anchor was produced by the chain.
+async fn execute_spend<S: StateWrite>(
+    state: &mut S,
+    spend: &Spend,
+    context: TransactionContext,
+) -> anyhow::Result<()> {
+    let anchor = context.anchor;
+    spend.check_stateless(context).await?;
+    state.check_claimed_anchor(anchor).await?;
+    spend.check_and_execute(&mut *state).await
+}
+
+fn fail(e: anyhow::Error) -> TestCaseError {
+    TestCaseError::fail(format!("{e:#}"))
+}
+
+/// Checks that the chain state agrees with the model.
+async fn check_invariants(
+    state: &StateDelta<cnidarium::Snapshot>,
+    model: &Model,
+) -> Result<(), TestCaseError> {
+    // The chain's state commitment tree has the same root as the model's, so
+    // it contains exactly the notes created by valid outputs, in order.
+    prop_assert_eq!(state.get_sct().await.root(), model.sct.root());
+
+    // Nullifier uniqueness: exactly the spent notes' nullifiers are recorded
+    // as spent.
+    for note in &model.notes {
+        let spend_info = state.spend_info(note.nullifier).await.map_err(fail)?;
+        prop_assert_eq!(spend_info.is_some(), note.spent);
+    }
+
+    // Value conservation: decrypting the notes the chain recorded, the value
+    // the chain considers created and unspent matches the model.
+    let fvk = &*test_keys::FULL_VIEWING_KEY;
+    let mut created = BTreeMap::<asset::Id, Amount>::new();
+    let mut unspent = BTreeMap::<asset::Id, Amount>::new();
+    for (position, payload, _) in state.pending_note_payloads() {
+        let note = payload
+            .trial_decrypt(fvk)
+            .ok_or_else(|| TestCaseError::fail("recorded note is not decryptable"))?;
+        *created.entry(note.asset_id()).or_default() += note.amount();
+        let nullifier = Nullifier::derive(fvk.nullifier_key(), position, &note.commit());
+        if state.spend_info(nullifier).await.map_err(fail)?.is_none() {
+            *unspent.entry(note.asset_id()).or_default() += note.amount();
+        }
+    }
+    let nonzero = |mut balance: BTreeMap<asset::Id, Amount>| {
+        balance.retain(|_, amount| *amount != Amount::zero());
+        balance
+    };
+    prop_assert_eq!(nonzero(created), nonzero(model.created.clone()));
+    prop_assert_eq!(nonzero(unspent), nonzero(model.balance(false)));
+
+    // Anchor validity: every anchor produced so far is recorded at its height
+    // and is still accepted.
+    for (height, tree) in &model.anchors {
+        let recorded = state.get_anchor_by_height(*height).await.map_err(fail)?;
+        prop_assert_eq!(recorded, Some(tree.root()));
+        prop_assert!(state.check_claimed_anchor(tree.root()).await.is_ok());
+    }
+
+    Ok(())
+}
+
+async fn run_trace(seed: u64, ops: Vec<Op>) -> Result<(), TestCaseError> {
+    let storage = TempStorage::new().await.map_err(fail)?;
+    let mut state = Arc::new(StateDelta::new(storage.latest_snapshot()));
+    let mut model = Model::new(seed);
+
+    {
+        let mut state_tx = state.try_begin_transaction().expect("state is unique");
+        state_tx.put_block_height(model.height);
+        state_tx.put_epoch_by_height(
+            model.height,
+            Epoch {
+                index: 0,
+                start_height: 0,
+            },
+        );
+        state_tx.apply();
+    }
+
+    for (counter, op) in ops.into_iter().enumerate() {
+        let mut state_tx = state.try_begin_transaction().expect("state is unique");
+        // Give each transaction a distinct source, so spends are attributable.
+        state_tx.put_mock_source(counter as u8);
+
+        match op {
+            Op::Output { denom, amount } => {
+                let (output, note) = model.output(denom, amount);
+                let result = execute_output(&mut state_tx, &output).await;
+                prop_assert!(result.is_ok(), "valid output rejected: {:?}", result);
+                state_tx.apply();
+
+                let position = model
+                    .sct
+                    .insert(tct::Witness::Keep, note.commit())
+                    .expect("tree is not full");
+                *model.created.entry(note.asset_id()).or_default() += note.amount();
+                model.notes.push(ModelNote {
+                    nullifier: Nullifier::derive(
+                        test_keys::FULL_VIEWING_KEY.nullifier_key(),
+                        position,
+                        &note.commit(),
+                    ),
+                    note,
+                    spent: false,
+                });
+            }
+            Op::Spend { index, fault } => {
+                // Spends are made against the latest anchor, so only notes
+                // created before the last block ended can be spent.
+                let Some((_, tree)) = model.anchors.last().cloned() else {
+                    continue;
+                };
+                if model.notes.is_empty() {
+                    continue;
+                }
+                let index = index % model.notes.len();
+                let note = model.notes[index].note.clone();
+                if tree.witness(note.commit()).is_none() {
+                    continue;
+                }
+
+                let (spend, context) = match fault {
+                    None => model.spend(&note, &tree),
+                    Some(Fault::WrongAnchor) => {
+                        // Claim an anchor the chain did produce, but not the
+                        // one the proof was made against.
+                        let (spend, mut context) = model.spend(&note, &tree);
+                        let Some((_, other)) =
+                            model.anchors.iter().find(|(_, t)| t.root() != tree.root())
+                        else {
+                            continue;
+                        };
+                        context.anchor = other.root();
+                        (spend, context)
+                    }
+                    Some(Fault::WrongValueBalance) => {
+                        let (mut spend, context) = model.spend(&note, &tree);
+                        let extra = Value {
+                            amount: 1u64.into(),
+                            asset_id: note.asset_id(),
+                        }
+                        .commit(Fr::rand(&mut model.rng));
+                        spend.body.balance_commitment = spend.body.balance_commitment + extra;
+                        (spend, context)
+                    }
+                    Some(Fault::UnknownNote) => {
+                        let unknown = OutputPlan::new(
+                            &mut model.rng,
+                            Model::value(0, 1),
+                            *test_keys::ADDRESS_0,
+                        )
+                        .output_note();
+                        let tree = bogus_tree(unknown.commit());
+                        model.spend(&unknown, &tree)
+                    }
+                };
+
+                let result = execute_spend(&mut state_tx, &spend, context).await;
+                let spent = model.notes[index].spent;
+                if spent || fault.is_some() {
+                    prop_assert!(
+                        result.is_err(),
+                        "invalid spend of note {} accepted (spent: {}, fault: {:?})",
+                        index,
+                        spent,
+                        fault
+                    );
+                    // Failed transactions are never applied.
+                    drop(state_tx);
+                } else {
+                    prop_assert!(result.is_ok(), "valid spend rejected: {:?}", result);
+                    model.notes[index].spent = true;
+                    state_tx.apply();
+                }
+            }
+            Op::Rollback { outputs, spends } => {
+                // Execute the transaction without recording it in the model,
+                // then discard it: the invariants check that the state didn't
+                // observe any of its effects either.
+                for (denom, amount) in outputs {
+                    let (output, _) = model.output(denom, amount);
+                    let _ = execute_output(&mut state_tx, &output).await;
+                }
+                if let Some((_, tree)) = model.anchors.last().cloned() {
+                    for index in spends {
+                        if model.notes.is_empty() {
+                            break;
+                        }
+                        let note = model.notes[index % model.notes.len()].note.clone();
+                        if tree.witness(note.commit()).is_none() {
+                            continue;
+                        }
+                        let (spend, context) = model.spend(&note, &tree);
+                        let _ = execute_spend(&mut state_tx, &spend, context).await;
+                    }
+                }
+                drop(state_tx);
+            }
+            Op::EndBlock => {
+                state_tx.end_sct_block(false).await.map_err(fail)?;
+                model.sct.end_block().expect("can end block");
+                model.anchors.push((model.height, model.sct.clone()));
+                model.height += 1;
+                state_tx.put_block_height(model.height);
+                state_tx.put_epoch_by_height(
+                    model.height,
+                    Epoch {
+                        index: 0,
+                        start_height: 0,
+                    },
+                );
+                state_tx.apply();
+            }
+            Op::ClaimAnchor { index } => {
+                if model.anchors.is_empty() {
+                    continue;
+                }
+                let (_, tree) = &model.anchors[index % model.anchors.len()];
+                prop_assert!(state_tx.check_claimed_anchor(tree.root()).await.is_ok());
+            }
+            Op::ClaimBogusAnchor { seed } => {
+                let anchor = bogus_tree(tct::StateCommitment(decaf377::Fq::from(seed))).root();
+                if model.anchors.iter().all(|(_, tree)| tree.root() != anchor) {
+                    prop_assert!(state_tx.check_claimed_anchor(anchor).await.is_err());
+                }
+            }
+        }
+
+        check_invariants(&state, &model).await?;
+    }
+
+    Ok(())
+}
+
+proptest! {
+    // Every output and spend is proven and verified, so keep the number of
+    // cases and the length of traces small.
+    #![proptest_config(ProptestConfig::with_cases(8))]
+    #[test]
+    fn shielded_pool_state_machine(
+        seed in any::<u64>(),
+        ops in prop::collection::vec(op(), 1..16),
+    ) {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("can build tokio runtime");
+        rt.block_on(run_trace(seed, ops))?;
+    }
+}
@@ -176,6 +176,15 @@ impl Component for Staking {
     }
 }
 
+impl cnidarium_component::EpochHandler for Staking {
+    const NAME: &'static str = "staking";
+
+    /// Rate updates consume the issuance budget computed by the distributions
+    /// component, and must not change voting power before governance has
+    /// tallied the delegator votes cast during the ending epoch.
+    const RUN_AFTER: &'static [&'static str] = &["distributions", "governance"];
+}
+
 pub trait ConsensusUpdateRead: StateRead {
     /// Returns a list of validator updates to send to Tendermint.
     ///
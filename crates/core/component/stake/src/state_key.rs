@@ -155,6 +155,42 @@ pub(super) mod internal {
     }
 }
 
+/// Declares this component's state keys in the application's key schema.
+#[cfg(feature = "component")]
+pub fn declare_schema(schema: &mut cnidarium_component::StateKeySchema) -> anyhow::Result<()> {
+    use crate::{
+        params::StakeParameters,
+        rate::{BaseRateData, RateData},
+        validator, CurrentConsensusKeys, DelegationChanges, IdentityKey, Penalty, Uptime,
+    };
+    use penumbra_num::Amount;
+
+    schema
+        .component("stake")
+        .verifiable::<StakeParameters>(parameters::key())?
+        .verifiable::<validator::Validator>("staking/validators/definitions/{identity_key}")?
+        .verifiable::<validator::State>("staking/validators/data/state/{identity_key}")?
+        .verifiable::<RateData>("staking/validators/data/rate/current/{identity_key}")?
+        .verifiable::<RateData>("staking/validators/data/rate/previous/{identity_key}")?
+        .verifiable::<Amount>("staking/validators/data/power/{identity_key}")?
+        .verifiable::<Amount>("staking/validators/data/pool/balance/{identity_key}")?
+        .verifiable::<validator::BondingState>(
+            "staking/validators/data/pool/bonding_state/{identity_key}",
+        )?
+        .verifiable::<IdentityKey>("staking/validators/lookup_by/consensus_key/{consensus_key}")?
+        .verifiable::<tendermint::PublicKey>(
+            "staking/validators/lookup_by/cometbft_address/{address}",
+        )?
+        .verifiable::<BaseRateData>(chain::base_rate::current())?
+        .verifiable::<DelegationChanges>("staking/delegation_changes/{height}")?
+        .verifiable::<Penalty>("staking/penalty/{identity_key}/{epoch_index}")?
+        .verifiable::<CurrentConsensusKeys>(consensus_update::consensus_keys())?
+        .nonverifiable::<Uptime>("staking/validators/data/uptime/{identity_key}")?
+        .nonverifiable::<u64>("staking/validators/data/last_disabled/{identity_key}")?
+        .nonverifiable::<String>("staking/validators/consensus_set_index/{identity_key}")?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use decaf377_rdsa as rdsa;
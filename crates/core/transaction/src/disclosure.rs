@@ -0,0 +1,317 @@
+//! Disclosure of a single transaction's contents to a third party.
+//!
+//! A [`TransactionDisclosure`] holds the per-action payload keys and the memo key of one
+//! transaction, which is enough to decrypt the parts of that transaction visible to the wallet
+//! that created the disclosure, without revealing any viewing keys. This lets a designated third
+//! party, e.g. an arbiter in a dispute, check what was sent in that transaction and nothing else.
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use penumbra_dex::swap::Swap;
+use penumbra_keys::PayloadKey;
+use penumbra_proto::core::{keys::v1 as pbk, transaction::v1 as pbt};
+use penumbra_shielded_pool::{Note, Output};
+use penumbra_tct::StateCommitment;
+use penumbra_txhash::TransactionId;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    memo::MemoCiphertext, MemoPlaintextView, MemoView, Transaction, TransactionPerspective,
+    TransactionView,
+};
+
+/// The keys needed to decrypt the contents of exactly one transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "DisclosureEncoding", into = "DisclosureEncoding")]
+pub struct TransactionDisclosure {
+    /// The transaction being disclosed.
+    pub transaction_id: TransactionId,
+    /// The payload keys of the disclosed outputs and swaps, indexed by the commitment of the
+    /// note or swap they decrypt.
+    pub payload_keys: BTreeMap<StateCommitment, PayloadKey>,
+    /// The key encrypting the transaction's memo, if it has one and it was visible to the
+    /// disclosing wallet.
+    pub memo_key: Option<PayloadKey>,
+}
+
+impl TransactionDisclosure {
+    /// Creates a disclosure of `transaction`, from a set of payload keys such as those in a
+    /// [`TransactionPerspective`].
+    ///
+    /// Only the keys that actually decrypt an action of `transaction` are kept, so that the
+    /// disclosure can't be used to read anything outside of it.
+    pub fn new(
+        transaction: &Transaction,
+        payload_keys: &BTreeMap<StateCommitment, PayloadKey>,
+    ) -> Self {
+        let mut disclosed_keys = BTreeMap::new();
+        let mut memo_key = None;
+        let has_memo = transaction.transaction_body().memo.is_some();
+
+        for output in transaction.outputs() {
+            let commitment = output.body.note_payload.note_commitment;
+            let Some(payload_key) = payload_keys.get(&commitment) else {
+                continue;
+            };
+            if decrypt_output(output, payload_key).is_ok() {
+                disclosed_keys.insert(commitment, payload_key.clone());
+                // The memo key is wrapped under the payload key of every output, so any of them
+                // will do.
+                if memo_key.is_none() && has_memo {
+                    memo_key = output
+                        .body
+                        .wrapped_memo_key
+                        .decrypt_outgoing(payload_key)
+                        .ok();
+                }
+            }
+        }
+
+        for swap in transaction.swaps() {
+            let commitment = swap.body.payload.commitment;
+            let Some(payload_key) = payload_keys.get(&commitment) else {
+                continue;
+            };
+            if decrypt_swap(swap, payload_key).is_ok() {
+                disclosed_keys.insert(commitment, payload_key.clone());
+            }
+        }
+
+        Self {
+            transaction_id: transaction.id(),
+            payload_keys: disclosed_keys,
+            memo_key,
+        }
+    }
+
+    /// Checks this disclosure against `transaction`, and returns the view of `transaction` it
+    /// discloses.
+    ///
+    /// This fails if `transaction` isn't the disclosed transaction, or if any of the disclosed
+    /// keys don't decrypt the action or memo they claim to.
+    pub fn verify(&self, transaction: &Transaction) -> anyhow::Result<TransactionView> {
+        let transaction_id = transaction.id();
+        if transaction_id != self.transaction_id {
+            anyhow::bail!(
+                "disclosure is for transaction {}, not {}",
+                self.transaction_id,
+                transaction_id
+            );
+        }
+
+        for (commitment, payload_key) in &self.payload_keys {
+            if let Some(output) = transaction
+                .outputs()
+                .find(|output| output.body.note_payload.note_commitment == *commitment)
+            {
+                decrypt_output(output, payload_key).with_context(|| {
+                    format!("payload key for output {commitment} does not decrypt it")
+                })?;
+                if let Some(memo_key) = &self.memo_key {
+                    if output.body.wrapped_memo_key.decrypt_outgoing(payload_key)? != *memo_key {
+                        anyhow::bail!(
+                            "memo key does not match the memo key of output {commitment}"
+                        );
+                    }
+                }
+            } else if let Some(swap) = transaction
+                .swaps()
+                .find(|swap| swap.body.payload.commitment == *commitment)
+            {
+                decrypt_swap(swap, payload_key).with_context(|| {
+                    format!("payload key for swap {commitment} does not decrypt it")
+                })?;
+            } else {
+                anyhow::bail!(
+                    "commitment {commitment} is not an output or swap of the transaction"
+                );
+            }
+        }
+
+        let memo_plaintext = match (&self.memo_key, transaction.transaction_body().memo) {
+            (Some(memo_key), Some(ciphertext)) => Some((
+                MemoCiphertext::decrypt(memo_key, ciphertext.clone())
+                    .context("memo key does not decrypt the transaction memo")?,
+                ciphertext,
+            )),
+            (Some(_), None) => anyhow::bail!("memo key disclosed for a transaction without a memo"),
+            (None, _) => None,
+        };
+
+        let txp = TransactionPerspective {
+            payload_keys: self.payload_keys.clone(),
+            transaction_id,
+            ..Default::default()
+        };
+        let mut view = transaction.view_from_perspective(&txp);
+
+        // The memo is visible even if no output was disclosed along with its key.
+        if let Some((plaintext, ciphertext)) = memo_plaintext {
+            view.body_view.memo_view = Some(MemoView::Visible {
+                plaintext: MemoPlaintextView {
+                    return_address: txp.view_address(plaintext.return_address()),
+                    text: plaintext.text().to_owned(),
+                },
+                ciphertext,
+            });
+        }
+
+        Ok(view)
+    }
+}
+
+fn decrypt_output(output: &Output, payload_key: &PayloadKey) -> anyhow::Result<Note> {
+    Ok(Note::decrypt_with_payload_key(
+        &output.body.note_payload.encrypted_note,
+        payload_key,
+        &output.body.note_payload.ephemeral_key,
+    )?)
+}
+
+fn decrypt_swap(swap: &Swap, payload_key: &PayloadKey) -> anyhow::Result<()> {
+    swap.body
+        .payload
+        .encrypted_swap
+        .decrypt_with_payload_key(payload_key, swap.body.payload.commitment)
+        .map(|_| ())
+}
+
+/// The serialized form of a [`TransactionDisclosure`], reusing the proto encodings of its keys.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DisclosureEncoding {
+    transaction_id: TransactionId,
+    payload_keys: Vec<pbt::PayloadKeyWithCommitment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    memo_key: Option<pbk::PayloadKey>,
+}
+
+impl From<TransactionDisclosure> for DisclosureEncoding {
+    fn from(disclosure: TransactionDisclosure) -> Self {
+        Self {
+            transaction_id: disclosure.transaction_id,
+            payload_keys: disclosure
+                .payload_keys
+                .into_iter()
+                .map(|(commitment, payload_key)| pbt::PayloadKeyWithCommitment {
+                    payload_key: Some(payload_key.into()),
+                    commitment: Some(commitment.into()),
+                })
+                .collect(),
+            memo_key: disclosure.memo_key.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<DisclosureEncoding> for TransactionDisclosure {
+    type Error = anyhow::Error;
+
+    fn try_from(encoding: DisclosureEncoding) -> Result<Self, Self::Error> {
+        let mut payload_keys = BTreeMap::new();
+        for pk in encoding.payload_keys {
+            payload_keys.insert(
+                pk.commitment
+                    .ok_or_else(|| anyhow!("missing commitment in payload key"))?
+                    .try_into()?,
+                pk.payload_key
+                    .ok_or_else(|| anyhow!("missing payload key"))?
+                    .try_into()?,
+            );
+        }
+
+        Ok(Self {
+            transaction_id: encoding.transaction_id,
+            payload_keys,
+            memo_key: encoding.memo_key.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+    use penumbra_fee::Fee;
+    use penumbra_keys::keys::{Bip44Path, SeedPhrase, SpendKey};
+    use penumbra_shielded_pool::OutputPlan;
+    use rand_core::OsRng;
+
+    use super::*;
+    use crate::{
+        memo::MemoPlaintext,
+        plan::{MemoPlan, TransactionPlan},
+        view::action_view::OutputView,
+        ActionView, TransactionParameters, WitnessData,
+    };
+
+    #[test]
+    fn disclosure_reveals_only_its_transaction() {
+        let sk = SpendKey::from_seed_phrase_bip44(SeedPhrase::generate(OsRng), &Bip44Path::new(0));
+        let fvk = sk.full_viewing_key();
+        let (addr, _dtk) = fvk.incoming().payment_address(0u32.into());
+
+        let build = |text: &str| {
+            let memo_plaintext =
+                MemoPlaintext::new(addr.clone(), text.to_string()).expect("valid memo");
+            let plan = TransactionPlan {
+                actions: vec![OutputPlan::new(
+                    &mut OsRng,
+                    Value {
+                        amount: 1000u64.into(),
+                        asset_id: *STAKING_TOKEN_ASSET_ID,
+                    },
+                    addr.clone(),
+                )
+                .into()],
+                transaction_parameters: TransactionParameters {
+                    expiry_height: 0,
+                    fee: Fee::default(),
+                    chain_id: "penumbra-test".to_string(),
+                },
+                detection_data: None,
+                memo: Some(MemoPlan::new(&mut OsRng, memo_plaintext).expect("valid memo")),
+            };
+            let auth_data = plan.authorize(OsRng, &sk).expect("can authorize");
+            let witness_data = WitnessData {
+                anchor: penumbra_tct::Tree::new().root(),
+                state_commitment_proofs: Default::default(),
+            };
+            plan.build(fvk, &witness_data, &auth_data)
+                .expect("can build transaction")
+        };
+        let tx = build("for the invoice");
+        let other_tx = build("for something else");
+
+        let disclosure = TransactionDisclosure::new(
+            &tx,
+            &tx.payload_keys(fvk).expect("can derive payload keys"),
+        );
+        assert_eq!(disclosure.payload_keys.len(), 1);
+        assert!(disclosure.memo_key.is_some());
+
+        // The disclosure survives a round trip through its serialized form.
+        let disclosure: TransactionDisclosure = serde_json::from_str(
+            &serde_json::to_string(&disclosure).expect("can serialize disclosure"),
+        )
+        .expect("can deserialize disclosure");
+
+        let view = disclosure.verify(&tx).expect("disclosure is valid");
+        assert!(matches!(
+            view.body_view.action_views[0],
+            ActionView::Output(OutputView::Visible { .. })
+        ));
+        match view.body_view.memo_view {
+            Some(MemoView::Visible { plaintext, .. }) => {
+                assert_eq!(plaintext.text, "for the invoice")
+            }
+            _ => panic!("memo should be visible"),
+        }
+
+        // It can't be used to read another transaction, even by changing its transaction ID.
+        assert!(disclosure.verify(&other_tx).is_err());
+        let retargeted = TransactionDisclosure {
+            transaction_id: other_tx.id(),
+            ..disclosure
+        };
+        assert!(retargeted.verify(&other_tx).is_err());
+    }
+}
@@ -26,6 +26,7 @@ mod transaction;
 mod witness_data;
 
 pub mod action;
+pub mod disclosure;
 pub mod gas;
 pub mod memo;
 pub mod plan;
@@ -34,6 +35,7 @@ pub mod view;
 pub use action::Action;
 pub use auth_data::AuthorizationData;
 pub use detection_data::DetectionData;
+pub use disclosure::TransactionDisclosure;
 pub use error::Error;
 pub use is_action::IsAction;
 pub use parameters::TransactionParameters;
@@ -33,6 +33,7 @@ pub use action::ActionPlan;
 pub use clue::CluePlan;
 pub use detection_data::DetectionDataPlan;
 pub use memo::MemoPlan;
+pub use penumbra_proof_params::{CancellationToken, Cancelled, ProvingObserver, ProvingStage};
 
 use crate::TransactionParameters;
 
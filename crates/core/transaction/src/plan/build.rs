@@ -3,6 +3,7 @@ use ark_ff::Zero;
 use decaf377::Fr;
 use decaf377_rdsa as rdsa;
 use penumbra_keys::FullViewingKey;
+use penumbra_proof_params::ProvingObserver;
 use penumbra_txhash::AuthorizingData;
 
 use super::TransactionPlan;
@@ -119,20 +120,40 @@ impl TransactionPlan {
         witness_data: &WitnessData,
         auth_data: &AuthorizationData,
     ) -> Result<Transaction> {
-        // TODO: stream progress updates
+        self.build_with_observer(
+            full_viewing_key,
+            witness_data,
+            auth_data,
+            &ProvingObserver::default(),
+        )
+    }
+
+    /// Build the serial transaction this plan describes, reporting the progress of proving to
+    /// `observer`.
+    ///
+    /// If the observer's cancellation token is cancelled, building stops with a
+    /// [`Cancelled`](super::Cancelled) error once the current proving stage ends.
+    pub fn build_with_observer(
+        self,
+        full_viewing_key: &FullViewingKey,
+        witness_data: &WitnessData,
+        auth_data: &AuthorizationData,
+        observer: &ProvingObserver,
+    ) -> Result<Transaction> {
         // 1. Build each action.
-        let actions = self
-            .actions
-            .iter()
-            .map(|action_plan| {
-                ActionPlan::build_unauth(
-                    action_plan.clone(),
-                    full_viewing_key,
-                    witness_data,
-                    self.memo_key(),
-                )
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let actions = observer.observe(|| {
+            self.actions
+                .iter()
+                .map(|action_plan| {
+                    ActionPlan::build_unauth(
+                        action_plan.clone(),
+                        full_viewing_key,
+                        witness_data,
+                        self.memo_key(),
+                    )
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
 
         // 2. Pass in the prebuilt actions to the build method.
         let tx = self
@@ -154,6 +175,29 @@ impl TransactionPlan {
         full_viewing_key: &FullViewingKey,
         witness_data: &WitnessData,
         auth_data: &AuthorizationData,
+    ) -> Result<Transaction> {
+        self.build_concurrent_with_observer(
+            full_viewing_key,
+            witness_data,
+            auth_data,
+            &ProvingObserver::default(),
+        )
+        .await
+    }
+
+    #[cfg(feature = "parallel")]
+    /// Build the transaction this plan describes while proving concurrently, reporting the
+    /// progress of proving to `observer`.
+    ///
+    /// If the observer's cancellation token is cancelled, building stops with a
+    /// [`Cancelled`](super::Cancelled) error once the current proving stage of
+    /// each action ends.
+    pub async fn build_concurrent_with_observer(
+        self,
+        full_viewing_key: &FullViewingKey,
+        witness_data: &WitnessData,
+        auth_data: &AuthorizationData,
+        observer: &ProvingObserver,
     ) -> Result<Transaction> {
         // Clone the witness data into an Arc so it can be shared between tasks.
         let witness_data = std::sync::Arc::new(witness_data.clone());
@@ -167,8 +211,11 @@ impl TransactionPlan {
                 let fvk2 = full_viewing_key.clone();
                 let witness_data2 = witness_data.clone(); // Arc
                 let memo_key2 = self.memo_key();
+                let observer2 = observer.clone();
                 tokio::task::spawn_blocking(move || {
-                    ActionPlan::build_unauth(action_plan, &fvk2, &*witness_data2, memo_key2)
+                    observer2.observe(|| {
+                        ActionPlan::build_unauth(action_plan, &fvk2, &*witness_data2, memo_key2)
+                    })
                 })
             })
             .collect::<Vec<_>>();
@@ -12,8 +12,10 @@ use std::ops::Deref;
 /// The length of our Groth16 proofs in bytes.
 pub const GROTH16_PROOF_LENGTH_BYTES: usize = 192;
 
+mod progress;
 mod traits;
 
+pub use progress::{create_proof, CancellationToken, Cancelled, ProvingObserver, ProvingStage};
 pub use traits::{
     generate_constraint_matrices, generate_prepared_test_parameters, generate_test_parameters,
     DummyWitness, ProvingKeyExt, VerifyingKeyExt,
@@ -0,0 +1,239 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Context;
+use ark_groth16::{r1cs_to_qap::LibsnarkReduction, Groth16, Proof, ProvingKey};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, OptimizationGoal};
+use decaf377::{Bls12_377, Fq};
+
+/// A stage of generating a Groth16 proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProvingStage {
+    /// Synthesizing the circuit's constraints and witness assignment.
+    Synthesis,
+    /// Computing the proof from the witness, which is dominated by multi-scalar multiplication.
+    Msm,
+}
+
+impl ProvingStage {
+    /// The number of stages each proof goes through.
+    pub const COUNT: usize = 2;
+}
+
+/// A token used to cancel proof generation, possibly from another thread.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The error returned when proof generation is cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("proof generation was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+thread_local! {
+    static CURRENT_OBSERVER: RefCell<Option<ProvingObserver>> = RefCell::new(None);
+}
+
+/// Reinstalls the observer that was current before [`ProvingObserver::observe`] when dropped, so
+/// that it's restored even if the observed closure panics.
+struct RestoreObserver(Option<ProvingObserver>);
+
+impl Drop for RestoreObserver {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT_OBSERVER.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Observes the stages of proof generation, and allows cancelling it.
+///
+/// Proofs are generated deep inside action building, so rather than being passed through every
+/// plan, an observer is installed for the duration of a closure with [`ProvingObserver::observe`],
+/// and applies to all proofs generated by [`create_proof`] on that thread.
+///
+/// A stage can't be interrupted once started, so cancellation takes effect at the next stage
+/// boundary.
+#[derive(Clone, Default)]
+pub struct ProvingObserver {
+    on_stage_complete: Option<Arc<dyn Fn(ProvingStage) + Send + Sync>>,
+    cancellation: CancellationToken,
+}
+
+impl fmt::Debug for ProvingObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProvingObserver")
+            .field("cancellation", &self.cancellation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProvingObserver {
+    /// Creates an observer calling `on_stage_complete` each time a proof finishes a stage, and
+    /// stopping proof generation once `cancellation` is cancelled.
+    pub fn new(
+        on_stage_complete: impl Fn(ProvingStage) + Send + Sync + 'static,
+        cancellation: CancellationToken,
+    ) -> Self {
+        Self {
+            on_stage_complete: Some(Arc::new(on_stage_complete)),
+            cancellation,
+        }
+    }
+
+    /// Runs `f`, reporting the proofs it generates on this thread to this observer.
+    pub fn observe<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _restore =
+            RestoreObserver(CURRENT_OBSERVER.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+
+    /// Returns an error if proof generation was cancelled.
+    pub fn check_cancelled(&self) -> Result<(), Cancelled> {
+        if self.cancellation.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn current() -> Self {
+        CURRENT_OBSERVER.with(|current| current.borrow().clone().unwrap_or_default())
+    }
+
+    fn complete(&self, stage: ProvingStage) {
+        if let Some(on_stage_complete) = &self.on_stage_complete {
+            on_stage_complete(stage);
+        }
+    }
+}
+
+/// Generates a Groth16 proof of `circuit`, reporting each stage to the current [`ProvingObserver`].
+///
+/// This does the same work as `Groth16::create_proof_with_reduction`, split at the boundary
+/// between constraint synthesis and the MSMs.
+pub fn create_proof<C: ConstraintSynthesizer<Fq>>(
+    circuit: C,
+    pk: &ProvingKey<Bls12_377>,
+    blinding_r: Fq,
+    blinding_s: Fq,
+) -> anyhow::Result<Proof<Bls12_377>> {
+    let observer = ProvingObserver::current();
+
+    observer.check_cancelled()?;
+    let cs = ConstraintSystem::new_ref();
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    circuit
+        .generate_constraints(cs.clone())
+        .map_err(|err| anyhow::anyhow!(err))?;
+    cs.finalize();
+    let matrices = cs
+        .to_matrices()
+        .context("can convert R1CS constraints into matrices")?;
+    let prover = cs.borrow().context("constraint system is not borrowed")?;
+    let full_assignment = [
+        prover.instance_assignment.as_slice(),
+        prover.witness_assignment.as_slice(),
+    ]
+    .concat();
+    observer.complete(ProvingStage::Synthesis);
+
+    observer.check_cancelled()?;
+    let proof = Groth16::<Bls12_377, LibsnarkReduction>::create_proof_with_reduction_and_matrices(
+        pk,
+        blinding_r,
+        blinding_s,
+        &matrices,
+        prover.num_instance_variables,
+        prover.num_constraints,
+        &full_assignment,
+    )
+    .map_err(|err| anyhow::anyhow!(err))?;
+    observer.complete(ProvingStage::Msm);
+
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic::AssertUnwindSafe, sync::Mutex};
+
+    use super::*;
+
+    #[test]
+    fn observer_is_scoped_to_closure() {
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let stages2 = stages.clone();
+        let observer = ProvingObserver::new(
+            move |stage| stages2.lock().expect("not poisoned").push(stage),
+            CancellationToken::new(),
+        );
+
+        observer.observe(|| ProvingObserver::current().complete(ProvingStage::Synthesis));
+        ProvingObserver::current().complete(ProvingStage::Msm);
+
+        assert_eq!(
+            *stages.lock().expect("not poisoned"),
+            vec![ProvingStage::Synthesis]
+        );
+    }
+
+    #[test]
+    fn previous_observer_is_restored_after_panic() {
+        let outer_cancellation = CancellationToken::new();
+        let outer = ProvingObserver::new(|_| {}, outer_cancellation.clone());
+        let inner_cancellation = CancellationToken::new();
+        inner_cancellation.cancel();
+        let inner = ProvingObserver::new(|_| {}, inner_cancellation);
+
+        outer.observe(|| {
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                inner.observe(|| panic!("proving failed"))
+            }));
+            assert!(result.is_err());
+            assert!(ProvingObserver::current().check_cancelled().is_ok());
+
+            outer_cancellation.cancel();
+            assert_eq!(ProvingObserver::current().check_cancelled(), Err(Cancelled));
+        });
+        assert!(CURRENT_OBSERVER.with(|current| current.borrow().is_none()));
+    }
+
+    #[test]
+    fn cancellation_is_visible_to_observed_proofs() {
+        let cancellation = CancellationToken::new();
+        let observer = ProvingObserver::new(|_| {}, cancellation.clone());
+
+        assert!(observer.observe(|| ProvingObserver::current().check_cancelled().is_ok()));
+        cancellation.cancel();
+        assert_eq!(
+            observer.observe(|| ProvingObserver::current().check_cancelled()),
+            Err(Cancelled)
+        );
+    }
+}
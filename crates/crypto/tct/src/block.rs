@@ -53,6 +53,58 @@ impl Finalized {
     pub fn root(&self) -> Root {
         Root(self.inner.hash())
     }
+
+    /// Build a finalized block directly from all of its commitments, in order.
+    ///
+    /// The result is identical to inserting each commitment into a [`Builder`] and then finalizing
+    /// it, but the internal hashes of the block are computed bottom-up in a single pass, and when
+    /// the `parallel` feature is enabled, independent subtrees are hashed in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InsertError`] if there are more commitments than fit in a single block.
+    pub(crate) fn from_commitments(
+        commitments: &[(Witness, StateCommitment)],
+    ) -> Result<Self, InsertError> {
+        if commitments.is_empty() {
+            return Ok(Self::default());
+        }
+
+        // Index all the kept commitments by their position in the block
+        let mut index = HashedMap::default();
+        let mut kept = 0;
+        for (position, (witness, commitment)) in commitments.iter().enumerate() {
+            let position: index::within::Block =
+                u16::try_from(position).map_err(|_| InsertError)?.into();
+            if let Witness::Keep = witness {
+                index.insert(*commitment, position);
+                kept += 1;
+            }
+        }
+
+        let inner = if kept == index.len() {
+            complete::Top::from_commitments(commitments)
+        } else {
+            // If any commitment is repeated, only its last occurrence is witnessed, just as when
+            // the previous one is forgotten on insertion into a builder; this case is handled for
+            // completeness, but should not happen in practice because commitments should be unique
+            let deduplicated: Vec<_> = commitments
+                .iter()
+                .enumerate()
+                .map(
+                    |(position, &(witness, commitment))| match index.get(&commitment) {
+                        Some(&kept) if usize::from(u16::from(kept)) == position => {
+                            (witness, commitment)
+                        }
+                        _ => (Witness::Forget, commitment),
+                    },
+                )
+                .collect();
+            complete::Top::from_commitments(&deduplicated)
+        };
+
+        Ok(Finalized { index, inner })
+    }
 }
 
 impl From<Root> for Finalized {
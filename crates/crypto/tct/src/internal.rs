@@ -140,7 +140,7 @@ pub mod complete {
     //! At the bottom of the bottom-most tier (perhaps at the bottom of multiple [`Tier`]s), there
     //! are [`Item`]s, each of which is merely a wrapper for a single
     //! [`Commitment`](crate::Commitment).
-    pub(crate) use super::interface::OutOfOrderOwned;
+    pub(crate) use super::interface::{FromCommitments, OutOfOrderOwned};
     #[doc(inline)]
     pub use super::interface::{Complete, ForgetOwned};
     pub(super) mod item;
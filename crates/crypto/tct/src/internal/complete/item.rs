@@ -92,6 +92,18 @@ impl OutOfOrderOwned for Item {
     }
 }
 
+impl FromCommitments for Item {
+    fn from_commitments(commitments: &[(crate::Witness, StateCommitment)]) -> Insert<Self> {
+        debug_assert_eq!(commitments.len(), 1, "an item holds exactly one commitment");
+        let (witness, commitment) = commitments[0];
+        let hash = Hash::of(commitment);
+        match witness {
+            crate::Witness::Keep => Insert::Keep(Item { hash, commitment }),
+            crate::Witness::Forget => Insert::Hash(hash),
+        }
+    }
+}
+
 impl UncheckedSetHash for Item {
     fn unchecked_set_hash(&mut self, index: u64, height: u8, hash: Hash) {
         if index != 0 {
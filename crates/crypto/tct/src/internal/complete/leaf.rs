@@ -84,6 +84,12 @@ impl<Item: OutOfOrderOwned> OutOfOrderOwned for Leaf<Item> {
     }
 }
 
+impl<Item: FromCommitments> FromCommitments for Leaf<Item> {
+    fn from_commitments(commitments: &[(crate::Witness, StateCommitment)]) -> Insert<Self> {
+        Item::from_commitments(commitments).map(Leaf)
+    }
+}
+
 impl<Item: UncheckedSetHash> UncheckedSetHash for Leaf<Item> {
     fn unchecked_set_hash(&mut self, index: u64, height: u8, hash: Hash) {
         self.0.unchecked_set_hash(index, height, hash)
@@ -272,6 +272,47 @@ impl<Child: Height + OutOfOrderOwned + Clone> OutOfOrderOwned for Node<Child> {
     }
 }
 
+/// The minimum capacity, in commitments, of each child of a node for its children to be built in
+/// parallel; below this, there is too little hashing per child to be worth distributing.
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 16;
+
+impl<Child: Height + GetHash + FromCommitments + Send + Clone> FromCommitments for Node<Child> {
+    fn from_commitments(commitments: &[(crate::Witness, StateCommitment)]) -> Insert<Self> {
+        // The number of commitments that fit beneath each child of this node
+        let stride = 4usize.pow(<Child as Height>::Height::HEIGHT.into());
+        debug_assert!(!commitments.is_empty() && commitments.len() <= 4 * stride);
+
+        // Each child depends only on its own commitments, so they can be built independently
+        #[cfg(feature = "parallel")]
+        let children: Vec<Insert<Child>> = if stride >= PARALLEL_THRESHOLD {
+            use rayon::prelude::*;
+            commitments
+                .par_chunks(stride)
+                .map(Child::from_commitments)
+                .collect()
+        } else {
+            commitments
+                .chunks(stride)
+                .map(Child::from_commitments)
+                .collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let children: Vec<Insert<Child>> = commitments
+            .chunks(stride)
+            .map(Child::from_commitments)
+            .collect();
+
+        // Fill any empty children with the *ONE* hash, exactly as when finalizing a frontier node
+        let mut children = children
+            .into_iter()
+            .chain(std::iter::repeat_with(|| Insert::Hash(Hash::one())));
+        let children = [(); 4].map(|()| children.next().expect("children are never exhausted"));
+
+        Node::from_children_or_else_hash([Forgotten::default(); 4], children)
+    }
+}
+
 impl<Child: GetHash + UncheckedSetHash + Clone> UncheckedSetHash for Node<Child> {
     fn unchecked_set_hash(&mut self, index: u64, height: u8, hash: Hash) {
         use std::cmp::Ordering::*;
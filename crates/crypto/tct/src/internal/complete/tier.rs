@@ -99,6 +99,12 @@ impl<Item: GetHash + Height + OutOfOrderOwned + Clone> OutOfOrderOwned for Tier<
     }
 }
 
+impl<Item: GetHash + Height + FromCommitments + Send + Clone> FromCommitments for Tier<Item> {
+    fn from_commitments(commitments: &[(crate::Witness, StateCommitment)]) -> Insert<Self> {
+        Nested::from_commitments(commitments).map(|inner| Tier { inner })
+    }
+}
+
 impl<Item: GetHash + UncheckedSetHash + Clone> UncheckedSetHash for Tier<Item> {
     fn unchecked_set_hash(&mut self, index: u64, height: u8, hash: Hash) {
         self.inner.unchecked_set_hash(index, height, hash)
@@ -162,3 +162,18 @@ pub(crate) trait UncheckedSetHash: Height {
     /// longer contains any `Hash::uninitialized()` anywhere.
     fn finish_initialize(&mut self);
 }
+
+/// Build a complete structure directly from the commitments to be placed in its leaves, in order,
+/// with any positions after the last commitment left empty.
+///
+/// This produces exactly the same structure and hashes as inserting each commitment into a
+/// frontier and then finalizing it, but because the subtrees of a complete node are independent of
+/// one another, their hashes can be computed in parallel.
+pub(crate) trait FromCommitments: Height + Sized {
+    /// Build the structure containing the given commitments, or its hash if none of them are to be
+    /// kept.
+    ///
+    /// The number of commitments must be non-zero, and must not exceed the capacity of the
+    /// structure.
+    fn from_commitments(commitments: &[(crate::Witness, StateCommitment)]) -> Insert<Self>;
+}
@@ -104,7 +104,7 @@ mod prelude {
         error::proof::VerifyError,
         index,
         internal::{
-            complete::{self, Complete, ForgetOwned, OutOfOrderOwned},
+            complete::{self, Complete, ForgetOwned, FromCommitments, OutOfOrderOwned},
             frontier::{
                 self, Focus, Forget, Frontier, Full, GetPosition, Insert, InsertMut, Item,
                 OutOfOrder,
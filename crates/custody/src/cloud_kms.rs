@@ -0,0 +1,430 @@
+//! A custody backend that keeps the spend authorization key in a cloud KMS or
+//! HSM, such as AWS KMS or a cloud-hosted HSM cluster.
+//!
+//! Penumbra's spend authorization signatures are `decaf377-rdsa` signatures
+//! made with a *randomized* signing key `rsk = ask + r`, where the randomizer
+//! `r` is chosen per-action by the transaction planner.  Key management
+//! systems generally only offer a sign-only API over a key they never reveal,
+//! so they cannot perform this randomization themselves without being given
+//! the randomizer and knowledge of the transaction format.  Instead, this
+//! backend splits the signature between the KMS and the client:
+//!
+//! 1. the KMS generates a fresh nonce `k` and returns the commitment
+//!    `R = [k] B` along with an opaque handle for the nonce;
+//! 2. the client computes the randomized verification key `rk = ak + [r] B`
+//!    and the `decaf377-rdsa` challenge `c = H(R || rk || msg)`;
+//! 3. the KMS computes `z = k + c * ask` using the nonce handle, and then
+//!    destroys the nonce;
+//! 4. the client computes `s = z + c * r`, so that `(R, s)` is a valid
+//!    signature under `rk`, and checks it before returning it.
+//!
+//! The KMS only ever learns the challenge, and the client never learns `ask`.
+//! Since the KMS cannot see what it is signing, all [`AuthPolicy`] checks are
+//! performed client-side, before any request is sent to the KMS.  Signing
+//! sessions are also run one at a time: a KMS answering several concurrent
+//! blind challenges for the same key can be induced to produce a signature
+//! the client never asked for.
+//!
+//! The KMS is accessed through the [`RemoteSigner`] trait; [`HttpSigner`]
+//! implements it for KMS deployments exposing the HTTP API it describes.
+//!
+//! [`AuthPolicy`]: crate::policy::AuthPolicy
+
+use anyhow::{anyhow, Context as _, Result};
+use ark_ff::PrimeField;
+use decaf377::Fr;
+use decaf377_rdsa::{Signature, SpendAuth, VerificationKey};
+use penumbra_proto::{
+    core::component::{
+        governance::v1::ValidatorVoteBody as ProtoValidatorVoteBody,
+        stake::v1::Validator as ProtoValidator,
+    },
+    custody::v1::{self as pb, AuthorizeResponse},
+    Message as _,
+};
+use penumbra_transaction::AuthorizationData;
+use tokio::sync::Mutex;
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::{
+    policy::Policy, AuthorizeRequest, AuthorizeValidatorDefinitionRequest,
+    AuthorizeValidatorVoteRequest,
+};
+
+mod config;
+mod http;
+
+pub use config::Config;
+pub use http::HttpSigner;
+
+/// A commitment to a signing nonce held by a [`RemoteSigner`].
+#[derive(Clone, Debug)]
+pub struct NonceCommitment {
+    /// An opaque handle identifying the nonce to the KMS.
+    pub nonce_id: String,
+    /// The nonce commitment `R = [k] B`.
+    pub commitment: decaf377::Encoding,
+}
+
+/// The sign-only interface to a KMS holding the spend authorization key `ask`.
+///
+/// Implementations must never reuse a nonce: the nonce identified by a
+/// [`NonceCommitment`] must be destroyed by the KMS after it is used in a
+/// single call to [`RemoteSigner::respond`], whether or not that call
+/// succeeds.
+#[async_trait]
+pub trait RemoteSigner: Send + Sync {
+    /// Asks the KMS to generate a fresh nonce `k`, returning its commitment.
+    async fn commit(&self) -> Result<NonceCommitment>;
+
+    /// Asks the KMS to compute `z = k + c * ask` for the given challenge `c`,
+    /// using the nonce with the given handle.
+    async fn respond(&self, nonce_id: &str, challenge: Fr) -> Result<Fr>;
+}
+
+/// Computes the `decaf377-rdsa` signature challenge for the given nonce
+/// commitment, verification key, and message.
+fn challenge(commitment: &decaf377::Encoding, vk: &VerificationKey<SpendAuth>, msg: &[u8]) -> Fr {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(b"decaf377-rdsa---")
+        .to_state()
+        .update(&commitment.0)
+        .update(&vk.to_bytes())
+        .update(msg)
+        .finalize();
+    Fr::from_le_bytes_mod_order(hash.as_bytes())
+}
+
+/// Produces a signature over `msg` under the spend authorization key
+/// randomized by `randomizer`, using `signer` to hold the unrandomized key.
+///
+/// `ak` must be the verification key for the key held by `signer`.
+async fn sign_randomized<S: RemoteSigner + ?Sized>(
+    signer: &S,
+    ak: &VerificationKey<SpendAuth>,
+    randomizer: &Fr,
+    msg: &[u8],
+) -> Result<Signature<SpendAuth>> {
+    let rk = ak.randomize(randomizer);
+
+    let NonceCommitment {
+        nonce_id,
+        commitment,
+    } = signer
+        .commit()
+        .await
+        .context("KMS nonce commitment failed")?;
+    let c = challenge(&commitment, &rk, msg);
+    let z = signer
+        .respond(&nonce_id, c)
+        .await
+        .context("KMS signing response failed")?;
+    let s = z + c * randomizer;
+
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&commitment.0);
+    bytes[32..].copy_from_slice(&s.to_bytes());
+    let signature = Signature::from(bytes);
+
+    // A misbehaving or misconfigured KMS (for instance, one holding a
+    // different key) would otherwise produce an invalid transaction.
+    rk.verify(msg, &signature)
+        .map_err(|_| anyhow!("KMS produced an invalid signature; is the key id correct?"))?;
+
+    Ok(signature)
+}
+
+/// A custody service whose spend authorization key is held by a remote KMS.
+pub struct CloudKms<S> {
+    config: Config,
+    signer: S,
+    /// Held for the duration of each signing request, so that the KMS never
+    /// has more than one outstanding challenge.
+    session: Mutex<()>,
+}
+
+impl CloudKms<HttpSigner> {
+    /// Initialize with the given [`Config`], using its endpoint to reach the KMS.
+    pub fn new(config: Config) -> Result<Self> {
+        let signer = HttpSigner::new(&config)?;
+        Ok(Self::with_signer(config, signer))
+    }
+}
+
+impl<S: RemoteSigner> CloudKms<S> {
+    /// Initialize with the given [`Config`], using `signer` to reach the KMS.
+    pub fn with_signer(config: Config, signer: S) -> Self {
+        Self {
+            config,
+            signer,
+            session: Mutex::new(()),
+        }
+    }
+
+    async fn sign_message(&self, randomizer: &Fr, msg: &[u8]) -> Result<Signature<SpendAuth>> {
+        sign_randomized(
+            &self.signer,
+            self.config.full_viewing_key.spend_verification_key(),
+            randomizer,
+            msg,
+        )
+        .await
+    }
+
+    /// Attempt to authorize the requested [`TransactionPlan`](penumbra_transaction::TransactionPlan).
+    #[tracing::instrument(skip(self, request), name = "cloud_kms_sign")]
+    pub async fn sign(&self, request: &AuthorizeRequest) -> Result<AuthorizationData> {
+        tracing::debug!(?request.plan);
+
+        for policy in &self.config.auth_policy {
+            policy.check_transaction(request)?;
+        }
+
+        let _session = self.session.lock().await;
+
+        let effect_hash = request.plan.effect_hash(&self.config.full_viewing_key)?;
+        let mut spend_auths = Vec::new();
+        let mut delegator_vote_auths = Vec::new();
+
+        for spend_plan in request.plan.spend_plans() {
+            spend_auths.push(
+                self.sign_message(&spend_plan.randomizer, effect_hash.as_ref())
+                    .await?,
+            );
+        }
+        for delegator_vote_plan in request.plan.delegator_vote_plans() {
+            delegator_vote_auths.push(
+                self.sign_message(&delegator_vote_plan.randomizer, effect_hash.as_ref())
+                    .await?,
+            );
+        }
+
+        Ok(AuthorizationData {
+            effect_hash: Some(effect_hash),
+            spend_auths,
+            delegator_vote_auths,
+        })
+    }
+
+    /// Attempt to authorize the requested validator definition.
+    #[tracing::instrument(skip(self, request), name = "cloud_kms_sign_validator_definition")]
+    pub async fn sign_validator_definition(
+        &self,
+        request: &AuthorizeValidatorDefinitionRequest,
+    ) -> Result<Signature<SpendAuth>> {
+        tracing::debug!(?request.validator_definition);
+
+        for policy in &self.config.auth_policy {
+            policy.check_validator_definition(request)?;
+        }
+
+        let protobuf_serialized: ProtoValidator = request.validator_definition.clone().into();
+        let validator_definition_bytes = protobuf_serialized.encode_to_vec();
+
+        let _session = self.session.lock().await;
+        self.sign_message(&Fr::from(0u64), &validator_definition_bytes)
+            .await
+    }
+
+    /// Attempt to authorize the requested validator vote.
+    #[tracing::instrument(skip(self, request), name = "cloud_kms_sign_validator_vote")]
+    pub async fn sign_validator_vote(
+        &self,
+        request: &AuthorizeValidatorVoteRequest,
+    ) -> Result<Signature<SpendAuth>> {
+        tracing::debug!(?request.validator_vote);
+
+        for policy in &self.config.auth_policy {
+            policy.check_validator_vote(request)?;
+        }
+
+        let protobuf_serialized: ProtoValidatorVoteBody = request.validator_vote.clone().into();
+        let validator_vote_bytes = protobuf_serialized.encode_to_vec();
+
+        let _session = self.session.lock().await;
+        self.sign_message(&Fr::from(0u64), &validator_vote_bytes)
+            .await
+    }
+}
+
+#[async_trait]
+impl<S: RemoteSigner + 'static> pb::custody_service_server::CustodyService for CloudKms<S> {
+    async fn authorize(
+        &self,
+        request: Request<pb::AuthorizeRequest>,
+    ) -> Result<Response<AuthorizeResponse>, Status> {
+        let request = request
+            .into_inner()
+            .try_into()
+            .map_err(|e: anyhow::Error| Status::invalid_argument(e.to_string()))?;
+
+        let authorization_data = self
+            .sign(&request)
+            .await
+            .map_err(|e| Status::unauthenticated(format!("{e:#}")))?;
+
+        let authorization_response = AuthorizeResponse {
+            data: Some(authorization_data.into()),
+        };
+
+        Ok(Response::new(authorization_response))
+    }
+
+    async fn authorize_validator_definition(
+        &self,
+        request: Request<pb::AuthorizeValidatorDefinitionRequest>,
+    ) -> Result<Response<pb::AuthorizeValidatorDefinitionResponse>, Status> {
+        let request = request
+            .into_inner()
+            .try_into()
+            .map_err(|e: anyhow::Error| Status::invalid_argument(e.to_string()))?;
+
+        let validator_definition_auth = self
+            .sign_validator_definition(&request)
+            .await
+            .map_err(|e| Status::unauthenticated(format!("{e:#}")))?;
+
+        let authorization_response = pb::AuthorizeValidatorDefinitionResponse {
+            validator_definition_auth: Some(validator_definition_auth.into()),
+        };
+
+        Ok(Response::new(authorization_response))
+    }
+
+    async fn authorize_validator_vote(
+        &self,
+        request: Request<pb::AuthorizeValidatorVoteRequest>,
+    ) -> Result<Response<pb::AuthorizeValidatorVoteResponse>, Status> {
+        let request = request
+            .into_inner()
+            .try_into()
+            .map_err(|e: anyhow::Error| Status::invalid_argument(e.to_string()))?;
+
+        let validator_vote_auth = self
+            .sign_validator_vote(&request)
+            .await
+            .map_err(|e| Status::unauthenticated(format!("{e:#}")))?;
+
+        let authorization_response = pb::AuthorizeValidatorVoteResponse {
+            validator_vote_auth: Some(validator_vote_auth.into()),
+        };
+
+        Ok(Response::new(authorization_response))
+    }
+
+    async fn export_full_viewing_key(
+        &self,
+        _request: Request<pb::ExportFullViewingKeyRequest>,
+    ) -> Result<Response<pb::ExportFullViewingKeyResponse>, Status> {
+        Ok(Response::new(pb::ExportFullViewingKeyResponse {
+            full_viewing_key: Some(self.config.full_viewing_key.clone().into()),
+        }))
+    }
+
+    async fn confirm_address(
+        &self,
+        request: Request<pb::ConfirmAddressRequest>,
+    ) -> Result<Response<pb::ConfirmAddressResponse>, Status> {
+        let address_index = request
+            .into_inner()
+            .address_index
+            .ok_or_else(|| {
+                Status::invalid_argument("missing address index in confirm address request")
+            })?
+            .try_into()
+            .map_err(|e| {
+                Status::invalid_argument(format!(
+                    "invalid address index in confirm address request: {e:#}"
+                ))
+            })?;
+
+        let (address, _dtk) = self.config.full_viewing_key.payment_address(address_index);
+
+        Ok(Response::new(pb::ConfirmAddressResponse {
+            address: Some(address.into()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ark_ff::UniformRand;
+    use penumbra_keys::{keys::SpendKey, test_keys};
+    use rand_core::OsRng;
+
+    use super::*;
+
+    /// An in-memory stand-in for a KMS, holding `ask` and single-use nonces.
+    struct MockKms {
+        ask: Fr,
+        nonces: std::sync::Mutex<BTreeMap<String, Fr>>,
+    }
+
+    impl MockKms {
+        fn new(spend_key: &SpendKey) -> Self {
+            Self {
+                ask: Fr::from_bytes(spend_key.spend_auth_key().to_bytes()).unwrap(),
+                nonces: Default::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RemoteSigner for MockKms {
+        async fn commit(&self) -> Result<NonceCommitment> {
+            let k = Fr::rand(&mut OsRng);
+            let mut nonces = self.nonces.lock().unwrap();
+            let nonce_id = nonces.len().to_string();
+            nonces.insert(nonce_id.clone(), k);
+            Ok(NonceCommitment {
+                nonce_id,
+                commitment: (k * decaf377::basepoint()).vartime_compress(),
+            })
+        }
+
+        async fn respond(&self, nonce_id: &str, challenge: Fr) -> Result<Fr> {
+            let k = self
+                .nonces
+                .lock()
+                .unwrap()
+                .remove(nonce_id)
+                .ok_or_else(|| anyhow!("unknown nonce"))?;
+            Ok(k + challenge * self.ask)
+        }
+    }
+
+    #[tokio::test]
+    async fn split_signatures_verify_under_randomized_key() -> Result<()> {
+        let kms = MockKms::new(&test_keys::SPEND_KEY);
+        let ak = test_keys::FULL_VIEWING_KEY.spend_verification_key();
+        let msg = b"effect hash";
+
+        for randomizer in [Fr::from(0u64), Fr::rand(&mut OsRng)] {
+            let signature = sign_randomized(&kms, ak, &randomizer, msg).await?;
+            ak.randomize(&randomizer).verify(msg, &signature)?;
+        }
+
+        // Nonces are single-use.
+        assert!(kms.respond("0", Fr::from(1u64)).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn signatures_from_the_wrong_key_are_rejected() {
+        let kms = MockKms {
+            ask: Fr::rand(&mut OsRng),
+            nonces: Default::default(),
+        };
+        let ak = test_keys::FULL_VIEWING_KEY.spend_verification_key();
+
+        assert!(
+            sign_randomized(&kms, ak, &Fr::rand(&mut OsRng), b"effect hash")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use crate::policy::AuthPolicy;
+use penumbra_keys::FullViewingKey;
+use serde::{Deserialize, Serialize};
+use serde_with::DisplayFromStr;
+
+/// Configuration data for the [`CloudKms`](super::CloudKms).
+///
+/// The `full_viewing_key` must be the viewing key derived from the spend key
+/// held by the KMS; signatures produced by a KMS holding any other key are
+/// rejected.  Leaving the `auth_policy` empty provides blind signing.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    #[serde_as(as = "DisplayFromStr")]
+    pub full_viewing_key: FullViewingKey,
+    /// The base URL of the KMS signing API.
+    pub endpoint: String,
+    /// The identifier of the spend authorization key within the KMS.
+    pub key_id: String,
+    /// The name of an environment variable holding a bearer token used to
+    /// authenticate to the KMS, if it requires one.
+    ///
+    /// The token itself is never stored in the config file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_env: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub auth_policy: Vec<AuthPolicy>,
+}
+
+/// Helper function for Serde serialization, allowing us to skip serialization
+/// of default config values.
+fn is_default<T: Default + Eq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_config_round_trip() {
+        let example = Config {
+            full_viewing_key: penumbra_keys::test_keys::FULL_VIEWING_KEY.clone(),
+            endpoint: "https://kms.example.com".to_string(),
+            key_id: "penumbra-spend-auth".to_string(),
+            credentials_env: Some("PENUMBRA_KMS_TOKEN".to_string()),
+            auth_policy: vec![AuthPolicy::OnlyIbcRelay],
+        };
+
+        let encoded = toml::to_string_pretty(&example).unwrap();
+        let example2 = toml::from_str(&encoded).unwrap();
+        assert_eq!(example, example2);
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::{anyhow, Context as _, Result};
+use decaf377::Fr;
+use serde::{Deserialize, Serialize};
+use tonic::async_trait;
+
+use super::{Config, NonceCommitment, RemoteSigner};
+
+/// A [`RemoteSigner`] for a KMS exposing a JSON-over-HTTP signing API.
+///
+/// The KMS must implement two endpoints for each key, both accepting and
+/// returning JSON, with all scalars and group elements hex-encoded in their
+/// canonical 32-byte encodings:
+///
+/// - `POST {endpoint}/v1/keys/{key_id}/commit`, with an empty body, returning
+///   `{"nonce_id": ..., "commitment": ...}`;
+/// - `POST {endpoint}/v1/keys/{key_id}/respond`, with the body
+///   `{"nonce_id": ..., "challenge": ...}`, returning `{"response": ...}`.
+///
+/// This is a thin adapter layer: deployments using a provider's native API
+/// (for instance, a custom key store backed by a cloud HSM) are expected to
+/// expose it through a small signing proxy running alongside the HSM.
+pub struct HttpSigner {
+    client: reqwest::Client,
+    commit_url: String,
+    respond_url: String,
+    token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CommitResponse {
+    nonce_id: String,
+    commitment: String,
+}
+
+#[derive(Serialize)]
+struct RespondRequest<'a> {
+    nonce_id: &'a str,
+    challenge: String,
+}
+
+#[derive(Deserialize)]
+struct RespondResponse {
+    response: String,
+}
+
+fn decode_32(field: &str, value: &str) -> Result<[u8; 32]> {
+    hex::decode(value)
+        .with_context(|| format!("KMS returned a malformed {field}"))?
+        .try_into()
+        .map_err(|_| anyhow!("KMS returned a {field} of the wrong length"))
+}
+
+impl HttpSigner {
+    /// Constructs a signer for the KMS and key described by `config`.
+    ///
+    /// If the config names a credentials environment variable, it must be set.
+    pub fn new(config: &Config) -> Result<Self> {
+        let token = config
+            .credentials_env
+            .as_ref()
+            .map(|var| {
+                std::env::var(var)
+                    .with_context(|| format!("KMS credentials variable {var} is not set"))
+            })
+            .transpose()?;
+        let base = format!(
+            "{}/v1/keys/{}",
+            config.endpoint.trim_end_matches('/'),
+            config.key_id
+        );
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            commit_url: format!("{base}/commit"),
+            respond_url: format!("{base}/respond"),
+            token,
+        })
+    }
+
+    fn post(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.post(url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteSigner for HttpSigner {
+    async fn commit(&self) -> Result<NonceCommitment> {
+        let CommitResponse {
+            nonce_id,
+            commitment,
+        } = self
+            .post(&self.commit_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(NonceCommitment {
+            nonce_id,
+            commitment: decaf377::Encoding(decode_32("nonce commitment", &commitment)?),
+        })
+    }
+
+    async fn respond(&self, nonce_id: &str, challenge: Fr) -> Result<Fr> {
+        let RespondResponse { response } = self
+            .post(&self.respond_url)
+            .json(&RespondRequest {
+                nonce_id,
+                challenge: hex::encode(challenge.to_bytes()),
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Fr::from_bytes(decode_32("signing response", &response)?)
+            .map_err(|_| anyhow!("KMS returned a non-canonical signing response"))
+    }
+}
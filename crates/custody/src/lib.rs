@@ -2,7 +2,8 @@
 //!
 //! This crate currently focuses on the [`soft_kms`] implementation, a basic
 //! software key management system that can perform basic policy-based
-//! authorization or blind signing.
+//! authorization or blind signing.  The [`cloud_kms`] implementation keeps the
+//! spend authorization key in an external KMS or HSM instead.
 
 #![deny(clippy::unwrap_used)]
 // Requires nightly.
@@ -14,8 +15,10 @@ mod client;
 mod pre_auth;
 mod request;
 
+pub mod cloud_kms;
 pub mod null_kms;
 pub mod policy;
+pub mod screening;
 pub mod soft_kms;
 pub mod threshold;
 
@@ -1,7 +1,8 @@
 //! A set of basic spend authorization policies.
 
-use std::collections::HashSet;
+use std::{collections::HashSet, path::PathBuf};
 
+use anyhow::Context as _;
 use penumbra_keys::Address;
 use penumbra_proto::{
     core::{
@@ -17,8 +18,8 @@ use penumbra_transaction::plan::ActionPlan;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    AuthorizeRequest, AuthorizeValidatorDefinitionRequest, AuthorizeValidatorVoteRequest,
-    PreAuthorization,
+    screening::ScreeningConfig, AuthorizeRequest, AuthorizeValidatorDefinitionRequest,
+    AuthorizeValidatorVoteRequest, PreAuthorization,
 };
 
 /// A trait for checking whether a transaction plan is allowed by a policy.
@@ -61,6 +62,24 @@ pub enum AuthPolicy {
     OnlyIbcRelay,
     /// Require specific pre-authorizations for submitted [`TransactionPlan`](penumbra_transaction::TransactionPlan)s.
     PreAuthorization(PreAuthorizationPolicy),
+    /// Screen the destinations of transactions against a local
+    /// [`ScreeningList`](crate::screening::ScreeningList).
+    ///
+    /// Transactions sending funds to denied addresses are refused.  Those
+    /// sending funds to addresses requiring confirmation are refused unless
+    /// they carry the pre-authorizations required by `confirmation`.  The list
+    /// is re-read for every transaction, so that it can be updated without
+    /// restarting the custody service.
+    AddressScreening {
+        /// The path to the screening list.
+        list_file: PathBuf,
+        /// The path to an audit log recording flagged destinations, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        audit_log: Option<PathBuf>,
+        /// The pre-authorizations confirming transactions to flagged destinations.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        confirmation: Option<PreAuthorizationPolicy>,
+    },
 }
 
 /// A set of pre-authorization policies.
@@ -129,7 +148,7 @@ impl PreAuthorizationPolicy {
     }
 }
 
-mod address_as_string {
+pub(crate) mod address_as_string {
     use std::str::FromStr;
 
     use penumbra_keys::Address;
@@ -233,6 +252,36 @@ impl Policy for AuthPolicy {
                 Ok(())
             }
             AuthPolicy::PreAuthorization(policy) => policy.check_transaction(request),
+            AuthPolicy::AddressScreening {
+                list_file,
+                audit_log,
+                confirmation,
+            } => {
+                let screener = ScreeningConfig {
+                    list_file: list_file.clone(),
+                    audit_log: audit_log.clone(),
+                }
+                .screener()?;
+
+                let to_confirm = screener.screen_plan(plan, "custody")?;
+                if to_confirm.is_empty() {
+                    return Ok(());
+                }
+                let confirmed = confirmation
+                    .as_ref()
+                    .map(|policy| policy.check_transaction(request));
+                screener.record_confirmation(
+                    &to_confirm,
+                    "custody",
+                    matches!(confirmed, Some(Ok(()))),
+                )?;
+                match confirmed {
+                    Some(result) => result.context("transaction to flagged destination was not confirmed"),
+                    None => anyhow::bail!(
+                        "transaction sends funds to addresses requiring confirmation, but no confirmation policy is configured"
+                    ),
+                }
+            }
         }
     }
 
@@ -0,0 +1,470 @@
+//! Opt-in, client-side screening of transaction destinations.
+//!
+//! A [`Screener`] checks every address a [`TransactionPlan`] sends funds to
+//! against a locally configured [`AddressScreen`], such as a [`ScreeningList`]
+//! loaded from a file.  Destinations can be denied outright, or allowed only
+//! after explicit confirmation, and every flagged destination is recorded in
+//! an [`AuditLog`].
+//!
+//! Screening happens entirely on the client: the lists and the audit trail
+//! are local files, nothing is reported to any third party, and nothing is
+//! screened unless it has been configured.  The same [`Screener`] can be
+//! given to the transaction planner, to reject plans before they are built,
+//! and used by the [`AuthPolicy::AddressScreening`](crate::policy::AuthPolicy)
+//! custody policy, to refuse to sign them.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    fs,
+    io::{BufRead as _, BufReader, Write as _},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use penumbra_keys::Address;
+use penumbra_transaction::TransactionPlan;
+use serde::{Deserialize, Serialize};
+use serde_with::DisplayFromStr;
+
+use crate::policy::address_as_string;
+
+/// The outcome of screening a single address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    /// Funds may be sent to the address.
+    Allow,
+    /// Funds may be sent to the address only after explicit confirmation.
+    Confirm,
+    /// Funds must not be sent to the address.
+    Deny,
+}
+
+/// A check applied to each address a transaction sends funds to.
+pub trait AddressScreen: Send + Sync {
+    /// Decides whether funds may be sent to `address`.
+    fn screen(&self, address: &Address) -> Verdict;
+}
+
+/// Lists of addresses to deny, or to require confirmation for.
+///
+/// Addresses are matched exactly, so each address of a wallet that should be
+/// screened must be listed separately.  In a file, the lists look like:
+///
+/// ```toml
+/// denied_addresses = ["penumbra1..."]
+/// confirm_addresses = ["penumbra1..."]
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScreeningList {
+    /// Addresses funds must never be sent to.
+    #[serde(default, with = "address_as_string")]
+    pub denied_addresses: Vec<Address>,
+    /// Addresses funds may only be sent to after explicit confirmation.
+    #[serde(default, with = "address_as_string")]
+    pub confirm_addresses: Vec<Address>,
+}
+
+impl ScreeningList {
+    /// Loads a screening list from a TOML file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("could not read screening list {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("could not parse screening list {}", path.display()))
+    }
+}
+
+impl AddressScreen for ScreeningList {
+    fn screen(&self, address: &Address) -> Verdict {
+        if self.denied_addresses.contains(address) {
+            Verdict::Deny
+        } else if self.confirm_addresses.contains(address) {
+            Verdict::Confirm
+        } else {
+            Verdict::Allow
+        }
+    }
+}
+
+/// Configuration for address screening, as it appears in a config file.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScreeningConfig {
+    /// The path to a [`ScreeningList`].
+    pub list_file: PathBuf,
+    /// The path to an [`AuditLog`] recording flagged destinations, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_log: Option<PathBuf>,
+}
+
+impl ScreeningConfig {
+    /// Loads the configured list, returning a [`Screener`] for it.
+    pub fn screener(&self) -> anyhow::Result<Screener> {
+        let mut screener = Screener::new(ScreeningList::load(&self.list_file)?);
+        if let Some(audit_log) = &self.audit_log {
+            screener = screener.with_audit_log(AuditLog::new(audit_log));
+        }
+        Ok(screener)
+    }
+}
+
+/// What happened to a flagged destination.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    /// The destination is denied, so the transaction was rejected.
+    Denied,
+    /// The destination requires confirmation, which has been requested.
+    ConfirmationRequired,
+    /// Sending to the destination was confirmed.
+    Confirmed,
+    /// Sending to the destination was not confirmed, so the transaction was
+    /// rejected.
+    Declined,
+}
+
+/// A record of a decision about a flagged destination.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    /// When the decision was made, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// What was screening the transaction, e.g. `planner` or `custody`.
+    pub context: String,
+    /// The flagged destination.
+    #[serde_as(as = "DisplayFromStr")]
+    pub address: Address,
+    /// The decision made about it.
+    pub decision: Decision,
+}
+
+/// An append-only audit trail of screening decisions, stored as JSON lines.
+///
+/// Only flagged destinations are recorded, so the audit log does not become
+/// a history of every transaction made by the wallet.
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Uses the file at `path` as an audit log, creating it if needed.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Appends an entry to the audit log.
+    pub fn record(&self, entry: &AuditEntry) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&line))
+            .with_context(|| format!("could not write to audit log {}", self.path.display()))
+    }
+
+    /// Reads back all the entries in the audit log.
+    pub fn entries(&self) -> anyhow::Result<Vec<AuditEntry>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("could not read audit log {}", self.path.display()))
+            }
+        };
+        BufReader::new(file)
+            .lines()
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+}
+
+/// A function asking whether funds may be sent to the given destinations.
+type ConfirmFn = dyn Fn(&[Address]) -> bool + Send + Sync;
+
+/// Screens the destinations of transaction plans.
+#[derive(Clone)]
+pub struct Screener {
+    screen: Arc<dyn AddressScreen>,
+    audit_log: Option<AuditLog>,
+    confirm: Option<Arc<ConfirmFn>>,
+}
+
+impl Debug for Screener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Screener")
+            .field("audit_log", &self.audit_log)
+            .field("confirm", &self.confirm.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Screener {
+    /// Creates a screener applying the given screen, without an audit log.
+    ///
+    /// Destinations requiring confirmation are rejected, unless a way to
+    /// confirm them is added with [`Screener::with_confirmation`].
+    pub fn new(screen: impl AddressScreen + 'static) -> Self {
+        Self {
+            screen: Arc::new(screen),
+            audit_log: None,
+            confirm: None,
+        }
+    }
+
+    /// Records flagged destinations in the given audit log.
+    pub fn with_audit_log(mut self, audit_log: AuditLog) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Uses `confirm` to ask whether funds may be sent to destinations that
+    /// require confirmation, e.g. by prompting the user.
+    pub fn with_confirmation(
+        mut self,
+        confirm: impl Fn(&[Address]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.confirm = Some(Arc::new(confirm));
+        self
+    }
+
+    /// Screens the destinations of `plan`, failing if any of them are
+    /// denied, and otherwise returning those that require confirmation.
+    ///
+    /// This does not ask for confirmation; the caller is responsible for
+    /// obtaining it, and recording the outcome with
+    /// [`Screener::record_confirmation`].  The `context` identifies the caller
+    /// in the audit log.
+    pub fn screen_plan(
+        &self,
+        plan: &TransactionPlan,
+        context: &str,
+    ) -> anyhow::Result<Vec<Address>> {
+        let mut denied = Vec::new();
+        let mut to_confirm = Vec::new();
+        for address in destinations(plan) {
+            let flagged = match self.screen.screen(&address) {
+                Verdict::Allow => continue,
+                Verdict::Confirm => &mut to_confirm,
+                Verdict::Deny => &mut denied,
+            };
+            if !flagged.contains(&address) {
+                flagged.push(address);
+            }
+        }
+
+        if !denied.is_empty() {
+            self.record(&denied, context, Decision::Denied)?;
+            anyhow::bail!(
+                "transaction sends funds to {} denied address(es): {}",
+                denied.len(),
+                display_addresses(&denied),
+            );
+        }
+        self.record(&to_confirm, context, Decision::ConfirmationRequired)?;
+
+        Ok(to_confirm)
+    }
+
+    /// Records whether sending to the given destinations was confirmed.
+    pub fn record_confirmation(
+        &self,
+        addresses: &[Address],
+        context: &str,
+        confirmed: bool,
+    ) -> anyhow::Result<()> {
+        let decision = if confirmed {
+            Decision::Confirmed
+        } else {
+            Decision::Declined
+        };
+        self.record(addresses, context, decision)
+    }
+
+    /// Screens the destinations of `plan`, asking for confirmation where
+    /// required, and fails unless all of them are allowed.
+    pub fn check_plan(&self, plan: &TransactionPlan, context: &str) -> anyhow::Result<()> {
+        let to_confirm = self.screen_plan(plan, context)?;
+        if to_confirm.is_empty() {
+            return Ok(());
+        }
+
+        let confirmed = self
+            .confirm
+            .as_ref()
+            .map(|confirm| confirm(&to_confirm))
+            .unwrap_or(false);
+        self.record_confirmation(&to_confirm, context, confirmed)?;
+
+        if !confirmed {
+            anyhow::bail!(
+                "sending funds to {} was not confirmed",
+                display_addresses(&to_confirm),
+            );
+        }
+        Ok(())
+    }
+
+    fn record(
+        &self,
+        addresses: &[Address],
+        context: &str,
+        decision: Decision,
+    ) -> anyhow::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        for address in addresses {
+            tracing::info!(%address, ?decision, context, "screened transaction destination");
+            let Some(audit_log) = &self.audit_log else {
+                continue;
+            };
+            audit_log.record(&AuditEntry {
+                timestamp,
+                context: context.to_owned(),
+                address: *address,
+                decision,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// The addresses a transaction plan sends funds to.
+///
+/// This includes the recipients of outputs (including change) and the claim
+/// addresses of swaps.  The destinations of ICS-20 withdrawals are addresses
+/// on other chains, and are not screened.
+pub fn destinations(plan: &TransactionPlan) -> impl Iterator<Item = Address> + '_ {
+    plan.output_plans().map(|output| output.dest_address).chain(
+        plan.swap_plans()
+            .map(|swap| swap.swap_plaintext.claim_address),
+    )
+}
+
+fn display_addresses(addresses: &[Address]) -> String {
+    addresses
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::{Value, STAKING_TOKEN_ASSET_ID};
+    use penumbra_keys::test_keys;
+    use penumbra_shielded_pool::OutputPlan;
+    use penumbra_transaction::plan::ActionPlan;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    fn plan_sending_to(addresses: &[Address]) -> TransactionPlan {
+        TransactionPlan {
+            actions: addresses
+                .iter()
+                .map(|address| {
+                    let value = Value {
+                        amount: 1u64.into(),
+                        asset_id: *STAKING_TOKEN_ASSET_ID,
+                    };
+                    ActionPlan::Output(OutputPlan::new(&mut OsRng, value, *address))
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn list() -> ScreeningList {
+        ScreeningList {
+            denied_addresses: vec![*test_keys::ADDRESS_1],
+            confirm_addresses: vec![*test_keys::ADDRESS_0],
+        }
+    }
+
+    #[test]
+    fn screening_list_toml_round_trip() {
+        let list = list();
+        let encoded = toml::to_string_pretty(&list).unwrap();
+        assert_eq!(toml::from_str::<ScreeningList>(&encoded).unwrap(), list);
+    }
+
+    #[test]
+    fn denied_destinations_are_rejected_and_audited() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let audit_log = AuditLog::new(dir.path().join("audit.jsonl"));
+        let screener = Screener::new(list())
+            .with_audit_log(audit_log.clone())
+            .with_confirmation(|_| true);
+
+        let plan = plan_sending_to(&[*test_keys::ADDRESS_1, *test_keys::ADDRESS_1]);
+        assert!(screener.check_plan(&plan, "test").is_err());
+
+        let entries = audit_log.entries()?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].address, *test_keys::ADDRESS_1);
+        assert_eq!(entries[0].decision, Decision::Denied);
+        assert_eq!(entries[0].context, "test");
+
+        Ok(())
+    }
+
+    #[test]
+    fn destinations_require_confirmation() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let audit_log = AuditLog::new(dir.path().join("audit.jsonl"));
+        let plan = plan_sending_to(&[*test_keys::ADDRESS_0]);
+
+        // Without a way to confirm, the plan is rejected.
+        let screener = Screener::new(list()).with_audit_log(audit_log.clone());
+        assert!(screener.check_plan(&plan, "test").is_err());
+        assert!(screener
+            .clone()
+            .with_confirmation(|_| false)
+            .check_plan(&plan, "test")
+            .is_err());
+        screener
+            .with_confirmation(|addresses| addresses == [*test_keys::ADDRESS_0].as_slice())
+            .check_plan(&plan, "test")?;
+
+        let decisions = audit_log
+            .entries()?
+            .into_iter()
+            .map(|entry| entry.decision)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decisions,
+            [
+                Decision::ConfirmationRequired,
+                Decision::Declined,
+                Decision::ConfirmationRequired,
+                Decision::Declined,
+                Decision::ConfirmationRequired,
+                Decision::Confirmed,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn allowed_destinations_are_not_audited() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let audit_log = AuditLog::new(dir.path().join("audit.jsonl"));
+        let screener = Screener::new(ScreeningList::default()).with_audit_log(audit_log.clone());
+
+        screener.check_plan(&plan_sending_to(&[*test_keys::ADDRESS_0]), "test")?;
+        assert!(audit_log.entries()?.is_empty());
+
+        Ok(())
+    }
+}
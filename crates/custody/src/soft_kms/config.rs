@@ -66,6 +66,14 @@ mod tests {
                 required_signatures: 1,
                 allowed_signers: vec![pvk],
             }),
+            AuthPolicy::AddressScreening {
+                list_file: "screening.toml".into(),
+                audit_log: Some("screening-audit.jsonl".into()),
+                confirmation: Some(PreAuthorizationPolicy::Ed25519 {
+                    required_signatures: 1,
+                    allowed_signers: vec![pvk],
+                }),
+            },
         ];
 
         let example = Config {
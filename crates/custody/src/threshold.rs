@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use ed25519_consensus::VerificationKey;
 use rand_core::OsRng;
 use serde::{Deserialize, Serialize};
 use tonic::{async_trait, Request, Response, Status};
@@ -14,6 +15,7 @@ pub use self::sign::{SigningRequest, SigningResponse};
 
 mod config;
 mod dkg;
+mod refresh;
 mod sign;
 
 fn to_json<T>(data: &T) -> Result<String>
@@ -187,6 +189,133 @@ pub async fn dkg(t: u16, n: u16, terminal: &impl Terminal) -> Result<Config> {
     dkg::round3(&mut OsRng, state, round2_replies)
 }
 
+/// A proactive refresh of the signing shares in a config, producing a new config.
+///
+/// The new config has the same FVK and group key, but a fresh signing share, so that shares
+/// leaked before the refresh can't be combined with those from after it. All of the participants
+/// in the config need to take part.
+///
+/// This returns `None` if the `coordinator`, identified by its verification key, called off the
+/// refresh with [`skip_refresh`]; without a coordinator, the refresh can't be called off.
+/// Otherwise, the new config needs to be stored, alongside the old one, before calling
+/// [`acknowledge_refresh`]: only once everyone has acknowledged it is the old config safe to
+/// discard.
+///
+/// This takes in a terminal, because it requires interacting with the other participants.
+pub async fn refresh(
+    config: &Config,
+    coordinator: Option<&VerificationKey>,
+    terminal: &impl Terminal,
+) -> Result<Option<Config>> {
+    if let Some(coordinator) = coordinator {
+        if !config.verification_keys().contains(coordinator) {
+            anyhow::bail!("the coordinator is not one of the participants");
+        }
+    }
+    let expected_responses = config.verification_keys().len().saturating_sub(1);
+    // Round 1 top
+    let (round1_message, state) = refresh::round1(&mut OsRng, config)?;
+    terminal
+        .explain("Round 1/3: Send this message to all other participants:")
+        .await?;
+    terminal.broadcast(&to_json(&round1_message)?).await?;
+    // Round 1 bottom
+    terminal
+        .explain(&format!(
+            "Round 1/3: Gather {expected_responses} messages from the other participants:"
+        ))
+        .await?;
+    let round1_replies = {
+        let mut acc: Vec<refresh::Round1> = Vec::new();
+        while acc.len() < expected_responses {
+            let string = terminal
+                .next_response()
+                .await?
+                .ok_or(anyhow!("expected message from another participant"))?;
+            acc.push(from_json(&string)?);
+        }
+        acc
+    };
+
+    // Round 2 top
+    let Some((round2_message, state)) =
+        refresh::round2(&mut OsRng, config, coordinator, state, round1_replies)?
+    else {
+        terminal
+            .explain("The coordinator called off the refresh.")
+            .await?;
+        return Ok(None);
+    };
+    terminal
+        .explain("Round 2/3: Send this message to all other participants:")
+        .await?;
+    terminal.broadcast(&to_json(&round2_message)?).await?;
+    // Round 2 bottom
+    terminal
+        .explain(&format!(
+            "Round 2/3: Gather {expected_responses} messages from the other participants:"
+        ))
+        .await?;
+    let round2_replies = {
+        let mut acc: Vec<refresh::Round2> = Vec::new();
+        while acc.len() < expected_responses {
+            let string = terminal
+                .next_response()
+                .await?
+                .ok_or(anyhow!("expected message from another participant"))?;
+            acc.push(from_json(&string)?);
+        }
+        acc
+    };
+    refresh::round3(&mut OsRng, config, state, round2_replies).map(Some)
+}
+
+/// Call off a refresh as its coordinator, telling the other participants not to go ahead with it.
+///
+/// This is sent in place of our first round message, so the other participants should run
+/// [`refresh`] as usual.
+pub async fn skip_refresh(config: &Config, terminal: &impl Terminal) -> Result<()> {
+    let round1_message = refresh::skip_round1(&mut OsRng, config);
+    terminal
+        .explain("Round 1/3: Send this message to all other participants, to call off the refresh:")
+        .await?;
+    terminal.broadcast(&to_json(&round1_message)?).await?;
+    Ok(())
+}
+
+/// Acknowledge that we've stored the config produced by [`refresh`], and wait for every other
+/// participant to acknowledge theirs.
+///
+/// Once this succeeds, all of the participants hold consistent refreshed shares, and the config
+/// from before the refresh can be discarded.
+pub async fn acknowledge_refresh(refreshed: &Config, terminal: &impl Terminal) -> Result<()> {
+    let expected_responses = refreshed.verification_keys().len().saturating_sub(1);
+    // Round 3 top
+    let acknowledgement = refresh::acknowledge(refreshed);
+    terminal
+        .explain("Round 3/3: Send this acknowledgement to all other participants:")
+        .await?;
+    terminal.broadcast(&to_json(&acknowledgement)?).await?;
+    // Round 3 bottom
+    terminal
+        .explain(&format!(
+            "Round 3/3: Gather {expected_responses} acknowledgements from the other participants:"
+        ))
+        .await?;
+    let acknowledgements = {
+        let mut acc: Vec<refresh::Acknowledgement> = Vec::new();
+        while acc.len() < expected_responses {
+            let string = terminal
+                .next_response()
+                .await?
+                .ok_or(anyhow!("expected message from another participant"))?;
+            acc.push(from_json(&string)?);
+        }
+        acc
+    };
+    refresh::check_acknowledgements(refreshed, acknowledgements)
+}
+
 /// A custody backend using threshold signing.
 ///
 /// This backend is initialized with a full viewing key, but only a share
@@ -547,9 +676,108 @@ mod test {
         Ok(())
     }
 
+    async fn run_refresh(configs: Vec<Config>) -> Result<Vec<Config>> {
+        let terminals = make_symmetric_terminals(configs.len());
+        let mut handles = Vec::new();
+        for (config, terminal) in configs.into_iter().zip(terminals) {
+            handles.push(tokio::spawn(async move {
+                let refreshed = refresh(&config, None, &terminal)
+                    .await?
+                    .ok_or(anyhow!("refresh was called off"))?;
+                acknowledge_refresh(&refreshed, &terminal).await?;
+                Ok::<_, anyhow::Error>(refreshed)
+            }));
+        }
+        let mut out = Vec::new();
+        for handle in handles {
+            out.push(handle.await??);
+        }
+        Ok(out)
+    }
+
     #[tokio::test]
-    async fn test_transaction_signing() -> Result<()> {
-        const TEST_PLAN: &'static str = r#"
+    async fn test_refresh_preserves_group_key() -> Result<()> {
+        const T: u16 = 2;
+        const N: u16 = 3;
+        let configs = run_dkg(T, N).await?;
+        let refreshed = run_refresh(configs.clone()).await?;
+
+        for (old, new) in configs.iter().zip(refreshed.iter()) {
+            assert_eq!(old.fvk(), new.fvk());
+            assert_eq!(new.refresh_epoch(), 1);
+            assert_ne!(
+                old.key_package().secret_share(),
+                new.key_package().secret_share()
+            );
+        }
+
+        // Any threshold of the new shares can still sign for the same key.
+        sign_test_plan(refreshed[0].clone(), vec![refreshed[1].clone()]).await?;
+        sign_test_plan(refreshed[2].clone(), vec![refreshed[0].clone()]).await?;
+
+        // But a share from before the refresh can't be combined with one from after it.
+        assert!(
+            sign_test_plan(refreshed[0].clone(), vec![configs[1].clone()])
+                .await
+                .is_err(),
+            "signing with a mix of old and new shares should fail"
+        );
+        Ok(())
+    }
+
+    /// Has one participant call off a refresh, while the others run it, trusting the participant
+    /// that `pick_coordinator` picks out of everyone's verification keys to call it off.
+    async fn run_skipped_refresh(
+        pick_coordinator: impl Fn(&[VerificationKey]) -> VerificationKey,
+    ) -> Result<Vec<Result<Option<Config>>>> {
+        const T: u16 = 2;
+        const N: u16 = 3;
+        let mut configs = run_dkg(T, N).await?;
+        let mut terminals = make_symmetric_terminals(N as usize);
+        let vks: Vec<_> = configs
+            .iter()
+            .map(|config| config.signing_key().verification_key())
+            .collect();
+        let coordinator = pick_coordinator(&vks);
+        let skipping_config = configs.remove(0);
+        let skipping_terminal = terminals.remove(0);
+
+        let mut handles = Vec::new();
+        for (config, terminal) in configs.into_iter().zip(terminals) {
+            handles.push(tokio::spawn(async move {
+                let refreshed = refresh(&config, Some(&coordinator), &terminal).await;
+                // Keep our terminal alive until everyone is done sending to it.
+                (refreshed, terminal)
+            }));
+        }
+        skip_refresh(&skipping_config, &skipping_terminal).await?;
+        let mut out = Vec::new();
+        for handle in handles {
+            out.push(handle.await?.0);
+        }
+        Ok(out)
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_can_skip_refresh() -> Result<()> {
+        for refreshed in run_skipped_refresh(|vks| vks[0]).await? {
+            assert!(refreshed?.is_none(), "refresh should have been called off");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_only_coordinator_can_skip_refresh() -> Result<()> {
+        for refreshed in run_skipped_refresh(|vks| vks[1]).await? {
+            assert!(
+                refreshed.is_err(),
+                "only the coordinator should be able to call off the refresh"
+            );
+        }
+        Ok(())
+    }
+
+    const TEST_PLAN: &'static str = r#"
 {
     "actions": [
         {
@@ -646,14 +874,14 @@ mod test {
     }
 }
         "#;
-        const T: u16 = 3;
-        const N: u16 = 3;
 
-        let (coordinator_config, follower_configs) = {
-            let mut configs = run_dkg(T, N).await?;
-            (configs.pop().unwrap(), configs)
-        };
-        let (coordinator_terminal, follower_terminals) = make_terminals((N - 1) as usize);
+    /// Sign a test transaction plan, with `coordinator_config` coordinating and each of
+    /// `follower_configs` following, and check the resulting signatures.
+    async fn sign_test_plan(
+        coordinator_config: Config,
+        follower_configs: Vec<Config>,
+    ) -> Result<()> {
+        let (coordinator_terminal, follower_terminals) = make_terminals(follower_configs.len());
         for (config, terminal) in follower_configs
             .into_iter()
             .zip(follower_terminals.into_iter())
@@ -691,4 +919,16 @@ mod test {
         }
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_transaction_signing() -> Result<()> {
+        const T: u16 = 3;
+        const N: u16 = 3;
+
+        let (coordinator_config, follower_configs) = {
+            let mut configs = run_dkg(T, N).await?;
+            (configs.pop().unwrap(), configs)
+        };
+        sign_test_plan(coordinator_config, follower_configs).await
+    }
 }
@@ -1,13 +1,16 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use ark_ff::UniformRand;
-use decaf377::Fq;
+use decaf377::{Element, Fq, Fr};
 use decaf377_frost as frost;
 use ed25519_consensus::{SigningKey, VerificationKey};
 use penumbra_keys::{keys::NullifierKey, FullViewingKey};
 use rand_core::CryptoRngCore;
 use serde::{Deserialize, Serialize};
 use serde_with::{formats::Uppercase, hex::Hex, DisplayFromStr, TryFromInto};
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 /// A shim to serialize frost::keys::SigningShare
 #[serde_as]
@@ -99,6 +102,19 @@ pub struct Config {
         as = "HashMap<TryFromInto<VerificationKeyWrapper>, TryFromInto<VerifyingShareWrapper>>"
     )]
     verifying_shares: HashMap<VerificationKey, frost::keys::VerifyingShare>,
+    /// The number of times the signing shares have been refreshed.
+    #[serde(default)]
+    refresh_epoch: u64,
+    /// When the signing share was created or last refreshed, in seconds since the UNIX epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_refresh: Option<u64>,
+}
+
+fn now_unix_secs() -> Option<u64> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
 }
 
 impl PartialEq for Config {
@@ -109,6 +125,8 @@ impl PartialEq for Config {
             // TIMING LEAK
             && self.signing_key.as_bytes() == other.signing_key.as_bytes()
             && self.verifying_shares == other.verifying_shares
+            && self.refresh_epoch == other.refresh_epoch
+            && self.last_refresh == other.last_refresh
     }
 }
 
@@ -147,6 +165,8 @@ impl Config {
             spend_key_share,
             signing_key,
             verifying_shares,
+            refresh_epoch: 0,
+            last_refresh: now_unix_secs(),
         }
     }
 
@@ -191,6 +211,7 @@ impl Config {
                 .expect("conversion of a group element to a VerifyingKey should not fail"),
             NullifierKey(Fq::rand(rng)),
         );
+        let last_refresh = now_unix_secs();
 
         Ok(signing_keys
             .into_iter()
@@ -203,6 +224,8 @@ impl Config {
                     fvk: fvk.clone(),
                     spend_key_share: signing_share,
                     verifying_shares: verifying_shares.clone(),
+                    refresh_epoch: 0,
+                    last_refresh,
                 }
             })
             .collect())
@@ -259,6 +282,91 @@ impl Config {
     pub fn verification_keys(&self) -> HashSet<VerificationKey> {
         self.verifying_shares.keys().cloned().collect()
     }
+
+    /// A hash of every participant's verifying share, along with the group key.
+    ///
+    /// Participants compare this after a refresh, to check that they all ended up with
+    /// consistent shares.
+    pub(crate) fn verifying_shares_hash(&self) -> [u8; 32] {
+        let mut shares: Vec<_> = self.verifying_shares.iter().collect();
+        shares.sort_by_key(|(vk, _)| vk.to_bytes());
+        let mut state = blake2b_simd::Params::new()
+            .personal(b"verifying-shares")
+            .to_state();
+        state.update(&self.fvk.spend_verification_key().to_bytes());
+        for (vk, share) in shares {
+            state.update(&vk.to_bytes());
+            state.update(share.serialize().as_slice());
+        }
+        state.finalize().as_array()[..32]
+            .try_into()
+            .expect("array conversion should not fail")
+    }
+
+    /// The number of times the signing shares have been refreshed.
+    pub fn refresh_epoch(&self) -> u64 {
+        self.refresh_epoch
+    }
+
+    /// Whether the signing share was last created or refreshed at least `interval` before `now`.
+    ///
+    /// Configs which predate share refreshes don't record this, so are always due.
+    pub fn refresh_due(&self, interval: Duration, now: SystemTime) -> bool {
+        let Some(last_refresh) = self.last_refresh else {
+            return true;
+        };
+        now.duration_since(UNIX_EPOCH + Duration::from_secs(last_refresh))
+            .map_or(false, |age| age >= interval)
+    }
+
+    /// Create the config resulting from a share refresh, by adding `share_delta` to our signing
+    /// share and each of `verifying_share_deltas` to the corresponding verifying share.
+    ///
+    /// The group key, and so the FVK, is unchanged.
+    pub(crate) fn refreshed(
+        &self,
+        refresh_epoch: u64,
+        share_delta: Fr,
+        verifying_share_deltas: &HashMap<VerificationKey, Element>,
+    ) -> Result<Self> {
+        let spend_key_share = {
+            let share = Fr::from_bytes(self.spend_key_share.serialize().as_slice().try_into()?)
+                .map_err(|_| anyhow!("invalid signing share"))?;
+            frost::keys::SigningShare::deserialize((share + share_delta).to_bytes().to_vec())?
+        };
+        let verifying_shares = self
+            .verifying_shares
+            .iter()
+            .map(|(vk, share)| {
+                let delta = verifying_share_deltas
+                    .get(vk)
+                    .ok_or_else(|| anyhow!("missing verifying share delta for participant"))?;
+                let share = decaf377::Encoding(share.serialize().as_slice().try_into()?)
+                    .vartime_decompress()
+                    .map_err(|_| anyhow!("invalid verifying share"))?;
+                let refreshed = (share + delta).vartime_compress().0.to_vec();
+                Ok((*vk, frost::keys::VerifyingShare::deserialize(refreshed)?))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        // Our refreshed signing share must still match the verifying share everyone else has for us.
+        let ours = verifying_shares
+            .get(&self.signing_key.verification_key())
+            .ok_or_else(|| anyhow!("missing our own verifying share"))?;
+        if frost::keys::VerifyingShare::from(spend_key_share) != *ours {
+            anyhow::bail!("refreshed signing share does not match its verifying share");
+        }
+
+        Ok(Self {
+            threshold: self.threshold,
+            fvk: self.fvk.clone(),
+            spend_key_share,
+            signing_key: self.signing_key.clone(),
+            verifying_shares,
+            refresh_epoch,
+            last_refresh: now_unix_secs(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -5,7 +5,7 @@ use decaf377::Fq;
 use decaf377_frost as frost;
 use frost::keys::dkg as frost_dkg;
 use std::collections::{HashMap, HashSet};
-mod encryption;
+pub(super) mod encryption;
 use ed25519_consensus::{Signature, SigningKey, VerificationKey};
 use encryption::EncryptionKey;
 use penumbra_proto::{custody::threshold::v1 as pb, DomainType, Message};
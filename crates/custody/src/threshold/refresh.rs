@@ -0,0 +1,581 @@
+//! Proactive refresh of the signing shares of an existing threshold key.
+//!
+//! Each participant deals a random polynomial of degree `t - 1` whose constant term is zero,
+//! sending each other participant its evaluation at their identifier. Adding up the evaluations
+//! received re-randomizes every share, while leaving the group key, and so the FVK, unchanged.
+//! Shares from before a refresh can't be combined with shares from after it, which limits how
+//! long a single compromised share is useful for.
+//!
+//! Every participant has to take part, since the share of anyone left out would no longer be
+//! consistent with the others. For the same reason, participants acknowledge once they've stored
+//! their refreshed share, and should keep their old share until everyone else has too.
+use anyhow::{anyhow, Result};
+use ark_ff::UniformRand;
+use decaf377::{Element, Fr};
+use decaf377_frost as frost;
+use ed25519_consensus::{Signature, SigningKey, VerificationKey};
+use penumbra_proto::{custody::threshold::v1 as pb, DomainType, Message};
+use rand_core::CryptoRngCore;
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    dkg::encryption::{DecryptionKey, EncryptionKey},
+    Config,
+};
+
+/// The scalar at which the polynomials get evaluated for a given participant.
+fn identifier_scalar(vk: &VerificationKey) -> Result<Fr> {
+    let id = frost::Identifier::derive(vk.as_bytes())?;
+    decode_scalar(&id.serialize())
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<Fr> {
+    Fr::from_bytes(bytes.try_into()?).map_err(|_| anyhow!("invalid scalar"))
+}
+
+fn decode_element(bytes: &[u8]) -> Result<Element> {
+    decaf377::Encoding(bytes.try_into()?)
+        .vartime_decompress()
+        .map_err(|_| anyhow!("invalid group element"))
+}
+
+/// Evaluate the polynomial with constant term zero, and the given higher coefficients, at `x`.
+fn evaluate_polynomial(coefficients: &[Fr], x: Fr) -> Fr {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Fr::from(0u64), |acc, c| (acc + *c) * x)
+}
+
+/// Evaluate the commitment to a polynomial with constant term zero at `x`.
+fn evaluate_commitment(commitments: &[Element], x: Fr) -> Element {
+    commitments
+        .iter()
+        .rev()
+        .fold(Element::default(), |acc, c| (acc + *c) * x)
+}
+
+/// The message we send in round 1 of the refresh protocol.
+#[derive(Clone)]
+pub struct Round1 {
+    /// The refresh epoch this message belongs to.
+    epoch: u64,
+    /// Commitments to the non-constant coefficients of our polynomial.
+    commitments: Vec<Element>,
+    /// An encryption key to receive the encrypted shares in round 2.
+    epk: EncryptionKey,
+    /// Whether the coordinator called off the refresh.
+    skip: bool,
+    /// Our identity.
+    vk: VerificationKey,
+    /// A signature over the rest of the message.
+    sig: Signature,
+}
+
+fn round1_inner_to_pb(
+    epoch: u64,
+    commitments: &[Element],
+    epk: &EncryptionKey,
+    skip: bool,
+) -> pb::refresh_round1::Inner {
+    pb::refresh_round1::Inner {
+        epoch,
+        commitments: commitments
+            .iter()
+            .map(|c| c.vartime_compress().0.to_vec())
+            .collect(),
+        epk: epk.as_bytes().to_vec(),
+        skip,
+    }
+}
+
+impl From<Round1> for pb::RefreshRound1 {
+    fn from(value: Round1) -> Self {
+        Self {
+            inner: Some(round1_inner_to_pb(
+                value.epoch,
+                &value.commitments,
+                &value.epk,
+                value.skip,
+            )),
+            vk: value.vk.as_bytes().to_vec(),
+            sig: value.sig.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<pb::RefreshRound1> for Round1 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: pb::RefreshRound1) -> std::result::Result<Self, Self::Error> {
+        let inner = value.inner.ok_or(anyhow!("RefreshRound1 missing inner"))?;
+        Ok(Self {
+            epoch: inner.epoch,
+            commitments: inner
+                .commitments
+                .iter()
+                .map(|c| decode_element(c))
+                .collect::<Result<_>>()?,
+            epk: inner.epk.as_slice().try_into()?,
+            skip: inner.skip,
+            vk: value.vk.as_slice().try_into()?,
+            sig: value.sig.as_slice().try_into()?,
+        })
+    }
+}
+
+impl DomainType for Round1 {
+    type Proto = pb::RefreshRound1;
+}
+
+impl Round1 {
+    fn make(
+        sk: &SigningKey,
+        epoch: u64,
+        commitments: Vec<Element>,
+        epk: EncryptionKey,
+        skip: bool,
+    ) -> Self {
+        let data = round1_inner_to_pb(epoch, &commitments, &epk, skip).encode_to_vec();
+        let sig = sk.sign(&data);
+        Self {
+            epoch,
+            commitments,
+            epk,
+            skip,
+            vk: sk.verification_key(),
+            sig,
+        }
+    }
+
+    fn verify(&self) -> Result<()> {
+        let data =
+            round1_inner_to_pb(self.epoch, &self.commitments, &self.epk, self.skip).encode_to_vec();
+        Ok(self.vk.verify(&self.sig, &data)?)
+    }
+}
+
+/// The message we send in round 2 of the refresh protocol.
+#[derive(Clone, Debug)]
+pub struct Round2 {
+    /// The refresh epoch this message belongs to.
+    epoch: u64,
+    /// A hash of the round 1 messages we saw.
+    transcript: [u8; 32],
+    /// For each other participant, a ciphertext containing their share of zero.
+    encrypted_shares: HashMap<VerificationKey, Vec<u8>>,
+    /// Our identity.
+    vk: VerificationKey,
+    /// A signature over the rest of the message.
+    sig: Signature,
+}
+
+fn round2_inner_to_pb(
+    epoch: u64,
+    transcript: [u8; 32],
+    encrypted_shares: HashMap<VerificationKey, Vec<u8>>,
+) -> pb::refresh_round2::Inner {
+    // Need to sort to guarantee a deterministic encoding for signing.
+    let encrypted_shares = {
+        let mut acc: Vec<_> = encrypted_shares
+            .into_iter()
+            .map(|(k, v)| pb::refresh_round2::TargetedShare {
+                vk: k.as_bytes().to_vec(),
+                encrypted_share: v,
+            })
+            .collect();
+        acc.sort_by_key(|x| x.vk.clone());
+        acc
+    };
+    pb::refresh_round2::Inner {
+        epoch,
+        transcript: transcript.to_vec(),
+        encrypted_shares,
+    }
+}
+
+impl From<Round2> for pb::RefreshRound2 {
+    fn from(value: Round2) -> Self {
+        Self {
+            inner: Some(round2_inner_to_pb(
+                value.epoch,
+                value.transcript,
+                value.encrypted_shares,
+            )),
+            vk: value.vk.as_bytes().to_vec(),
+            sig: value.sig.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<pb::RefreshRound2> for Round2 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: pb::RefreshRound2) -> std::result::Result<Self, Self::Error> {
+        let inner = value.inner.ok_or(anyhow!("RefreshRound2 missing inner"))?;
+        Ok(Self {
+            epoch: inner.epoch,
+            transcript: inner.transcript.as_slice().try_into()?,
+            encrypted_shares: inner
+                .encrypted_shares
+                .into_iter()
+                .map(|x| Ok((x.vk.as_slice().try_into()?, x.encrypted_share)))
+                .collect::<Result<HashMap<_, _>, Self::Error>>()?,
+            vk: value.vk.as_slice().try_into()?,
+            sig: value.sig.as_slice().try_into()?,
+        })
+    }
+}
+
+impl DomainType for Round2 {
+    type Proto = pb::RefreshRound2;
+}
+
+impl Round2 {
+    fn make(
+        sk: &SigningKey,
+        epoch: u64,
+        transcript: [u8; 32],
+        encrypted_shares: HashMap<VerificationKey, Vec<u8>>,
+    ) -> Self {
+        let data = round2_inner_to_pb(epoch, transcript, encrypted_shares.clone()).encode_to_vec();
+        let sig = sk.sign(&data);
+        Self {
+            epoch,
+            transcript,
+            encrypted_shares,
+            vk: sk.verification_key(),
+            sig,
+        }
+    }
+
+    fn verify(&self) -> Result<()> {
+        let data = round2_inner_to_pb(self.epoch, self.transcript, self.encrypted_shares.clone())
+            .encode_to_vec();
+        Ok(self.vk.verify(&self.sig, &data)?)
+    }
+}
+
+/// The message we send once we've stored our refreshed share.
+#[derive(Clone, Debug)]
+pub struct Acknowledgement {
+    /// The refresh epoch this message belongs to.
+    epoch: u64,
+    /// A hash of the verifying shares we ended up with.
+    verifying_shares_hash: [u8; 32],
+    /// Our identity.
+    vk: VerificationKey,
+    /// A signature over the rest of the message.
+    sig: Signature,
+}
+
+fn acknowledgement_inner_to_pb(
+    epoch: u64,
+    verifying_shares_hash: [u8; 32],
+) -> pb::refresh_acknowledgement::Inner {
+    pb::refresh_acknowledgement::Inner {
+        epoch,
+        verifying_shares_hash: verifying_shares_hash.to_vec(),
+    }
+}
+
+impl From<Acknowledgement> for pb::RefreshAcknowledgement {
+    fn from(value: Acknowledgement) -> Self {
+        Self {
+            inner: Some(acknowledgement_inner_to_pb(
+                value.epoch,
+                value.verifying_shares_hash,
+            )),
+            vk: value.vk.as_bytes().to_vec(),
+            sig: value.sig.to_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<pb::RefreshAcknowledgement> for Acknowledgement {
+    type Error = anyhow::Error;
+
+    fn try_from(value: pb::RefreshAcknowledgement) -> std::result::Result<Self, Self::Error> {
+        let inner = value
+            .inner
+            .ok_or(anyhow!("RefreshAcknowledgement missing inner"))?;
+        Ok(Self {
+            epoch: inner.epoch,
+            verifying_shares_hash: inner.verifying_shares_hash.as_slice().try_into()?,
+            vk: value.vk.as_slice().try_into()?,
+            sig: value.sig.as_slice().try_into()?,
+        })
+    }
+}
+
+impl DomainType for Acknowledgement {
+    type Proto = pb::RefreshAcknowledgement;
+}
+
+impl Acknowledgement {
+    fn verify(&self) -> Result<()> {
+        let data =
+            acknowledgement_inner_to_pb(self.epoch, self.verifying_shares_hash).encode_to_vec();
+        Ok(self.vk.verify(&self.sig, &data)?)
+    }
+}
+
+/// Hash the round 1 messages of every participant, including our own.
+///
+/// Everyone checks that the others computed the same hash, so that nobody can be given a
+/// different set of commitments than everyone else.
+fn transcript(config: &Config, epoch: u64, messages: &[&Round1]) -> [u8; 32] {
+    let mut sorted = messages.to_vec();
+    sorted.sort_by_key(|m| m.vk.to_bytes());
+    let mut state = blake2b_simd::Params::new()
+        .personal(b"refresh-transcrp")
+        .to_state();
+    state.update(&config.fvk().spend_verification_key().to_bytes());
+    state.update(&epoch.to_le_bytes());
+    for m in sorted {
+        state.update(&m.vk.to_bytes());
+        for c in &m.commitments {
+            state.update(&c.vartime_compress().0);
+        }
+        state.update(m.epk.as_bytes());
+    }
+    state.finalize().as_array()[..32]
+        .try_into()
+        .expect("array conversion should not fail")
+}
+
+/// Check that `senders` are exactly the other participants in `config`.
+fn check_senders<'a>(
+    config: &Config,
+    senders: impl Iterator<Item = &'a VerificationKey>,
+) -> Result<()> {
+    let me = config.signing_key().verification_key();
+    let mut expected = config.verification_keys();
+    expected.remove(&me);
+    let mut seen = HashSet::new();
+    for vk in senders {
+        if !expected.contains(vk) {
+            anyhow::bail!("message from unknown or unexpected verification key");
+        }
+        if !seen.insert(*vk) {
+            anyhow::bail!("duplicate verification key in messages");
+        }
+    }
+    if seen.len() != expected.len() {
+        anyhow::bail!("missing messages from some participants");
+    }
+    Ok(())
+}
+
+/// The state we need to remember after round 1.
+pub struct Round1State {
+    /// The non-constant coefficients of our polynomial.
+    coefficients: Vec<Fr>,
+    /// Our own round 1 message, for the transcript.
+    round1: Round1,
+    /// We remember the new decryption key we've created, to decrypt the next round's shares.
+    edk: DecryptionKey,
+}
+
+/// The state we need to remember after round 2.
+pub struct Round2State {
+    /// Our share of our own polynomial.
+    own_share: Fr,
+    /// The transcript hash everyone else should agree with.
+    transcript: [u8; 32],
+    /// Everyone's commitments, including ours, to check the shares we receive.
+    commitments: HashMap<VerificationKey, Vec<Element>>,
+    /// We keep this, so that we can decrypt the shares.
+    edk: DecryptionKey,
+}
+
+pub fn round1(mut rng: impl CryptoRngCore, config: &Config) -> Result<(Round1, Round1State)> {
+    let epoch = config.refresh_epoch() + 1;
+    let t = usize::from(config.threshold());
+    let coefficients: Vec<Fr> = (1..t).map(|_| Fr::rand(&mut rng)).collect();
+    let commitments = coefficients
+        .iter()
+        .map(|c| *c * decaf377::basepoint())
+        .collect();
+    let edk = DecryptionKey::new(&mut rng);
+    let round1 = Round1::make(
+        config.signing_key(),
+        epoch,
+        commitments,
+        edk.public(),
+        false,
+    );
+    let state = Round1State {
+        coefficients,
+        round1: round1.clone(),
+        edk,
+    };
+    Ok((round1, state))
+}
+
+/// The round 1 message a coordinator sends to call off a refresh, instead of taking part.
+pub fn skip_round1(mut rng: impl CryptoRngCore, config: &Config) -> Round1 {
+    let epoch = config.refresh_epoch() + 1;
+    let edk = DecryptionKey::new(&mut rng);
+    Round1::make(config.signing_key(), epoch, Vec::new(), edk.public(), true)
+}
+
+/// Returns `None` if the `coordinator` called off the refresh in round 1.
+///
+/// Only the coordinator may call off the refresh, so this is an error if anyone else tries to, or
+/// if there is no coordinator.
+pub fn round2(
+    mut rng: impl CryptoRngCore,
+    config: &Config,
+    coordinator: Option<&VerificationKey>,
+    state: Round1State,
+    messages: Vec<Round1>,
+) -> Result<Option<(Round2, Round2State)>> {
+    let epoch = state.round1.epoch;
+    let expected_commitments = usize::from(config.threshold()) - 1;
+    check_senders(config, messages.iter().map(|m| &m.vk))?;
+    for m in &messages {
+        m.verify()?;
+        if m.epoch != epoch {
+            anyhow::bail!(
+                "round 1 message for refresh epoch {}, not {}",
+                m.epoch,
+                epoch
+            );
+        }
+    }
+    if let Some(m) = messages.iter().find(|m| m.skip) {
+        if Some(&m.vk) != coordinator {
+            anyhow::bail!("round 1 message calls off the refresh, but isn't from the coordinator");
+        }
+        return Ok(None);
+    }
+    for m in &messages {
+        if m.commitments.len() != expected_commitments {
+            anyhow::bail!("round 1 message has the wrong number of commitments");
+        }
+    }
+
+    let transcript = {
+        let mut all: Vec<_> = messages.iter().collect();
+        all.push(&state.round1);
+        transcript(config, epoch, &all)
+    };
+    let encrypted_shares = messages
+        .iter()
+        .map(|m| {
+            let share = evaluate_polynomial(&state.coefficients, identifier_scalar(&m.vk)?);
+            Ok((m.vk, m.epk.encrypt(&mut rng, &share.to_bytes())))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+    let round2 = Round2::make(config.signing_key(), epoch, transcript, encrypted_shares);
+
+    let me = config.signing_key().verification_key();
+    let own_share = evaluate_polynomial(&state.coefficients, identifier_scalar(&me)?);
+    let mut commitments: HashMap<_, _> = messages
+        .into_iter()
+        .map(|m| (m.vk, m.commitments))
+        .collect();
+    commitments.insert(me, state.round1.commitments);
+    let state = Round2State {
+        own_share,
+        transcript,
+        commitments,
+        edk: state.edk,
+    };
+    Ok(Some((round2, state)))
+}
+
+pub fn round3(
+    mut rng: impl CryptoRngCore,
+    config: &Config,
+    state: Round2State,
+    messages: Vec<Round2>,
+) -> Result<Config> {
+    let epoch = config.refresh_epoch() + 1;
+    let me = config.signing_key().verification_key();
+    let my_id = identifier_scalar(&me)?;
+    check_senders(config, messages.iter().map(|m| &m.vk))?;
+
+    let mut share_delta = state.own_share;
+    for m in messages {
+        m.verify()?;
+        if m.epoch != epoch {
+            anyhow::bail!(
+                "round 2 message for refresh epoch {}, not {}",
+                m.epoch,
+                epoch
+            );
+        }
+        if m.transcript != state.transcript {
+            anyhow::bail!("round 2 message was made from a different set of round 1 messages");
+        }
+        let my_ciphertext = m
+            .encrypted_shares
+            .get(&me)
+            .ok_or(anyhow!("no encrypted share for this recipient"))?;
+        let share = decode_scalar(&state.edk.decrypt(&mut rng, my_ciphertext)?)?;
+        let commitments = state
+            .commitments
+            .get(&m.vk)
+            .ok_or(anyhow!("unknown verification key in round 2 message"))?;
+        if share * decaf377::basepoint() != evaluate_commitment(commitments, my_id) {
+            anyhow::bail!("share of zero did not match the sender's commitments");
+        }
+        share_delta += share;
+    }
+
+    // Everyone's verifying share moves by the sum of the commitments evaluated at their identifier.
+    let verifying_share_deltas = config
+        .verification_keys()
+        .into_iter()
+        .map(|vk| {
+            let x = identifier_scalar(&vk)?;
+            let delta = state
+                .commitments
+                .values()
+                .map(|c| evaluate_commitment(c, x))
+                .fold(Element::default(), |acc, d| acc + d);
+            Ok((vk, delta))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    config.refreshed(epoch, share_delta, &verifying_share_deltas)
+}
+
+/// Acknowledge that we've stored `refreshed`, the config resulting from round 3.
+pub fn acknowledge(refreshed: &Config) -> Acknowledgement {
+    let epoch = refreshed.refresh_epoch();
+    let verifying_shares_hash = refreshed.verifying_shares_hash();
+    let data = acknowledgement_inner_to_pb(epoch, verifying_shares_hash).encode_to_vec();
+    let sk = refreshed.signing_key();
+    Acknowledgement {
+        epoch,
+        verifying_shares_hash,
+        vk: sk.verification_key(),
+        sig: sk.sign(&data),
+    }
+}
+
+/// Check that every other participant stored the same refresh of the shares as we did.
+///
+/// Only once this succeeds is it safe to discard the shares from before the refresh.
+pub fn check_acknowledgements(refreshed: &Config, messages: Vec<Acknowledgement>) -> Result<()> {
+    let epoch = refreshed.refresh_epoch();
+    let verifying_shares_hash = refreshed.verifying_shares_hash();
+    check_senders(refreshed, messages.iter().map(|m| &m.vk))?;
+    for m in messages {
+        m.verify()?;
+        if m.epoch != epoch {
+            anyhow::bail!(
+                "acknowledgement for refresh epoch {}, not {}",
+                m.epoch,
+                epoch
+            );
+        }
+        if m.verifying_shares_hash != verifying_shares_hash {
+            anyhow::bail!("a participant ended up with different verifying shares");
+        }
+    }
+    Ok(())
+}
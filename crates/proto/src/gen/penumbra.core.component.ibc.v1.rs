@@ -173,6 +173,9 @@ pub struct IbcParameters {
     /// Whether outbound ICS-20 transfers are enabled
     #[prost(bool, tag = "3")]
     pub outbound_ics20_transfers_enabled: bool,
+    /// Whether hosting ICS-27 interchain accounts is enabled
+    #[prost(bool, tag = "4")]
+    pub ics27_host_enabled: bool,
 }
 impl ::prost::Name for IbcParameters {
     const NAME: &'static str = "IbcParameters";
@@ -188,6 +191,9 @@ pub struct GenesisContent {
     /// IBC parameters.
     #[prost(message, optional, tag = "1")]
     pub ibc_params: ::core::option::Option<IbcParameters>,
+    /// Light clients to create at genesis, in order of client ID.
+    #[prost(message, repeated, tag = "2")]
+    pub clients: ::prost::alloc::vec::Vec<GenesisClient>,
 }
 impl ::prost::Name for GenesisContent {
     const NAME: &'static str = "GenesisContent";
@@ -196,3 +202,22 @@ impl ::prost::Name for GenesisContent {
         ::prost::alloc::format!("penumbra.core.component.ibc.v1.{}", Self::NAME)
     }
 }
+/// A light client of a counterparty chain, created at genesis.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GenesisClient {
+    /// The client state, which must be a Tendermint client state.
+    #[prost(message, optional, tag = "1")]
+    pub client_state: ::core::option::Option<::pbjson_types::Any>,
+    /// The consensus state at the client state's latest height, which must be a
+    /// Tendermint consensus state.
+    #[prost(message, optional, tag = "2")]
+    pub consensus_state: ::core::option::Option<::pbjson_types::Any>,
+}
+impl ::prost::Name for GenesisClient {
+    const NAME: &'static str = "GenesisClient";
+    const PACKAGE: &'static str = "penumbra.core.component.ibc.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.core.component.ibc.v1.{}", Self::NAME)
+    }
+}
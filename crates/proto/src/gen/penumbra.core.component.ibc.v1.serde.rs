@@ -701,6 +701,120 @@ impl<'de> serde::Deserialize<'de> for FungibleTokenPacketData {
         deserializer.deserialize_struct("penumbra.core.component.ibc.v1.FungibleTokenPacketData", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for GenesisClient {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.client_state.is_some() {
+            len += 1;
+        }
+        if self.consensus_state.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("penumbra.core.component.ibc.v1.GenesisClient", len)?;
+        if let Some(v) = self.client_state.as_ref() {
+            struct_ser.serialize_field("clientState", v)?;
+        }
+        if let Some(v) = self.consensus_state.as_ref() {
+            struct_ser.serialize_field("consensusState", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GenesisClient {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "client_state",
+            "clientState",
+            "consensus_state",
+            "consensusState",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ClientState,
+            ConsensusState,
+            __SkipField__,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "clientState" | "client_state" => Ok(GeneratedField::ClientState),
+                            "consensusState" | "consensus_state" => Ok(GeneratedField::ConsensusState),
+                            _ => Ok(GeneratedField::__SkipField__),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GenesisClient;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct penumbra.core.component.ibc.v1.GenesisClient")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<GenesisClient, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut client_state__ = None;
+                let mut consensus_state__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::ClientState => {
+                            if client_state__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientState"));
+                            }
+                            client_state__ = map_.next_value()?;
+                        }
+                        GeneratedField::ConsensusState => {
+                            if consensus_state__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("consensusState"));
+                            }
+                            consensus_state__ = map_.next_value()?;
+                        }
+                        GeneratedField::__SkipField__ => {
+                            let _ = map_.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(GenesisClient {
+                    client_state: client_state__,
+                    consensus_state: consensus_state__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("penumbra.core.component.ibc.v1.GenesisClient", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for GenesisContent {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -712,10 +826,16 @@ impl serde::Serialize for GenesisContent {
         if self.ibc_params.is_some() {
             len += 1;
         }
+        if !self.clients.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.ibc.v1.GenesisContent", len)?;
         if let Some(v) = self.ibc_params.as_ref() {
             struct_ser.serialize_field("ibcParams", v)?;
         }
+        if !self.clients.is_empty() {
+            struct_ser.serialize_field("clients", &self.clients)?;
+        }
         struct_ser.end()
     }
 }
@@ -728,11 +848,13 @@ impl<'de> serde::Deserialize<'de> for GenesisContent {
         const FIELDS: &[&str] = &[
             "ibc_params",
             "ibcParams",
+            "clients",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             IbcParams,
+            Clients,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -756,6 +878,7 @@ impl<'de> serde::Deserialize<'de> for GenesisContent {
                     {
                         match value {
                             "ibcParams" | "ibc_params" => Ok(GeneratedField::IbcParams),
+                            "clients" => Ok(GeneratedField::Clients),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -776,6 +899,7 @@ impl<'de> serde::Deserialize<'de> for GenesisContent {
                     V: serde::de::MapAccess<'de>,
             {
                 let mut ibc_params__ = None;
+                let mut clients__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::IbcParams => {
@@ -784,6 +908,12 @@ impl<'de> serde::Deserialize<'de> for GenesisContent {
                             }
                             ibc_params__ = map_.next_value()?;
                         }
+                        GeneratedField::Clients => {
+                            if clients__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clients"));
+                            }
+                            clients__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -791,6 +921,7 @@ impl<'de> serde::Deserialize<'de> for GenesisContent {
                 }
                 Ok(GenesisContent {
                     ibc_params: ibc_params__,
+                    clients: clients__.unwrap_or_default(),
                 })
             }
         }
@@ -814,6 +945,9 @@ impl serde::Serialize for IbcParameters {
         if self.outbound_ics20_transfers_enabled {
             len += 1;
         }
+        if self.ics27_host_enabled {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("penumbra.core.component.ibc.v1.IbcParameters", len)?;
         if self.ibc_enabled {
             struct_ser.serialize_field("ibcEnabled", &self.ibc_enabled)?;
@@ -824,6 +958,9 @@ impl serde::Serialize for IbcParameters {
         if self.outbound_ics20_transfers_enabled {
             struct_ser.serialize_field("outboundIcs20TransfersEnabled", &self.outbound_ics20_transfers_enabled)?;
         }
+        if self.ics27_host_enabled {
+            struct_ser.serialize_field("ics27HostEnabled", &self.ics27_host_enabled)?;
+        }
         struct_ser.end()
     }
 }
@@ -840,6 +977,8 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
             "inboundIcs20TransfersEnabled",
             "outbound_ics20_transfers_enabled",
             "outboundIcs20TransfersEnabled",
+            "ics27_host_enabled",
+            "ics27HostEnabled",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -847,6 +986,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
             IbcEnabled,
             InboundIcs20TransfersEnabled,
             OutboundIcs20TransfersEnabled,
+            Ics27HostEnabled,
             __SkipField__,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
@@ -872,6 +1012,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                             "ibcEnabled" | "ibc_enabled" => Ok(GeneratedField::IbcEnabled),
                             "inboundIcs20TransfersEnabled" | "inbound_ics20_transfers_enabled" => Ok(GeneratedField::InboundIcs20TransfersEnabled),
                             "outboundIcs20TransfersEnabled" | "outbound_ics20_transfers_enabled" => Ok(GeneratedField::OutboundIcs20TransfersEnabled),
+                            "ics27HostEnabled" | "ics27_host_enabled" => Ok(GeneratedField::Ics27HostEnabled),
                             _ => Ok(GeneratedField::__SkipField__),
                         }
                     }
@@ -894,6 +1035,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                 let mut ibc_enabled__ = None;
                 let mut inbound_ics20_transfers_enabled__ = None;
                 let mut outbound_ics20_transfers_enabled__ = None;
+                let mut ics27_host_enabled__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::IbcEnabled => {
@@ -914,6 +1056,12 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                             }
                             outbound_ics20_transfers_enabled__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::Ics27HostEnabled => {
+                            if ics27_host_enabled__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ics27HostEnabled"));
+                            }
+                            ics27_host_enabled__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::__SkipField__ => {
                             let _ = map_.next_value::<serde::de::IgnoredAny>()?;
                         }
@@ -923,6 +1071,7 @@ impl<'de> serde::Deserialize<'de> for IbcParameters {
                     ibc_enabled: ibc_enabled__.unwrap_or_default(),
                     inbound_ics20_transfers_enabled: inbound_ics20_transfers_enabled__.unwrap_or_default(),
                     outbound_ics20_transfers_enabled: outbound_ics20_transfers_enabled__.unwrap_or_default(),
+                    ics27_host_enabled: ics27_host_enabled__.unwrap_or_default(),
                 })
             }
         }
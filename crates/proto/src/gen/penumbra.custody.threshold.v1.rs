@@ -297,3 +297,161 @@ impl ::prost::Name for DkgRound2 {
         ::prost::alloc::format!("penumbra.custody.threshold.v1.{}", Self::NAME)
     }
 }
+/// The first message we broadcast when refreshing our signing shares.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RefreshRound1 {
+    #[prost(message, optional, tag = "1")]
+    pub inner: ::core::option::Option<refresh_round1::Inner>,
+    /// The verification key identifying the sender.
+    #[prost(bytes = "vec", tag = "2")]
+    pub vk: ::prost::alloc::vec::Vec<u8>,
+    /// A signature over the proto-encoded inner message.
+    #[prost(bytes = "vec", tag = "3")]
+    pub sig: ::prost::alloc::vec::Vec<u8>,
+}
+/// Nested message and enum types in `RefreshRound1`.
+pub mod refresh_round1 {
+    /// An inner message that will be signed.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Inner {
+        /// The refresh epoch this message belongs to.
+        #[prost(uint64, tag = "1")]
+        pub epoch: u64,
+        /// Commitments to the non-constant coefficients of a polynomial sharing zero.
+        #[prost(bytes = "vec", repeated, tag = "2")]
+        pub commitments: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+        /// An encryption key for the second round.
+        #[prost(bytes = "vec", tag = "3")]
+        pub epk: ::prost::alloc::vec::Vec<u8>,
+        /// Set by the coordinator to call off the refresh, in which case there are no commitments.
+        #[prost(bool, tag = "4")]
+        pub skip: bool,
+    }
+    impl ::prost::Name for Inner {
+        const NAME: &'static str = "Inner";
+        const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.custody.threshold.v1.RefreshRound1.{}", Self::NAME
+            )
+        }
+    }
+}
+impl ::prost::Name for RefreshRound1 {
+    const NAME: &'static str = "RefreshRound1";
+    const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.custody.threshold.v1.{}", Self::NAME)
+    }
+}
+/// The second message we broadcast when refreshing our signing shares.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RefreshRound2 {
+    #[prost(message, optional, tag = "1")]
+    pub inner: ::core::option::Option<refresh_round2::Inner>,
+    /// The verification key identifying the sender.
+    #[prost(bytes = "vec", tag = "2")]
+    pub vk: ::prost::alloc::vec::Vec<u8>,
+    /// A signature over the proto-encoded inner message.
+    #[prost(bytes = "vec", tag = "3")]
+    pub sig: ::prost::alloc::vec::Vec<u8>,
+}
+/// Nested message and enum types in `RefreshRound2`.
+pub mod refresh_round2 {
+    /// A share of zero, encrypted, along with an identifier for the recipient.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct TargetedShare {
+        /// A verification key identifying the recipient.
+        #[prost(bytes = "vec", tag = "1")]
+        pub vk: ::prost::alloc::vec::Vec<u8>,
+        /// The ciphertext of the recipient's share.
+        #[prost(bytes = "vec", tag = "2")]
+        pub encrypted_share: ::prost::alloc::vec::Vec<u8>,
+    }
+    impl ::prost::Name for TargetedShare {
+        const NAME: &'static str = "TargetedShare";
+        const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.custody.threshold.v1.RefreshRound2.{}", Self::NAME
+            )
+        }
+    }
+    /// An inner message that will be signed.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Inner {
+        /// The refresh epoch this message belongs to.
+        #[prost(uint64, tag = "1")]
+        pub epoch: u64,
+        /// A hash of all the first round messages, committing the sender to the transcript it saw.
+        #[prost(bytes = "vec", tag = "2")]
+        pub transcript: ::prost::alloc::vec::Vec<u8>,
+        /// Encrypted shares for each recipient.
+        #[prost(message, repeated, tag = "3")]
+        pub encrypted_shares: ::prost::alloc::vec::Vec<TargetedShare>,
+    }
+    impl ::prost::Name for Inner {
+        const NAME: &'static str = "Inner";
+        const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.custody.threshold.v1.RefreshRound2.{}", Self::NAME
+            )
+        }
+    }
+}
+impl ::prost::Name for RefreshRound2 {
+    const NAME: &'static str = "RefreshRound2";
+    const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.custody.threshold.v1.{}", Self::NAME)
+    }
+}
+/// The message we broadcast once we've stored our refreshed signing share.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RefreshAcknowledgement {
+    #[prost(message, optional, tag = "1")]
+    pub inner: ::core::option::Option<refresh_acknowledgement::Inner>,
+    /// The verification key identifying the sender.
+    #[prost(bytes = "vec", tag = "2")]
+    pub vk: ::prost::alloc::vec::Vec<u8>,
+    /// A signature over the proto-encoded inner message.
+    #[prost(bytes = "vec", tag = "3")]
+    pub sig: ::prost::alloc::vec::Vec<u8>,
+}
+/// Nested message and enum types in `RefreshAcknowledgement`.
+pub mod refresh_acknowledgement {
+    /// An inner message that will be signed.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Inner {
+        /// The refresh epoch this message belongs to.
+        #[prost(uint64, tag = "1")]
+        pub epoch: u64,
+        /// A hash of every participant's refreshed verifying share, as computed by the sender.
+        #[prost(bytes = "vec", tag = "2")]
+        pub verifying_shares_hash: ::prost::alloc::vec::Vec<u8>,
+    }
+    impl ::prost::Name for Inner {
+        const NAME: &'static str = "Inner";
+        const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+        fn full_name() -> ::prost::alloc::string::String {
+            ::prost::alloc::format!(
+                "penumbra.custody.threshold.v1.RefreshAcknowledgement.{}", Self::NAME
+            )
+        }
+    }
+}
+impl ::prost::Name for RefreshAcknowledgement {
+    const NAME: &'static str = "RefreshAcknowledgement";
+    const PACKAGE: &'static str = "penumbra.custody.threshold.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.custody.threshold.v1.{}", Self::NAME)
+    }
+}
@@ -1548,6 +1548,121 @@ impl ::prost::Name for UnbondingTokensByAddressIndexResponse {
         ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
     }
 }
+/// Requests the FMD detection key for an address index.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportDetectionKeyRequest {
+    /// The address index to export the detection key for.
+    #[prost(message, optional, tag = "1")]
+    pub address_index: ::core::option::Option<
+        super::super::core::keys::v1::AddressIndex,
+    >,
+}
+impl ::prost::Name for ExportDetectionKeyRequest {
+    const NAME: &'static str = "ExportDetectionKeyRequest";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportDetectionKeyResponse {
+    /// The address whose clues the detection key can examine.
+    #[prost(message, optional, tag = "1")]
+    pub address: ::core::option::Option<super::super::core::keys::v1::Address>,
+    /// The 32-byte decaf377-fmd detection key.
+    #[prost(bytes = "vec", tag = "2")]
+    pub detection_key: ::prost::alloc::vec::Vec<u8>,
+}
+impl ::prost::Name for ExportDetectionKeyResponse {
+    const NAME: &'static str = "ExportDetectionKeyResponse";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+/// Requests that the detection key for an address index be marked as revoked.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeDetectionKeyRequest {
+    /// The address index whose detection key should be revoked.
+    #[prost(message, optional, tag = "1")]
+    pub address_index: ::core::option::Option<
+        super::super::core::keys::v1::AddressIndex,
+    >,
+}
+impl ::prost::Name for RevokeDetectionKeyRequest {
+    const NAME: &'static str = "RevokeDetectionKeyRequest";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeDetectionKeyResponse {}
+impl ::prost::Name for RevokeDetectionKeyResponse {
+    const NAME: &'static str = "RevokeDetectionKeyResponse";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+/// Requests the list of exported detection keys.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DetectionKeysRequest {
+    /// If set, also return detection keys that have been revoked.
+    #[prost(bool, tag = "1")]
+    pub include_revoked: bool,
+}
+impl ::prost::Name for DetectionKeysRequest {
+    const NAME: &'static str = "DetectionKeysRequest";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DetectionKeysResponse {
+    #[prost(message, optional, tag = "1")]
+    pub record: ::core::option::Option<DetectionKeyRecord>,
+}
+impl ::prost::Name for DetectionKeysResponse {
+    const NAME: &'static str = "DetectionKeysResponse";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
+/// A record of a detection key that has been exported from the view service.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DetectionKeyRecord {
+    /// The address index the detection key was derived for.
+    #[prost(message, optional, tag = "1")]
+    pub address_index: ::core::option::Option<
+        super::super::core::keys::v1::AddressIndex,
+    >,
+    /// The address whose clues the detection key can examine.
+    #[prost(message, optional, tag = "2")]
+    pub address: ::core::option::Option<super::super::core::keys::v1::Address>,
+    /// The sync height at which the detection key was first exported.
+    #[prost(uint64, tag = "3")]
+    pub exported_height: u64,
+    /// The sync height at which the detection key was revoked, if it has been.
+    #[prost(uint64, optional, tag = "4")]
+    pub revoked_height: ::core::option::Option<u64>,
+}
+impl ::prost::Name for DetectionKeyRecord {
+    const NAME: &'static str = "DetectionKeyRecord";
+    const PACKAGE: &'static str = "penumbra.view.v1";
+    fn full_name() -> ::prost::alloc::string::String {
+        ::prost::alloc::format!("penumbra.view.v1.{}", Self::NAME)
+    }
+}
 /// Generated client implementations.
 #[cfg(feature = "rpc")]
 pub mod view_service_client {
@@ -2457,6 +2572,101 @@ pub mod view_service_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Derive the FMD detection key for the given address index, recording that
+        /// it has been exported.
+        ///
+        /// A detection key allows its holder to probabilistically detect which
+        /// transactions are sent to the corresponding address, at the false positive
+        /// rate set by the chain's FMD parameters, without being able to decrypt them
+        /// or detect transactions sent to any other address.
+        pub async fn export_detection_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExportDetectionKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExportDetectionKeyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.view.v1.ViewService/ExportDetectionKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("penumbra.view.v1.ViewService", "ExportDetectionKey"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Mark the exported detection key for the given address index as revoked.
+        ///
+        /// Revocation cannot prevent a third party who already holds the key from
+        /// using it: it records that the key should no longer be exported.  Since
+        /// the view service's records are lost if it is reset, clients should also
+        /// keep their own record of revoked keys.
+        pub async fn revoke_detection_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RevokeDetectionKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RevokeDetectionKeyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.view.v1.ViewService/RevokeDetectionKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("penumbra.view.v1.ViewService", "RevokeDetectionKey"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// List the detection keys exported so far.
+        pub async fn detection_keys(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DetectionKeysRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::DetectionKeysResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/penumbra.view.v1.ViewService/DetectionKeys",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("penumbra.view.v1.ViewService", "DetectionKeys"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -2805,6 +3015,47 @@ pub mod view_service_server {
             tonic::Response<Self::UnbondingTokensByAddressIndexStream>,
             tonic::Status,
         >;
+        /// Derive the FMD detection key for the given address index, recording that
+        /// it has been exported.
+        ///
+        /// A detection key allows its holder to probabilistically detect which
+        /// transactions are sent to the corresponding address, at the false positive
+        /// rate set by the chain's FMD parameters, without being able to decrypt them
+        /// or detect transactions sent to any other address.
+        async fn export_detection_key(
+            &self,
+            request: tonic::Request<super::ExportDetectionKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExportDetectionKeyResponse>,
+            tonic::Status,
+        >;
+        /// Mark the exported detection key for the given address index as revoked.
+        ///
+        /// Revocation cannot prevent a third party who already holds the key from
+        /// using it: it records that the key should no longer be exported.  Since
+        /// the view service's records are lost if it is reset, clients should also
+        /// keep their own record of revoked keys.
+        async fn revoke_detection_key(
+            &self,
+            request: tonic::Request<super::RevokeDetectionKeyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RevokeDetectionKeyResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the DetectionKeys method.
+        type DetectionKeysStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::DetectionKeysResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// List the detection keys exported so far.
+        async fn detection_keys(
+            &self,
+            request: tonic::Request<super::DetectionKeysRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::DetectionKeysStream>,
+            tonic::Status,
+        >;
     }
     /// The view RPC is used by a view client, who wants to do some
     /// transaction-related actions, to request data from a view service, which is
@@ -4220,6 +4471,145 @@ pub mod view_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/penumbra.view.v1.ViewService/ExportDetectionKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExportDetectionKeySvc<T: ViewService>(pub Arc<T>);
+                    impl<
+                        T: ViewService,
+                    > tonic::server::UnaryService<super::ExportDetectionKeyRequest>
+                    for ExportDetectionKeySvc<T> {
+                        type Response = super::ExportDetectionKeyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExportDetectionKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ViewService>::export_detection_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExportDetectionKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/penumbra.view.v1.ViewService/RevokeDetectionKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RevokeDetectionKeySvc<T: ViewService>(pub Arc<T>);
+                    impl<
+                        T: ViewService,
+                    > tonic::server::UnaryService<super::RevokeDetectionKeyRequest>
+                    for RevokeDetectionKeySvc<T> {
+                        type Response = super::RevokeDetectionKeyResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RevokeDetectionKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ViewService>::revoke_detection_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RevokeDetectionKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/penumbra.view.v1.ViewService/DetectionKeys" => {
+                    #[allow(non_camel_case_types)]
+                    struct DetectionKeysSvc<T: ViewService>(pub Arc<T>);
+                    impl<
+                        T: ViewService,
+                    > tonic::server::ServerStreamingService<super::DetectionKeysRequest>
+                    for DetectionKeysSvc<T> {
+                        type Response = super::DetectionKeysResponse;
+                        type ResponseStream = T::DetectionKeysStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DetectionKeysRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ViewService>::detection_keys(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DetectionKeysSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
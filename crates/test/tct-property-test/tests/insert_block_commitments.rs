@@ -0,0 +1,146 @@
+use proptest::{arbitrary::*, prelude::*};
+
+use penumbra_tct::{builder::block, validate, StateCommitment, Tree, Witness};
+
+const MAX_USED_COMMITMENTS: usize = 4;
+const MAX_BLOCKS: usize = 4;
+const MAX_BLOCK_SIZE: usize = 40;
+
+/// Generate a sequence of blocks drawing their commitments from a small pool, so that repeated
+/// commitments are exercised too.
+fn blocks() -> impl Strategy<Value = Vec<Vec<(Witness, StateCommitment)>>> {
+    prop::collection::vec(any::<StateCommitment>(), 1..MAX_USED_COMMITMENTS).prop_flat_map(
+        |commitments| {
+            prop::collection::vec(
+                prop::collection::vec(
+                    (any::<Witness>(), prop::sample::select(commitments)),
+                    0..MAX_BLOCK_SIZE,
+                ),
+                1..MAX_BLOCKS,
+            )
+        },
+    )
+}
+
+/// Insert a block one commitment at a time, the slow way, returning its root.
+fn insert_sequentially(tree: &mut Tree, block: &[(Witness, StateCommitment)]) -> block::Root {
+    for &(witness, commitment) in block {
+        tree.insert(witness, commitment).unwrap();
+    }
+    tree.end_block().unwrap()
+}
+
+/// A distinct commitment for each index.
+fn commitment(i: u64) -> StateCommitment {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&i.to_le_bytes());
+    StateCommitment::try_from(bytes).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn bulk_insertion_matches_sequential_insertion(blocks in blocks()) {
+        let mut sequential = Tree::new();
+        let mut bulk = Tree::new();
+
+        for block in blocks {
+            let expected_root = insert_sequentially(&mut sequential, &block);
+            let block_root = bulk.insert_block_commitments(block).unwrap();
+
+            assert_eq!(expected_root, block_root);
+            assert_eq!(sequential.root(), bulk.root());
+            assert_eq!(sequential.position(), bulk.position());
+            assert_eq!(sequential, bulk);
+        }
+
+        validate::all_proofs(&bulk).unwrap();
+        validate::cached_hashes(&bulk).unwrap();
+    }
+
+    #[test]
+    fn bulk_insertion_into_open_block(
+        (open, blocks) in (
+            prop::collection::vec((any::<Witness>(), any::<StateCommitment>()), 1..MAX_BLOCK_SIZE),
+            blocks(),
+        )
+    ) {
+        let mut sequential = Tree::new();
+        let mut bulk = Tree::new();
+
+        // Leave the current block open with some commitments already in it
+        for &(witness, commitment) in &open {
+            sequential.insert(witness, commitment).unwrap();
+            bulk.insert(witness, commitment).unwrap();
+        }
+
+        for block in blocks {
+            insert_sequentially(&mut sequential, &block);
+            bulk.insert_block_commitments(block).unwrap();
+            assert_eq!(sequential, bulk);
+        }
+
+        validate::all_proofs(&bulk).unwrap();
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// With the `parallel` feature enabled, blocks with more than sixteen commitments have
+    /// their subtrees built in parallel, which must produce exactly the same tree as building them
+    /// sequentially.
+    #[test]
+    fn parallel_bulk_insertion_matches_sequential_insertion(
+        witnesses in prop::collection::vec(any::<Witness>(), 0..2_000),
+        offset in any::<u32>(),
+    ) {
+        let block: Vec<_> = witnesses
+            .into_iter()
+            .enumerate()
+            .map(|(i, witness)| (witness, commitment(u64::from(offset) + i as u64)))
+            .collect();
+
+        let mut sequential = Tree::new();
+        let expected_root = insert_sequentially(&mut sequential, &block);
+
+        let mut parallel = Tree::new();
+        assert_eq!(expected_root, parallel.insert_block_commitments(block).unwrap());
+        assert_eq!(sequential, parallel);
+
+        validate::all_proofs(&parallel).unwrap();
+        validate::cached_hashes(&parallel).unwrap();
+    }
+}
+
+#[test]
+fn large_block_matches_sequential_insertion() {
+    // Enough commitments to span several levels of the block, leaving the last subtrees partial
+    let block: Vec<_> = (0u64..1_500)
+        .map(|i| {
+            let witness = if i % 7 == 0 {
+                Witness::Keep
+            } else {
+                Witness::Forget
+            };
+            (witness, commitment(i))
+        })
+        .collect();
+
+    let mut sequential = Tree::new();
+    let expected_root = insert_sequentially(&mut sequential, &block);
+
+    let mut bulk = Tree::new();
+    assert_eq!(expected_root, bulk.insert_block_commitments(block).unwrap());
+
+    assert_eq!(sequential, bulk);
+    validate::all_proofs(&bulk).unwrap();
+}
+
+#[test]
+fn oversized_block_is_rejected() {
+    let block = vec![(Witness::Forget, commitment(0)); 65_537];
+
+    let mut tree = Tree::new();
+    assert!(tree.insert_block_commitments(block).is_err());
+    assert_eq!(tree, Tree::new());
+}
@@ -0,0 +1,269 @@
+//! Bearer-token authentication for view services exposed over the network.
+//!
+//! A [`ViewServer`](crate::ViewServer) only ever serves the data for a single
+//! full viewing key, but when it is reachable by other hosts (for instance,
+//! when run inside `pclientd`), anyone who can connect to it can read that
+//! wallet's balances and transaction history.  This module provides an opt-in
+//! authentication layer for that case.
+//!
+//! Clients present an [`AuthToken`] as a `Bearer` token in the gRPC
+//! `authorization` header.  The server never stores the tokens themselves;
+//! instead, it stores an [`AuthTokenDigest`] for each authorized token, which
+//! commits to both the token and the [`WalletId`] it was issued for.  This
+//! binds each token to a single wallet: a token issued for one FVK will be
+//! rejected by a view service serving a different FVK, so several hosted view
+//! services can share a single list of authorized digests without any user
+//! being able to read another user's wallet.
+
+use std::{collections::BTreeSet, str::FromStr, sync::Arc};
+
+use anyhow::anyhow;
+use penumbra_keys::keys::WalletId;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tonic::{metadata::MetadataValue, service::Interceptor, Request, Status};
+
+/// The domain separator used when computing [`AuthTokenDigest`]s.
+const AUTH_TOKEN_DIGEST_DOMAIN_SEP: &[u8] = b"penumbra.view.auth-token";
+
+/// The gRPC metadata key used to carry the bearer token.
+const AUTHORIZATION_HEADER: &str = "authorization";
+
+/// The scheme prefix for bearer tokens in the `authorization` header.
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// A secret bearer token granting read access to a remote view service.
+///
+/// Tokens are random 32-byte strings, hex-encoded when displayed or parsed.
+/// A token is only meaningful together with the [`WalletId`] it was issued
+/// for; see [`AuthToken::digest`].
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AuthToken([u8; 32]);
+
+impl AuthToken {
+    /// Generates a fresh random token.
+    pub fn generate<R: RngCore + CryptoRng>(mut rng: R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Computes the digest of this token, bound to the given wallet.
+    ///
+    /// This is the value that should be recorded in the server's list of
+    /// authorized tokens.
+    pub fn digest(&self, wallet_id: &WalletId) -> AuthTokenDigest {
+        let mut hasher = Sha256::new();
+        hasher.update(AUTH_TOKEN_DIGEST_DOMAIN_SEP);
+        hasher.update(wallet_id.0);
+        hasher.update(self.0);
+        AuthTokenDigest(hasher.finalize().into())
+    }
+}
+
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Avoid leaking the token into logs.
+        f.write_str("AuthToken(..)")
+    }
+}
+
+impl std::fmt::Display for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for AuthToken {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(
+            hex::decode(s.trim())?
+                .try_into()
+                .map_err(|_| anyhow!("auth token must be 32 bytes"))?,
+        ))
+    }
+}
+
+impl TryFrom<String> for AuthToken {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AuthToken> for String {
+    fn from(value: AuthToken) -> Self {
+        value.to_string()
+    }
+}
+
+/// Attaches the token to outgoing requests, so that an [`AuthToken`] can be
+/// used directly as a client-side interceptor.
+impl Interceptor for AuthToken {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let value = MetadataValue::try_from(format!("{BEARER_PREFIX}{self}"))
+            .map_err(|_| Status::internal("could not encode auth token"))?;
+        request.metadata_mut().insert(AUTHORIZATION_HEADER, value);
+        Ok(request)
+    }
+}
+
+/// A wallet-bound commitment to an [`AuthToken`], as stored by the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AuthTokenDigest([u8; 32]);
+
+impl std::fmt::Display for AuthTokenDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for AuthTokenDigest {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(hex::decode(s.trim())?.try_into().map_err(|_| {
+            anyhow!("auth token digest must be 32 bytes")
+        })?))
+    }
+}
+
+impl TryFrom<String> for AuthTokenDigest {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AuthTokenDigest> for String {
+    fn from(value: AuthTokenDigest) -> Self {
+        value.to_string()
+    }
+}
+
+/// Server-side configuration for view service authentication.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthConfig {
+    /// The digests of all tokens authorized to access the view service.
+    #[serde(default)]
+    pub authorized_tokens: Vec<AuthTokenDigest>,
+}
+
+/// A server-side [`Interceptor`] that rejects requests without a valid bearer
+/// token for the wallet being served.
+///
+/// If constructed with [`AuthInterceptor::disabled`], all requests are
+/// allowed; this makes it possible to use a single service type whether or
+/// not authentication is configured.
+#[derive(Clone, Debug)]
+pub struct AuthInterceptor {
+    wallet_id: WalletId,
+    authorized: Option<Arc<BTreeSet<AuthTokenDigest>>>,
+}
+
+impl AuthInterceptor {
+    /// Constructs an interceptor admitting only the tokens in `config` that
+    /// were issued for `wallet_id`.
+    pub fn new(wallet_id: WalletId, config: &AuthConfig) -> Self {
+        Self {
+            wallet_id,
+            authorized: Some(Arc::new(config.authorized_tokens.iter().copied().collect())),
+        }
+    }
+
+    /// Constructs an interceptor that admits all requests.
+    pub fn disabled(wallet_id: WalletId) -> Self {
+        Self {
+            wallet_id,
+            authorized: None,
+        }
+    }
+
+    /// Checks whether the given token is authorized for the served wallet.
+    pub fn is_authorized(&self, token: &AuthToken) -> bool {
+        match &self.authorized {
+            None => true,
+            Some(authorized) => authorized.contains(&token.digest(&self.wallet_id)),
+        }
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        if self.authorized.is_none() {
+            return Ok(request);
+        }
+
+        let token = request
+            .metadata()
+            .get(AUTHORIZATION_HEADER)
+            .ok_or_else(|| Status::unauthenticated("missing bearer token"))?
+            .to_str()
+            .map_err(|_| Status::unauthenticated("malformed authorization header"))?
+            .strip_prefix(BEARER_PREFIX)
+            .ok_or_else(|| Status::unauthenticated("expected a bearer token"))?
+            .parse::<AuthToken>()
+            .map_err(|_| Status::unauthenticated("malformed bearer token"))?;
+
+        if !self.is_authorized(&token) {
+            tracing::debug!(wallet_id = ?self.wallet_id, "rejected view request with unauthorized token");
+            return Err(Status::permission_denied(
+                "bearer token is not authorized for this wallet",
+            ));
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_bound_to_a_single_wallet() {
+        let token = AuthToken::generate(rand_core::OsRng);
+        let wallet = WalletId([1; 32]);
+        let other_wallet = WalletId([2; 32]);
+
+        let config = AuthConfig {
+            authorized_tokens: vec![token.digest(&wallet)],
+        };
+
+        assert!(AuthInterceptor::new(wallet, &config).is_authorized(&token));
+        assert!(!AuthInterceptor::new(other_wallet, &config).is_authorized(&token));
+        assert!(AuthInterceptor::disabled(other_wallet).is_authorized(&token));
+    }
+
+    #[test]
+    fn interceptor_round_trip() {
+        let mut token = AuthToken::generate(rand_core::OsRng);
+        let wallet = WalletId([1; 32]);
+        let config = AuthConfig {
+            authorized_tokens: vec![token.digest(&wallet)],
+        };
+        let mut server = AuthInterceptor::new(wallet, &config);
+
+        assert_eq!(
+            server.call(Request::new(())).unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+
+        let request = token.call(Request::new(())).unwrap();
+        assert!(server.call(request).is_ok());
+
+        let mut stranger = AuthToken::generate(rand_core::OsRng);
+        let request = stranger.call(Request::new(())).unwrap();
+        assert_eq!(
+            server.call(request).unwrap_err().code(),
+            tonic::Code::PermissionDenied
+        );
+    }
+}
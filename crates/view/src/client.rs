@@ -28,7 +28,9 @@ use penumbra_transaction::{
     txhash::TransactionId, AuthorizationData, Transaction, TransactionPlan, WitnessData,
 };
 
-use crate::{SpendableNoteRecord, StatusStreamResponse, SwapRecord, TransactionInfo};
+use crate::{
+    DetectionKeyRecord, SpendableNoteRecord, StatusStreamResponse, SwapRecord, TransactionInfo,
+};
 
 pub(crate) type BroadcastStatusStream = Pin<
     Box<dyn Future<Output = Result<Streaming<BroadcastTransactionResponse>, anyhow::Error>> + Send>,
@@ -306,6 +308,28 @@ pub trait ViewClient {
     fn unclaimed_swaps(
         &mut self,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<SwapRecord>>> + Send + 'static>>;
+
+    /// Exports the FMD detection key for the given address index, along with the address it
+    /// detects transactions for.
+    fn export_detection_key(
+        &mut self,
+        address_index: AddressIndex,
+    ) -> Pin<Box<dyn Future<Output = Result<(Address, fmd::DetectionKey)>> + Send + 'static>>;
+
+    /// Marks the exported FMD detection key for the given address index as revoked, so that it
+    /// can no longer be exported.
+    ///
+    /// Fails with a `NotFound` status if the key was never exported through the view service.
+    fn revoke_detection_key(
+        &mut self,
+        address_index: AddressIndex,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>>;
+
+    /// Queries for the FMD detection keys exported so far.
+    fn detection_keys(
+        &mut self,
+        include_revoked: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<DetectionKeyRecord>>> + Send + 'static>>;
 }
 
 // We need to tell `async_trait` not to add a `Send` bound to the boxed
@@ -915,4 +939,77 @@ where
         }
         .boxed()
     }
+
+    fn export_detection_key(
+        &mut self,
+        address_index: AddressIndex,
+    ) -> Pin<Box<dyn Future<Output = Result<(Address, fmd::DetectionKey)>> + Send + 'static>> {
+        let mut self2 = self.clone();
+        async move {
+            let rsp = ViewServiceClient::export_detection_key(
+                &mut self2,
+                tonic::Request::new(pb::ExportDetectionKeyRequest {
+                    address_index: Some(address_index.into()),
+                }),
+            )
+            .await?
+            .into_inner();
+
+            let address = rsp
+                .address
+                .ok_or_else(|| anyhow::anyhow!("empty ExportDetectionKeyResponse message"))?
+                .try_into()?;
+            let detection_key = fmd::DetectionKey::from_bytes(
+                rsp.detection_key
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("detection key must be 32 bytes"))?,
+            )?;
+
+            Ok((address, detection_key))
+        }
+        .boxed()
+    }
+
+    fn revoke_detection_key(
+        &mut self,
+        address_index: AddressIndex,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>> {
+        let mut self2 = self.clone();
+        async move {
+            ViewServiceClient::revoke_detection_key(
+                &mut self2,
+                tonic::Request::new(pb::RevokeDetectionKeyRequest {
+                    address_index: Some(address_index.into()),
+                }),
+            )
+            .await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn detection_keys(
+        &mut self,
+        include_revoked: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<DetectionKeyRecord>>> + Send + 'static>> {
+        let mut self2 = self.clone();
+        async move {
+            let rsp = ViewServiceClient::detection_keys(
+                &mut self2,
+                tonic::Request::new(pb::DetectionKeysRequest { include_revoked }),
+            );
+            let pb_records: Vec<_> = rsp.await?.into_inner().try_collect().await?;
+
+            pb_records
+                .into_iter()
+                .map(|rsp| {
+                    rsp.record
+                        .ok_or_else(|| anyhow::anyhow!("empty DetectionKeysResponse message"))?
+                        .try_into()
+                })
+                .collect()
+        }
+        .boxed()
+    }
 }
@@ -0,0 +1,68 @@
+use penumbra_keys::{keys::AddressIndex, Address};
+use penumbra_proto::{view::v1 as pb, DomainType};
+
+use r2d2_sqlite::rusqlite::Row;
+use serde::{Deserialize, Serialize};
+
+/// A record of an FMD detection key exported for a particular address index.
+///
+/// Detection keys are exported so that a third party can scan for transactions
+/// addressed to the corresponding address on the user's behalf.  Revoking a key
+/// only records that it should no longer be exported or used: a third party
+/// that already holds the key retains its detection capability for any future
+/// transactions sent to that address.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "pb::DetectionKeyRecord", into = "pb::DetectionKeyRecord")]
+pub struct DetectionKeyRecord {
+    pub address_index: AddressIndex,
+    pub address: Address,
+    pub exported_height: u64,
+    pub revoked_height: Option<u64>,
+}
+
+impl DomainType for DetectionKeyRecord {
+    type Proto = pb::DetectionKeyRecord;
+}
+
+impl From<DetectionKeyRecord> for pb::DetectionKeyRecord {
+    fn from(msg: DetectionKeyRecord) -> Self {
+        pb::DetectionKeyRecord {
+            address_index: Some(msg.address_index.into()),
+            address: Some(msg.address.into()),
+            exported_height: msg.exported_height,
+            revoked_height: msg.revoked_height,
+        }
+    }
+}
+
+impl TryFrom<pb::DetectionKeyRecord> for DetectionKeyRecord {
+    type Error = anyhow::Error;
+    fn try_from(value: pb::DetectionKeyRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address_index: value
+                .address_index
+                .ok_or_else(|| anyhow::anyhow!("missing address_index"))?
+                .try_into()?,
+            address: value
+                .address
+                .ok_or_else(|| anyhow::anyhow!("missing address"))?
+                .try_into()?,
+            exported_height: value.exported_height,
+            revoked_height: value.revoked_height,
+        })
+    }
+}
+
+impl TryFrom<&Row<'_>> for DetectionKeyRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(row: &Row<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address_index: row.get::<_, Vec<u8>>("address_index")?[..].try_into()?,
+            // Address is not proto-encoded
+            address: row.get::<_, Vec<u8>>("address")?[..].try_into()?,
+            exported_height: row.get("exported_height")?,
+            revoked_height: row.get("revoked_height")?,
+        })
+    }
+}
@@ -7,16 +7,25 @@
 //! This crate also provides a [`Planner`]. This is a planner for
 //! [`TransactionPlan`][penumbra_transaction::TransactionPlan].
 //!
-//! Finally, this crate provides a [`Storage`] type for managing persistent sqlite storage.
+//! This crate also provides a [`Storage`] type for managing persistent sqlite storage.
+//!
+//! The [`price`] module provides optional price feeds, used to value balances and transactions.
+//!
+//! Finally, the [`auth`] module provides optional bearer-token authentication for view services
+//! that are exposed over the network.
 
 #![deny(clippy::unwrap_used)]
 #![recursion_limit = "512"]
 // Requires nightly.
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+pub mod auth;
 mod client;
+mod detection_key_record;
 mod metrics;
 mod note_record;
 mod planner;
+pub mod price;
+mod scan_filter;
 mod service;
 mod status;
 mod storage;
@@ -26,9 +35,11 @@ mod transaction_info;
 mod worker;
 
 pub use crate::client::ViewClient;
+pub use crate::detection_key_record::DetectionKeyRecord;
 pub use crate::metrics::register_metrics;
 pub use crate::note_record::SpendableNoteRecord;
 pub use crate::planner::Planner;
+pub use crate::scan_filter::ScanFilter;
 pub use crate::service::ViewServer;
 pub use crate::status::StatusStreamResponse;
 pub use crate::storage::Storage;
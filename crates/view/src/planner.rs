@@ -11,6 +11,7 @@ use tracing::instrument;
 
 use penumbra_asset::{asset, Balance, Value, STAKING_TOKEN_ASSET_ID};
 use penumbra_community_pool::CommunityPoolDeposit;
+use penumbra_custody::screening::Screener;
 use penumbra_dex::{
     lp::action::{PositionClose, PositionOpen},
     lp::plan::PositionWithdrawPlan,
@@ -51,6 +52,7 @@ pub struct Planner<R: RngCore + CryptoRng> {
     ibc_actions: Vec<IbcRelay>,
     gas_prices: GasPrices,
     fee_tier: FeeTier,
+    screener: Option<Screener>,
     // IMPORTANT: if you add more fields here, make sure to clear them when the planner is finished
 }
 
@@ -82,6 +84,7 @@ impl<R: RngCore + CryptoRng> Planner<R> {
             ibc_actions: Vec::new(),
             gas_prices: GasPrices::zero(),
             fee_tier: FeeTier::default(),
+            screener: None,
         }
     }
 
@@ -99,6 +102,14 @@ impl<R: RngCore + CryptoRng> Planner<R> {
         self
     }
 
+    /// Screen the destinations of planned transactions, rejecting plans that the screener does not
+    /// allow.
+    #[instrument(skip(self))]
+    pub fn set_screener(&mut self, screener: Screener) -> &mut Self {
+        self.screener = Some(screener);
+        self
+    }
+
     /// Get the current transaction balance of the planner.
     pub fn balance(&self) -> &Balance {
         &self.balance
@@ -684,6 +695,11 @@ impl<R: RngCore + CryptoRng> Planner<R> {
 
         tracing::debug!(plan = ?self.plan, "finished balancing transaction");
 
+        // Screen the destinations of the finished plan, if configured to do so.
+        if let Some(screener) = &self.screener {
+            screener.check_plan(&self.plan, "planner")?;
+        }
+
         // Clear the planner and pull out the plan to return
         self.balance = Balance::zero();
         self.vote_intents = BTreeMap::new();
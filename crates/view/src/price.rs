@@ -0,0 +1,310 @@
+//! Optional price feeds for valuing balances and transactions.
+//!
+//! When a [`PriceFeed`] is enabled on a [`ViewServer`](crate::ViewServer),
+//! the view service periodically fetches asset prices from a
+//! [`PriceProvider`], and uses them to add equivalent values to the
+//! `ValueView`s in balance responses and to the prices in transaction
+//! perspectives.
+//!
+//! Price feeds are disabled unless explicitly configured: fetching prices
+//! means periodically contacting a third party, which reveals that the user is
+//! running a Penumbra client (and from which network address) to that third
+//! party.  Providers are only ever asked for a fixed, configured list of
+//! assets, never for the assets the wallet actually holds.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use penumbra_asset::{asset, EstimatedPrice};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, RwLock};
+
+use crate::Storage;
+
+/// A price quote, expressed in display units.
+///
+/// For instance, a quote with `priced_unit: "penumbra"`, `numeraire_unit:
+/// "test_usd"`, and `price: 1.5` means that one `penumbra` is worth one and a
+/// half `test_usd`.  Units may be given as any denomination unit, display or
+/// base, known to the view service.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quote {
+    /// The unit being priced.
+    pub priced_unit: String,
+    /// The unit the price is expressed in.
+    pub numeraire_unit: String,
+    /// The number of `numeraire_unit`s that one `priced_unit` is worth.
+    pub price: f64,
+}
+
+/// A source of asset prices.
+#[async_trait]
+pub trait PriceProvider: Send + Sync + 'static {
+    /// Fetches the current prices of all assets known to this provider.
+    async fn fetch_quotes(&self) -> anyhow::Result<Vec<Quote>>;
+}
+
+/// Configuration for an [`HttpJsonProvider`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpJsonConfig {
+    /// The URL of a JSON document containing the prices.
+    pub url: String,
+    /// The unit that all prices in the document are expressed in.
+    pub numeraire: String,
+    /// For each priced unit, a JSON pointer (RFC 6901) locating its price in
+    /// the document, for instance `"/penumbra/usd"`.
+    pub prices: BTreeMap<String, String>,
+}
+
+/// A [`PriceProvider`] that reads prices from a JSON document served over
+/// HTTP, as provided by most public price APIs.
+pub struct HttpJsonProvider {
+    config: HttpJsonConfig,
+    client: reqwest::Client,
+}
+
+impl HttpJsonProvider {
+    pub fn new(config: HttpJsonConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for HttpJsonProvider {
+    async fn fetch_quotes(&self) -> anyhow::Result<Vec<Quote>> {
+        let document: serde_json::Value = self
+            .client
+            .get(&self.config.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        self.config
+            .prices
+            .iter()
+            .map(|(unit, pointer)| {
+                let value = document
+                    .pointer(pointer)
+                    .ok_or_else(|| anyhow!("no price for {unit} at {pointer}"))?;
+                // Many APIs serve prices as strings, to avoid float rounding.
+                let price = match value {
+                    serde_json::Value::Number(n) => n.as_f64(),
+                    serde_json::Value::String(s) => s.parse().ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| anyhow!("price for {unit} at {pointer} is not a number"))?;
+
+                Ok(Quote {
+                    priced_unit: unit.clone(),
+                    numeraire_unit: self.config.numeraire.clone(),
+                    price,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Configuration for a [`PriceFeed`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceFeedConfig {
+    /// How often to refresh prices, in seconds.
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+    /// Where to fetch prices from.
+    pub provider: HttpJsonConfig,
+}
+
+fn default_refresh_interval_secs() -> u64 {
+    300
+}
+
+/// A [`PriceProvider`] together with its refresh schedule.
+pub struct PriceFeed {
+    provider: Box<dyn PriceProvider>,
+    refresh_interval: Duration,
+}
+
+impl PriceFeed {
+    /// Constructs a feed refreshing prices from `provider` every `refresh_interval`.
+    pub fn new(provider: impl PriceProvider, refresh_interval: Duration) -> Self {
+        Self {
+            provider: Box::new(provider),
+            refresh_interval,
+        }
+    }
+
+    /// Refreshes `prices` on schedule, until the process exits.
+    ///
+    /// Fetch failures are logged and leave the previous prices in place.
+    pub(crate) async fn run(
+        self,
+        storage: Storage,
+        prices: Arc<RwLock<Prices>>,
+        sync_height_rx: watch::Receiver<u64>,
+    ) {
+        let mut interval = tokio::time::interval(self.refresh_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let height = *sync_height_rx.borrow();
+            match self.refresh(&storage, height).await {
+                Ok(fresh) => {
+                    tracing::debug!(count = fresh.prices.len(), "refreshed prices");
+                    *prices.write().await = fresh;
+                }
+                Err(e) => tracing::warn!(?e, "failed to refresh prices"),
+            }
+        }
+    }
+
+    async fn refresh(&self, storage: &Storage, height: u64) -> anyhow::Result<Prices> {
+        let quotes = self
+            .provider
+            .fetch_quotes()
+            .await
+            .context("could not fetch prices")?;
+        let mut assets = asset::Cache::with_known_assets();
+        assets.extend(storage.all_assets().await?);
+        Ok(Prices::from_quotes(quotes, &assets, height))
+    }
+}
+
+impl From<PriceFeedConfig> for PriceFeed {
+    fn from(config: PriceFeedConfig) -> Self {
+        Self::new(
+            HttpJsonProvider::new(config.provider),
+            Duration::from_secs(config.refresh_interval_secs),
+        )
+    }
+}
+
+/// The most recently fetched prices, along with the metadata for their
+/// numeraires.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Prices {
+    pub prices: Vec<EstimatedPrice>,
+    pub numeraires: asset::Cache,
+}
+
+impl Prices {
+    /// Returns the prices of the given asset.
+    pub fn for_asset(&self, id: asset::Id) -> impl Iterator<Item = &EstimatedPrice> {
+        self.prices.iter().filter(move |p| p.priced_asset == id)
+    }
+
+    /// Converts display-unit quotes to base-unit prices, using `assets` to
+    /// resolve units.  Quotes for unknown units, and quotes whose price is not
+    /// a finite, non-negative number, are skipped.
+    fn from_quotes(quotes: Vec<Quote>, assets: &asset::Cache, as_of_height: u64) -> Self {
+        let mut prices = Self::default();
+        for quote in quotes {
+            if !quote.price.is_finite() || quote.price < 0.0 {
+                tracing::warn!(?quote, "skipping invalid price quote");
+                continue;
+            }
+            let (Some(priced), Some(numeraire)) = (
+                assets.get_unit(&quote.priced_unit),
+                assets.get_unit(&quote.numeraire_unit),
+            ) else {
+                tracing::debug!(?quote, "skipping price quote for unknown unit");
+                continue;
+            };
+
+            // A base unit of the priced asset is worth `price` display units
+            // of the numeraire, scaled down by the priced unit's exponent, and
+            // up by the numeraire unit's exponent.
+            let numeraire_per_unit = quote.price * 10f64.powi(numeraire.exponent().into())
+                / 10f64.powi(priced.exponent().into());
+            if !numeraire_per_unit.is_finite() {
+                tracing::warn!(?quote, "skipping price quote out of range");
+                continue;
+            }
+
+            prices.prices.push(EstimatedPrice {
+                priced_asset: priced.id(),
+                numeraire: numeraire.id(),
+                numeraire_per_unit,
+                as_of_height,
+            });
+            prices.numeraires.extend([numeraire.base()]);
+        }
+        prices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::Value;
+
+    use super::*;
+
+    #[test]
+    fn quotes_are_converted_to_base_unit_prices() {
+        let assets = asset::Cache::with_known_assets();
+        let quotes = vec![
+            Quote {
+                priced_unit: "penumbra".to_string(),
+                numeraire_unit: "test_usd".to_string(),
+                price: 2.5,
+            },
+            Quote {
+                priced_unit: "not_a_real_unit".to_string(),
+                numeraire_unit: "test_usd".to_string(),
+                price: 1.0,
+            },
+        ];
+        let prices = Prices::from_quotes(quotes, &assets, 7);
+        assert_eq!(prices.prices.len(), 1);
+
+        let penumbra = assets.get_unit("penumbra").unwrap();
+        let test_usd = assets.get_unit("test_usd").unwrap();
+
+        // Three penumbra should be worth seven and a half test_usd.
+        let value = Value {
+            amount: penumbra.parse_value("3").unwrap(),
+            asset_id: penumbra.id(),
+        };
+        let view = value
+            .view_with_cache(&assets)
+            .with_prices(&prices.prices, &prices.numeraires);
+        let penumbra_asset::ValueView::KnownAssetId {
+            equivalent_values, ..
+        } = view
+        else {
+            panic!("penumbra is a known asset");
+        };
+        assert_eq!(equivalent_values.len(), 1);
+        assert_eq!(
+            equivalent_values[0].equivalent_amount,
+            test_usd.parse_value("7.5").unwrap()
+        );
+        assert_eq!(equivalent_values[0].as_of_height, 7);
+    }
+
+    #[test]
+    fn invalid_quotes_are_skipped() {
+        let assets = asset::Cache::with_known_assets();
+        let quote = |price| Quote {
+            priced_unit: "penumbra".to_string(),
+            numeraire_unit: "test_usd".to_string(),
+            price,
+        };
+        let quotes = vec![
+            quote(f64::NAN),
+            quote(f64::INFINITY),
+            quote(f64::NEG_INFINITY),
+            quote(-1.0),
+            quote(f64::MAX),
+            quote(0.5),
+        ];
+        let prices = Prices::from_quotes(quotes, &assets, 7);
+        assert_eq!(prices.prices.len(), 1);
+        assert!(prices.prices[0].numeraire_per_unit > 0.0);
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Sections of compact blocks that the view service can skip while syncing.
+///
+/// Wallets that never use some features can skip the work of scanning for
+/// them, which saves CPU time and storage for simple payment wallets.  The
+/// filter is recorded in the view database, since anything relevant to a
+/// skipped section is missing from the synced state: if a skipped section is
+/// later enabled, the view service forgets its synced state and resyncs from
+/// genesis (see [`Storage::set_scan_filter`](crate::Storage::set_scan_filter)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanFilter {
+    /// Don't trial-decrypt swap payloads.
+    ///
+    /// Swaps made by this wallet, and the outputs of their swap claims, won't
+    /// be detected.
+    pub skip_swaps: bool,
+    /// Don't track liquidity positions opened by this wallet, or their LPNFTs.
+    pub skip_positions: bool,
+    /// Don't process blocks just because a governance proposal started in
+    /// them.
+    pub skip_governance: bool,
+}
+
+impl ScanFilter {
+    /// A filter skipping everything but what's needed for sending and
+    /// receiving payments.
+    pub fn payments_only() -> Self {
+        Self {
+            skip_swaps: true,
+            skip_positions: true,
+            skip_governance: true,
+        }
+    }
+
+    /// Returns true if this filter skips nothing.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns true if state synced with this filter is incomplete for the
+    /// `other` filter, because `other` scans for something this filter skips.
+    pub fn requires_resync_for(&self, other: &ScanFilter) -> bool {
+        // Nothing is recorded for the start of a proposal, so skipping them
+        // leaves no gaps in the synced state.
+        (self.skip_swaps && !other.skip_swaps) || (self.skip_positions && !other.skip_positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resync_is_required_only_to_enable_skipped_sections() {
+        let payments_only = ScanFilter::payments_only();
+        let everything = ScanFilter::default();
+
+        assert!(payments_only.requires_resync_for(&everything));
+        assert!(!everything.requires_resync_for(&payments_only));
+        assert!(!payments_only.requires_resync_for(&payments_only));
+        assert!(!payments_only.requires_resync_for(&ScanFilter {
+            skip_governance: false,
+            ..payments_only
+        }));
+    }
+
+    #[test]
+    fn partial_config_skips_only_named_sections() {
+        let filter: ScanFilter =
+            serde_json::from_str(r#"{"skip_swaps": true}"#).expect("valid filter");
+        assert_eq!(
+            filter,
+            ScanFilter {
+                skip_swaps: true,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -54,10 +54,15 @@ use penumbra_proto::{
 use penumbra_stake::rate::RateData;
 use penumbra_tct::{Proof, StateCommitment};
 use penumbra_transaction::{
+    plan::{CancellationToken, ProvingObserver, ProvingStage},
     AuthorizationData, Transaction, TransactionPerspective, TransactionPlan, WitnessData,
 };
 
-use crate::{worker::Worker, Planner, Storage};
+use crate::{
+    price::{PriceFeed, Prices},
+    worker::Worker,
+    Planner, Storage,
+};
 
 /// A [`futures::Stream`] of broadcast transaction responses.
 ///
@@ -86,6 +91,8 @@ pub struct ViewServer {
     node: Url,
     /// Used to watch for changes to the sync height.
     sync_height_rx: watch::Receiver<u64>,
+    /// The latest prices, which remain empty unless a price feed is enabled.
+    prices: Arc<RwLock<Prices>>,
 }
 
 impl ViewServer {
@@ -119,9 +126,26 @@ impl ViewServer {
             sync_height_rx,
             state_commitment_tree: sct,
             node,
+            prices: Default::default(),
         })
     }
 
+    /// Enables valuation of balances and transactions, spawning a task that
+    /// refreshes prices from the given [`PriceFeed`].
+    ///
+    /// This should be called at most once; see the [`price`](crate::price)
+    /// module for the privacy implications of enabling a price feed.
+    ///
+    /// The task runs until it is aborted through the returned handle, which
+    /// callers should do when shutting down.
+    pub fn enable_price_feed(&self, feed: PriceFeed) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(feed.run(
+            self.storage.clone(),
+            self.prices.clone(),
+            self.sync_height_rx.clone(),
+        ))
+    }
+
     async fn check_worker(&self) -> Result<(), tonic::Status> {
         // If the shared error slot is set, then an error has occurred in the worker
         // that we should bubble up.
@@ -342,6 +366,15 @@ impl ViewServer {
     }
 }
 
+/// Cancels proof generation when dropped.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
 #[async_trait]
 impl ViewService for ViewServer {
     type NotesStream =
@@ -387,6 +420,9 @@ impl ViewService for ViewServer {
                 > + Send,
         >,
     >;
+    type DetectionKeysStream = Pin<
+        Box<dyn futures::Stream<Item = Result<pb::DetectionKeysResponse, tonic::Status>> + Send>,
+    >;
 
     async fn broadcast_transaction(
         &self,
@@ -908,6 +944,20 @@ impl ViewService for ViewServer {
 
         txp.denoms.extend(denoms);
 
+        // If a price feed is enabled, include the prices of the viewed assets.
+        {
+            let prices = self.prices.read().await;
+            for id in txp.denoms.keys() {
+                txp.prices.extend(prices.for_asset(*id).cloned());
+            }
+            let numeraires = txp
+                .prices
+                .iter()
+                .filter_map(|p| prices.numeraires.get(&p.numeraire).cloned())
+                .collect::<Vec<_>>();
+            txp.denoms.extend(numeraires);
+        }
+
         txp.address_views = address_views.into_values().collect();
 
         // Finally, compute the full TxV from the full TxP:
@@ -1007,7 +1057,12 @@ impl ViewService for ViewServer {
                     amount: element.amount.into(),
                 };
 
-                let value_view = value.view_with_denom(metadata)?;
+                let value_view = {
+                    let prices = self2.prices.read().await;
+                    value
+                        .view_with_denom(metadata)?
+                        .with_prices(&prices.prices, &prices.numeraires)
+                };
 
                 let address: Address = self2
                   .address_by_index(Request::new(pb::AddressByIndexRequest {
@@ -1473,27 +1528,63 @@ impl ViewService for ViewServer {
                 tonic::Status::failed_precondition("Error retrieving full viewing key")
             })?;
 
-        let transaction = Some(
-            transaction_plan
-                // TODO: calling `.build` should provide some mechanism to get progress
-                // updates
-                .build(&fvk, &witness_data, &authorization_data)
-                .map_err(|_| tonic::Status::failed_precondition("Error building transaction"))?
-                .into(),
+        // Proving is CPU-bound, so build the transaction on a blocking thread, reporting each
+        // completed proving stage back to the response stream.
+        let total_stages = transaction_plan.num_proofs() * ProvingStage::COUNT;
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let cancellation = CancellationToken::new();
+        let observer = ProvingObserver::new(
+            move |_stage| {
+                let _ = progress_tx.send(());
+            },
+            cancellation.clone(),
         );
+        let build = tokio::task::spawn_blocking(move || {
+            transaction_plan.build_with_observer(
+                &fvk,
+                &witness_data,
+                &authorization_data,
+                &observer,
+            )
+        });
+
+        // If the client cancels the request, the response stream is dropped, and proving stops
+        // early.
+        let cancel_on_drop = CancelOnDrop(cancellation);
 
         let stream = try_stream! {
+            let _cancel_on_drop = cancel_on_drop;
+
+            // The progress sender is dropped with the observer once building finishes.
+            let mut completed_stages = 0;
+            while progress_rx.recv().await.is_some() {
+                completed_stages += 1;
+                yield pb::WitnessAndBuildResponse {
+                    status: Some(pb::witness_and_build_response::Status::BuildProgress(
+                        pb::witness_and_build_response::BuildProgress {
+                            progress: completed_stages as f32 / total_stages as f32,
+                        },
+                    )),
+                };
+            }
+
+            let transaction = build
+                .await?
+                .context("error building transaction")?;
+
             yield pb::WitnessAndBuildResponse {
                 status: Some(pb::witness_and_build_response::Status::Complete(
-                    pb::witness_and_build_response::Complete { transaction },
+                    pb::witness_and_build_response::Complete {
+                        transaction: Some(transaction.into()),
+                    },
                 )),
-            }
+            };
         };
 
         Ok(tonic::Response::new(
             stream
                 .map_err(|e: anyhow::Error| {
-                    tonic::Status::unavailable(format!("error witnessing transaction: {e}"))
+                    tonic::Status::unavailable(format!("error witnessing transaction: {e:#}"))
                 })
                 .boxed(),
         ))
@@ -1681,4 +1772,123 @@ impl ViewService for ViewServer {
     ) -> Result<tonic::Response<Self::UnbondingTokensByAddressIndexStream>, tonic::Status> {
         unimplemented!("unbonding_tokens_by_address_index currently only implemented on web")
     }
+
+    async fn export_detection_key(
+        &self,
+        request: tonic::Request<pb::ExportDetectionKeyRequest>,
+    ) -> Result<tonic::Response<pb::ExportDetectionKeyResponse>, tonic::Status> {
+        let fvk =
+            self.storage.full_viewing_key().await.map_err(|_| {
+                tonic::Status::failed_precondition("Error retrieving full viewing key")
+            })?;
+
+        let address_index: AddressIndex = request
+            .into_inner()
+            .address_index
+            .ok_or_else(|| tonic::Status::invalid_argument("Missing address index"))?
+            .try_into()
+            .map_err(|e| {
+                tonic::Status::invalid_argument(format!("Could not parse address index: {e:#}"))
+            })?;
+
+        let revoked = self
+            .storage
+            .detection_keys(true)
+            .await
+            .map_err(|e| tonic::Status::internal(format!("error reading detection keys: {e:#}")))?
+            .into_iter()
+            .any(|record| record.address_index == address_index && record.revoked_height.is_some());
+        if revoked {
+            return Err(tonic::Status::failed_precondition(
+                "The detection key for this address index was revoked; use a fresh address index instead",
+            ));
+        }
+
+        let (address, detection_key) = fvk.payment_address(address_index);
+        let height = self
+            .storage
+            .last_sync_height()
+            .await
+            .map_err(|e| tonic::Status::internal(format!("error reading sync height: {e:#}")))?
+            .unwrap_or(0);
+
+        self.storage
+            .record_detection_key_export(address_index, address, height)
+            .await
+            .map_err(|e| {
+                tonic::Status::failed_precondition(format!(
+                    "error recording detection key export: {e:#}"
+                ))
+            })?;
+
+        Ok(tonic::Response::new(pb::ExportDetectionKeyResponse {
+            address: Some(address.into()),
+            detection_key: detection_key.to_bytes().to_vec(),
+        }))
+    }
+
+    async fn revoke_detection_key(
+        &self,
+        request: tonic::Request<pb::RevokeDetectionKeyRequest>,
+    ) -> Result<tonic::Response<pb::RevokeDetectionKeyResponse>, tonic::Status> {
+        let address_index: AddressIndex = request
+            .into_inner()
+            .address_index
+            .ok_or_else(|| tonic::Status::invalid_argument("Missing address index"))?
+            .try_into()
+            .map_err(|e| {
+                tonic::Status::invalid_argument(format!("Could not parse address index: {e:#}"))
+            })?;
+
+        let height = self
+            .storage
+            .last_sync_height()
+            .await
+            .map_err(|e| tonic::Status::internal(format!("error reading sync height: {e:#}")))?
+            .unwrap_or(0);
+
+        let revoked = self
+            .storage
+            .revoke_detection_key(address_index, height)
+            .await
+            .map_err(|e| tonic::Status::internal(format!("error revoking detection key: {e:#}")))?;
+        if !revoked {
+            return Err(tonic::Status::not_found(
+                "no detection key was exported for this address",
+            ));
+        }
+
+        Ok(tonic::Response::new(pb::RevokeDetectionKeyResponse {}))
+    }
+
+    async fn detection_keys(
+        &self,
+        request: tonic::Request<pb::DetectionKeysRequest>,
+    ) -> Result<tonic::Response<Self::DetectionKeysStream>, tonic::Status> {
+        let pb::DetectionKeysRequest { include_revoked } = request.into_inner();
+
+        let records = self
+            .storage
+            .detection_keys(include_revoked)
+            .await
+            .map_err(|e| {
+                tonic::Status::unavailable(format!("error getting detection keys: {e}"))
+            })?;
+
+        let stream = try_stream! {
+            for record in records {
+                yield pb::DetectionKeysResponse {
+                    record: Some(record.into()),
+                }
+            }
+        };
+
+        Ok(tonic::Response::new(
+            stream
+                .map_err(|e: anyhow::Error| {
+                    tonic::Status::unavailable(format!("error getting detection keys: {e}"))
+                })
+                .boxed(),
+        ))
+    }
 }
@@ -40,7 +40,7 @@ use penumbra_transaction::Transaction;
 use sct::TreeStore;
 use tct::StateCommitment;
 
-use crate::{sync::FilteredBlock, SpendableNoteRecord, SwapRecord};
+use crate::{sync::FilteredBlock, DetectionKeyRecord, ScanFilter, SpendableNoteRecord, SwapRecord};
 
 mod sct;
 
@@ -1592,4 +1592,304 @@ impl Storage {
 
         Ok(records)
     }
+
+    /// Record that the detection key for `address_index` was exported at `height`.
+    ///
+    /// Re-exporting an already-exported key keeps its original export height.  Returns an error
+    /// if the key has been revoked.
+    pub async fn record_detection_key_export(
+        &self,
+        address_index: AddressIndex,
+        address: Address,
+        height: u64,
+    ) -> anyhow::Result<()> {
+        let address_index = address_index.to_bytes().to_vec();
+        let address = address.to_vec();
+
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            let mut lock = pool.get()?;
+            let dbtx = lock.transaction()?;
+
+            let revoked_height = dbtx
+                .prepare_cached(
+                    "SELECT revoked_height FROM detection_keys WHERE address_index = ?1",
+                )?
+                .query_row([&address_index], |row| row.get::<_, Option<u64>>(0))
+                .optional()?
+                .flatten();
+            if let Some(revoked_height) = revoked_height {
+                anyhow::bail!("detection key was revoked at height {revoked_height}");
+            }
+
+            dbtx.execute(
+                "INSERT OR IGNORE INTO detection_keys (address_index, address, exported_height) VALUES (?1, ?2, ?3)",
+                (address_index, address, height),
+            )?;
+            dbtx.commit()?;
+
+            anyhow::Ok(())
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    /// Mark the exported detection key for `address_index` as revoked at `height`.
+    ///
+    /// Returns `false` if the key was never exported through this view service, in which case
+    /// there is no record to mark.  Revoking an already-revoked key keeps its original
+    /// revocation height.
+    pub async fn revoke_detection_key(
+        &self,
+        address_index: AddressIndex,
+        height: u64,
+    ) -> anyhow::Result<bool> {
+        let address_index = address_index.to_bytes().to_vec();
+
+        let pool = self.pool.clone();
+
+        let updated = spawn_blocking(move || {
+            pool.get()?
+                .execute(
+                    "UPDATE detection_keys
+                    SET revoked_height = COALESCE(revoked_height, ?2)
+                    WHERE address_index = ?1",
+                    (address_index, height),
+                )
+                .map_err(anyhow::Error::from)
+        })
+        .await??;
+
+        Ok(updated > 0)
+    }
+
+    /// Query for the detection keys exported so far, optionally including revoked ones.
+    pub async fn detection_keys(
+        &self,
+        include_revoked: bool,
+    ) -> anyhow::Result<Vec<DetectionKeyRecord>> {
+        let pool = self.pool.clone();
+
+        let query = if include_revoked {
+            "SELECT * FROM detection_keys ORDER BY exported_height ASC"
+        } else {
+            "SELECT * FROM detection_keys WHERE revoked_height IS NULL ORDER BY exported_height ASC"
+        };
+
+        let records = spawn_blocking(move || {
+            pool.get()?
+                .prepare(query)?
+                .query_and_then((), |record| record.try_into())?
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .await??;
+
+        Ok(records)
+    }
+
+    /// The sections of compact blocks skipped while syncing this database.
+    pub async fn scan_filter(&self) -> anyhow::Result<ScanFilter> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            let bytes = pool
+                .get()?
+                .prepare_cached("SELECT v FROM kv WHERE k = 'scan_filter'")?
+                .query_row([], |row| row.get::<_, Vec<u8>>("v"))
+                .optional()?;
+
+            match bytes {
+                Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+                None => Ok(ScanFilter::default()),
+            }
+        })
+        .await?
+    }
+
+    /// Set the sections of compact blocks to skip while syncing this database.
+    ///
+    /// If the new filter scans for anything that was skipped so far, the synced state is
+    /// incomplete, so it's cleared to resync from genesis, and this returns `true`.  Known assets,
+    /// the app parameters, and the record of exported detection keys are kept.
+    ///
+    /// This must be called before the view service using this database is started.
+    pub async fn set_scan_filter(&self, filter: ScanFilter) -> anyhow::Result<bool> {
+        let current = self.scan_filter().await?;
+        if current == filter {
+            return Ok(false);
+        }
+        let resync =
+            current.requires_resync_for(&filter) && self.last_sync_height().await?.is_some();
+
+        let filter_bytes = serde_json::to_vec(&filter)?;
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            let mut lock = pool.get()?;
+            let dbtx = lock.transaction()?;
+
+            if resync {
+                dbtx.execute_batch(
+                    "DELETE FROM sct_hashes;
+                    DELETE FROM sct_commitments;
+                    UPDATE sct_position SET position = 0;
+                    UPDATE sct_forgotten SET forgotten = 0;
+                    DELETE FROM tx_by_nullifier;
+                    DELETE FROM tx;
+                    DELETE FROM notes;
+                    DELETE FROM spendable_notes;
+                    DELETE FROM swaps;
+                    DELETE FROM positions;
+                    UPDATE sync_height SET height = -1;",
+                )?;
+            }
+            dbtx.execute(
+                "INSERT INTO kv (k, v) VALUES ('scan_filter', ?1)
+                ON CONFLICT (k) DO UPDATE SET v = excluded.v",
+                [&filter_bytes[..]],
+            )?;
+            dbtx.commit()?;
+
+            anyhow::Ok(())
+        })
+        .await??;
+
+        if resync {
+            tracing::info!(
+                ?current,
+                ?filter,
+                "scan filter enables skipped sections, resyncing from genesis"
+            );
+            *self.uncommitted_height.lock() = None;
+        }
+
+        Ok(resync)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use penumbra_asset::STAKING_TOKEN_ASSET_ID;
+    use penumbra_compact_block::{CompactBlock, StatePayload};
+    use penumbra_dex::{swap::SwapPlaintext, BatchSwapOutputData};
+    use penumbra_fee::Fee;
+    use penumbra_keys::test_keys;
+    use rand_core::OsRng;
+
+    use super::*;
+
+    /// Scans and records `block` the same way the worker does.
+    async fn sync_block(storage: &Storage, block: CompactBlock) -> anyhow::Result<()> {
+        let fvk = &*test_keys::FULL_VIEWING_KEY;
+        let filter = storage.scan_filter().await?;
+        let mut sct = storage.state_commitment_tree().await?;
+        let filtered = crate::sync::scan_block(fvk, &mut sct, block, storage, &filter).await?;
+        // The node is only contacted when the block updates the app parameters.
+        let node = Url::parse("http://127.0.0.1:8080")?;
+        storage
+            .record_block(filtered, Vec::new(), &mut sct, node)
+            .await
+    }
+
+    #[tokio::test]
+    async fn skipped_swaps_are_found_after_the_filter_is_cleared() -> anyhow::Result<()> {
+        let fvk = &*test_keys::FULL_VIEWING_KEY;
+        let storage =
+            Storage::initialize(None::<&str>, fvk.clone(), AppParameters::default()).await?;
+        // Nothing has been synced yet, so there's nothing to resync.
+        assert!(!storage.set_scan_filter(ScanFilter::payments_only()).await?);
+
+        let gm = asset::Cache::with_known_assets()
+            .get_unit("gm")
+            .expect("gm is a known asset");
+        let trading_pair = TradingPair::new(*STAKING_TOKEN_ASSET_ID, gm.id());
+        let note = Note::generate(
+            &mut OsRng,
+            &test_keys::ADDRESS_0,
+            Value {
+                amount: 100u64.into(),
+                asset_id: *STAKING_TOKEN_ASSET_ID,
+            },
+        );
+        let swap = SwapPlaintext::new(
+            &mut OsRng,
+            trading_pair,
+            100u64.into(),
+            0u64.into(),
+            Fee::default(),
+            test_keys::ADDRESS_0.clone(),
+        );
+        let source = CommitmentSource::Transaction { id: Some([0; 32]) };
+        let block = CompactBlock {
+            height: 0,
+            state_payloads: vec![
+                StatePayload::Note {
+                    source: source.clone(),
+                    note: Box::new(note.payload()),
+                },
+                StatePayload::Swap {
+                    source,
+                    swap: Box::new(swap.encrypt(fvk.outgoing())),
+                },
+            ],
+            swap_outputs: [(
+                trading_pair,
+                BatchSwapOutputData {
+                    delta_1: 100u64.into(),
+                    delta_2: 0u64.into(),
+                    lambda_1: 0u64.into(),
+                    lambda_2: 50u64.into(),
+                    unfilled_1: 0u64.into(),
+                    unfilled_2: 0u64.into(),
+                    height: 0,
+                    trading_pair,
+                    epoch_starting_height: 0,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        sync_block(&storage, block.clone()).await?;
+        storage.note_by_commitment(note.commit(), false).await?;
+        assert!(storage.unclaimed_swaps().await?.is_empty());
+
+        // Scanning for swaps again forgets everything synced without them.
+        assert!(storage.set_scan_filter(ScanFilter::default()).await?);
+        assert_eq!(storage.last_sync_height().await?, None);
+        assert!(storage
+            .note_by_commitment(note.commit(), false)
+            .await
+            .is_err());
+
+        sync_block(&storage, block).await?;
+        storage.note_by_commitment(note.commit(), false).await?;
+        let swaps = storage.unclaimed_swaps().await?;
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].swap_commitment, swap.swap_commitment());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn revoking_a_key_that_was_never_exported_does_nothing() -> anyhow::Result<()> {
+        let fvk = &*test_keys::FULL_VIEWING_KEY;
+        let storage =
+            Storage::initialize(None::<&str>, fvk.clone(), AppParameters::default()).await?;
+        let exported = AddressIndex::from(1u32);
+        let never_exported = AddressIndex::from(2u32);
+
+        storage
+            .record_detection_key_export(exported, test_keys::ADDRESS_1.clone(), 1)
+            .await?;
+        assert!(!storage.revoke_detection_key(never_exported, 2).await?);
+        assert!(storage.revoke_detection_key(exported, 2).await?);
+        assert!(storage.detection_keys(false).await?.is_empty());
+        assert_eq!(storage.detection_keys(true).await?.len(), 1);
+
+        Ok(())
+    }
 }
@@ -128,3 +128,12 @@ CREATE TABLE positions (
      position_state         TEXT NOT NULL,
      trading_pair           TEXT NOT NULL
 );
+
+-- This table records the FMD detection keys handed out to third parties, by
+-- address index, so that they can be listed and revoked later.
+CREATE TABLE detection_keys (
+    address_index           BLOB PRIMARY KEY NOT NULL,
+    address                 BLOB NOT NULL,
+    exported_height         BIGINT NOT NULL,
+    revoked_height          BIGINT
+);
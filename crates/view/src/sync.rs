@@ -9,7 +9,7 @@ use penumbra_shielded_pool::{fmd, Note, NotePayload};
 use penumbra_tct::{self as tct, StateCommitment};
 use tracing::Instrument;
 
-use crate::{SpendableNoteRecord, Storage, SwapRecord};
+use crate::{ScanFilter, SpendableNoteRecord, Storage, SwapRecord};
 
 /// Contains the results of scanning a single block.
 #[derive(Debug, Clone)]
@@ -42,6 +42,7 @@ pub async fn scan_block(
         ..
     }: CompactBlock,
     storage: &Storage,
+    filter: &ScanFilter,
 ) -> anyhow::Result<FilteredBlock> {
     // Trial-decrypt a note with our own specific viewing key
     let trial_decrypt_note = |note_payload: NotePayload| -> tokio::task::JoinHandle<Option<Note>> {
@@ -78,7 +79,10 @@ pub async fn scan_block(
                 note_decryptions.push(trial_decrypt_note((**note).clone()));
             }
             StatePayload::Swap { swap, .. } => {
-                swap_decryptions.push(trial_decrypt_swap((**swap).clone()));
+                // Skipped swaps are treated like any other commitment that isn't ours.
+                if !filter.skip_swaps {
+                    swap_decryptions.push(trial_decrypt_swap((**swap).clone()));
+                }
             }
             StatePayload::RolledUp { commitment, .. } => unknown_commitments.push(*commitment),
         }
@@ -116,9 +120,16 @@ pub async fn scan_block(
             .expect("inserting a block root must succeed");
     } else {
         // If we found at least one note for us in this block, we have to explicitly construct the
-        // whole block in the SCT by inserting each commitment one at a time
+        // whole block in the SCT from all of its commitments
         tracing::debug!("found at least one relevant SCT entry, reconstructing block subtree");
 
+        // Every block starts on a block boundary, so the position of each commitment is determined
+        // by its index within the block
+        let block_start = state_commitment_tree
+            .position()
+            .expect("commitment tree must not be full");
+        let mut block_commitments = Vec::with_capacity(state_payloads.len());
+
         for payload in state_payloads.into_iter() {
             // We need to insert each commitment, so use a match statement to ensure we
             // exhaustively cover all possible cases.
@@ -128,9 +139,12 @@ pub async fn scan_block(
             ) {
                 (Some(note), None) => {
                     // Keep track of this commitment for later witnessing
-                    let position = state_commitment_tree
-                        .insert(tct::Witness::Keep, *payload.commitment())
-                        .expect("inserting a commitment must succeed");
+                    let position = tct::Position::from((
+                        block_start.epoch(),
+                        block_start.block(),
+                        u16::try_from(block_commitments.len())?,
+                    ));
+                    block_commitments.push((tct::Witness::Keep, *payload.commitment()));
 
                     let source = payload.source().clone();
                     let nullifier =
@@ -154,9 +168,12 @@ pub async fn scan_block(
                 }
                 (None, Some(swap)) => {
                     // Keep track of this commitment for later witnessing
-                    let position = state_commitment_tree
-                        .insert(tct::Witness::Keep, *payload.commitment())
-                        .expect("inserting a commitment must succeed");
+                    let position = tct::Position::from((
+                        block_start.epoch(),
+                        block_start.block(),
+                        u16::try_from(block_commitments.len())?,
+                    ));
+                    block_commitments.push((tct::Witness::Keep, *payload.commitment()));
 
                     let Some(output_data) = swap_outputs.get(&swap.trading_pair).cloned() else {
                         // We've been given an invalid compact block, but we
@@ -194,18 +211,17 @@ pub async fn scan_block(
                 }
                 (None, None) => {
                     // Don't remember this commitment; it wasn't ours
-                    state_commitment_tree
-                        .insert(tct::Witness::Forget, *payload.commitment())
-                        .expect("inserting a commitment must succeed");
+                    block_commitments.push((tct::Witness::Forget, *payload.commitment()));
                 }
                 (Some(_), Some(_)) => unreachable!("swap and note commitments are distinct"),
             }
         }
 
-        // End the block in the commitment tree
+        // Insert the whole block into the commitment tree at once, which is much faster than
+        // inserting each commitment individually
         state_commitment_tree
-            .end_block()
-            .expect("ending the block must succed");
+            .insert_block_commitments(block_commitments)
+            .expect("inserting a block must succeed");
     }
 
     // If we've also reached the end of the epoch, end the epoch in the commitment tree
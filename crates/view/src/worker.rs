@@ -33,13 +33,14 @@ use url::Url;
 
 use crate::{
     sync::{scan_block, FilteredBlock},
-    Storage,
+    ScanFilter, Storage,
 };
 
 pub struct Worker {
     storage: Storage,
     sct: Arc<RwLock<penumbra_tct::Tree>>,
     fvk: FullViewingKey, // TODO: notifications (see TODOs on ViewService)
+    scan_filter: ScanFilter,
     error_slot: Arc<Mutex<Option<anyhow::Error>>>,
     sync_height_tx: watch::Sender<u64>,
     /// Tonic channel used to create GRPC clients.
@@ -67,6 +68,10 @@ impl Worker {
         anyhow::Error,
     > {
         let fvk = storage.full_viewing_key().await?;
+        let scan_filter = storage.scan_filter().await?;
+        if !scan_filter.is_empty() {
+            tracing::info!(?scan_filter, "skipping filtered sections of compact blocks");
+        }
 
         // Create a shared, in-memory SCT.
         let sct = Arc::new(RwLock::new(storage.state_commitment_tree().await?));
@@ -89,6 +94,7 @@ impl Worker {
                 storage,
                 sct: sct.clone(),
                 fvk,
+                scan_filter,
                 error_slot: error_slot.clone(),
                 sync_height_tx,
                 channel,
@@ -219,7 +225,11 @@ impl Worker {
         });
 
         while let Some(block) = buffered_stream.recv().await {
-            let block: CompactBlock = block?.try_into()?;
+            let mut block: CompactBlock = block?.try_into()?;
+            if self.scan_filter.skip_governance {
+                // Don't scan blocks just because a proposal started in them.
+                block.proposal_started = false;
+            }
 
             let height = block.height;
 
@@ -242,15 +252,26 @@ impl Worker {
                 self.sync_height_tx.send(height)?;
             } else {
                 // Otherwise, scan the block and commit its changes:
-                let mut filtered_block =
-                    scan_block(&self.fvk, &mut sct_guard, block, &self.storage).await?;
+                let mut filtered_block = scan_block(
+                    &self.fvk,
+                    &mut sct_guard,
+                    block,
+                    &self.storage,
+                    &self.scan_filter,
+                )
+                .await?;
 
                 // Download any transactions we detected.
                 let transactions = self.fetch_transactions(&mut filtered_block).await?;
 
                 // LPNFT asset IDs won't be known to the chain, so we need to pre-populate them in the local
                 // registry based on transaction contents.
-                for transaction in &transactions {
+                let tracked_transactions = if self.scan_filter.skip_positions {
+                    &[][..]
+                } else {
+                    &transactions[..]
+                };
+                for transaction in tracked_transactions {
                     for action in transaction.actions() {
                         match action {
                             penumbra_transaction::Action::PositionOpen(position_open) => {
@@ -2,14 +2,18 @@ use anyhow::Result;
 
 use penumbra_custody::{AuthorizeRequest, CustodyClient};
 use penumbra_keys::FullViewingKey;
-use penumbra_transaction::{AuthorizationData, Transaction, TransactionPlan};
+use penumbra_transaction::{
+    plan::ProvingObserver, AuthorizationData, Transaction, TransactionPlan,
+};
 use penumbra_view::ViewClient;
 
+/// Authorizes and builds `plan`, reporting the progress of its proofs to `observer`.
 pub async fn build_transaction<V, C>(
     fvk: &FullViewingKey,
     view: &mut V,
     custody: &mut C,
     plan: TransactionPlan,
+    observer: &ProvingObserver,
 ) -> Result<Transaction>
 where
     V: ViewClient,
@@ -32,14 +36,14 @@ where
     // ... and then build the transaction:
     #[cfg(not(feature = "parallel"))]
     {
-        let tx = plan.build(fvk, &witness_data, &auth_data)?;
+        let tx = plan.build_with_observer(fvk, &witness_data, &auth_data, observer)?;
         return Ok(tx);
     }
 
     #[cfg(feature = "parallel")]
     {
         let tx = plan
-            .build_concurrent(fvk, &witness_data, &auth_data)
+            .build_concurrent_with_observer(fvk, &witness_data, &auth_data, observer)
             .await
             .map_err(|_| tonic::Status::failed_precondition("Error building transaction"))?;
 
@@ -47,7 +47,8 @@ cargo build --quiet --release --bin pd
 
 echo "Generating testnet config..."
 EPOCH_DURATION="${EPOCH_DURATION:-50}"
-UNBONDING_DELAY="${UNBONDING_DELAY:-50}"
+# Unbonding must take at least two epochs.
+UNBONDING_DELAY="${UNBONDING_DELAY:-$((EPOCH_DURATION * 2 + 1))}"
 cargo run --quiet --release --bin pd -- testnet generate --unbonding-delay "$UNBONDING_DELAY" --epoch-duration "$EPOCH_DURATION" --timeout-commit 500ms
 
 echo "Starting CometBFT..."